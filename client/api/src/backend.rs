@@ -19,7 +19,7 @@
 use scale_codec::{Decode, Encode};
 // Substrate
 use sp_core::{H160, H256};
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, NumberFor, Zero};
 // Frontier
 use fp_storage::EthereumStorageSchema;
 
@@ -58,6 +58,24 @@ pub trait Backend<Block: BlockT>: Send + Sync {
 
 	/// Get the hash of the latest substrate block fully indexed by the backend.
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String>;
+
+	/// Get the number and hash of the best canonical substrate block indexed by the backend,
+	/// such that all the canonical blocks below it are indexed as well, or `None` if the indexed
+	/// blocks don't reach genesis yet.
+	///
+	/// The backend lags behind the chain while the mapping-sync worker catches up, and the
+	/// blocks beyond this one may not be found by their ethereum hashes yet.
+	async fn latest_indexed_block(&self)
+		-> Result<Option<(NumberFor<Block>, Block::Hash)>, String>;
+
+	/// Get the number of the best canonical substrate block indexed by the backend, as reported
+	/// by `latest_indexed_block`, or zero if there is none.
+	async fn best_indexed_block(&self) -> Result<NumberFor<Block>, String> {
+		Ok(self
+			.latest_indexed_block()
+			.await?
+			.map_or_else(Zero::zero, |(number, _)| number))
+	}
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -77,6 +95,9 @@ pub trait LogIndexerBackend<Block: BlockT>: Send + Sync {
 	fn is_indexed(&self) -> bool;
 
 	/// Filter the logs by the parameters.
+	///
+	/// The logs returned are candidates to match exactly against the filter. A backend bounding
+	/// the number of candidates fails rather than returning a truncated list.
 	async fn filter_logs(
 		&self,
 		from_block: u64,
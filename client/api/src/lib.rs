@@ -19,5 +19,6 @@
 #![warn(unused_crate_dependencies)]
 
 pub mod backend;
+pub mod log_filter;
 
-pub use self::backend::*;
+pub use self::{backend::*, log_filter::*};
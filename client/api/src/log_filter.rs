@@ -0,0 +1,181 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Log filter matching shared by the log backends and their users.
+//!
+//! The KV backend matches every log of the scanned blocks, while the SQL backend pushes the
+//! addresses and topics it can into its query and its candidate logs are then matched the same
+//! way. Both go through [`LogFilter::block_logs`], so they return identical results.
+
+use std::collections::BTreeSet;
+
+use sp_core::{H160, H256};
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fp_rpc::TransactionStatus;
+
+use crate::FilteredLog;
+
+/// The number of topic columns of the SQL backend.
+const INDEXED_TOPIC_COUNT: usize = 4;
+
+/// Matches logs against the addresses and topics of a filter.
+#[derive(Clone, Debug)]
+pub struct LogFilter {
+	addresses: Option<Vec<H160>>,
+	/// The topic combinations of the filter, `None` if it has no topics. A log matches when it
+	/// matches any of them, and a wildcard (`None`) position matches any topic.
+	topics: Option<Vec<Vec<Option<H256>>>>,
+}
+
+impl LogFilter {
+	/// A filter matching the logs emitted by one of the addresses, with topics matching one of
+	/// the topic combinations. No addresses, or no combinations, match any log.
+	pub fn new(addresses: Vec<H160>, topics: Vec<Vec<Option<H256>>>) -> Self {
+		Self {
+			addresses: if addresses.is_empty() {
+				None
+			} else {
+				Some(addresses)
+			},
+			topics: if topics.is_empty() {
+				None
+			} else {
+				Some(topics)
+			},
+		}
+	}
+
+	/// Whether a log with the given address and topics matches the filter.
+	pub fn matches(&self, address: &H160, topics: &[H256]) -> bool {
+		let address_matches = self
+			.addresses
+			.as_ref()
+			.is_none_or(|addresses| addresses.contains(address));
+		let topics_match = self.topics.as_ref().is_none_or(|combinations| {
+			combinations.iter().any(|combination| {
+				combination
+					.iter()
+					.enumerate()
+					.all(|(position, expected)| match expected {
+						Some(expected) => topics.get(position) == Some(expected),
+						None => true,
+					})
+			})
+		});
+		address_matches && topics_match
+	}
+
+	/// The addresses and topics the SQL backend can filter on.
+	///
+	/// They select a superset of the matching logs: a topic position is only constrained when
+	/// every topic combination of the filter constrains it. The candidates are then matched
+	/// with [`LogFilter::matches`] by [`LogFilter::block_logs`].
+	pub fn indexer_input(&self) -> (Vec<H160>, Vec<Vec<Option<H256>>>) {
+		let addresses = self.addresses.clone().unwrap_or_default();
+
+		let mut topics = Vec::new();
+		if let Some(combinations) = &self.topics {
+			for position in 0..INDEXED_TOPIC_COUNT {
+				let options = combinations
+					.iter()
+					.map(|combination| combination.get(position).copied().flatten())
+					.collect::<Option<BTreeSet<H256>>>();
+				// Each topic combination given to the backend constrains a single position.
+				for topic in options.into_iter().flatten() {
+					let mut combination = vec![None; position];
+					combination.push(Some(topic));
+					topics.push(combination);
+				}
+			}
+		}
+
+		(addresses, topics)
+	}
+
+	/// Collect the logs of a block that match the filter, in block order.
+	///
+	/// Only the logs for which `is_candidate(transaction_index, transaction_log_index)` holds are
+	/// matched.
+	pub fn block_logs<'a>(
+		&self,
+		transaction_statuses: &'a [TransactionStatus],
+		mut is_candidate: impl FnMut(u32, u32) -> bool,
+	) -> Vec<BlockLog<'a>> {
+		let mut logs = Vec::new();
+		let mut log_index: u32 = 0;
+		for status in transaction_statuses {
+			for (transaction_log_index, log) in status.logs.iter().enumerate() {
+				let transaction_log_index = transaction_log_index as u32;
+				if is_candidate(status.transaction_index, transaction_log_index)
+					&& self.matches(&log.address, &log.topics)
+				{
+					logs.push(BlockLog {
+						status,
+						log,
+						log_index,
+						transaction_log_index,
+					});
+				}
+				log_index += 1;
+			}
+		}
+		logs
+	}
+}
+
+/// A log of a block matching a filter.
+pub struct BlockLog<'a> {
+	/// The status of the transaction emitting the log.
+	pub status: &'a TransactionStatus,
+	/// The log itself.
+	pub log: &'a ethereum::Log,
+	/// The index of the log in its block.
+	pub log_index: u32,
+	/// The index of the log in its transaction.
+	pub transaction_log_index: u32,
+}
+
+/// The candidate logs of a block returned by the SQL backend.
+pub struct IndexedBlockLogs<B: BlockT> {
+	pub substrate_block_hash: B::Hash,
+	pub ethereum_block_hash: H256,
+	pub block_number: u32,
+	/// The `(transaction_index, transaction_log_index)` of the candidate logs.
+	pub candidates: BTreeSet<(u32, u32)>,
+}
+
+/// Group the candidate logs returned by the SQL backend by block, keeping the block order.
+pub fn group_indexed_logs<B: BlockT>(logs: &[FilteredLog<B>]) -> Vec<IndexedBlockLogs<B>> {
+	let mut blocks: Vec<IndexedBlockLogs<B>> = Vec::new();
+	for log in logs {
+		let candidate = (log.transaction_index, log.log_index);
+		match blocks.last_mut() {
+			Some(block) if block.substrate_block_hash == log.substrate_block_hash => {
+				block.candidates.insert(candidate);
+			}
+			_ => blocks.push(IndexedBlockLogs {
+				substrate_block_hash: log.substrate_block_hash,
+				ethereum_block_hash: log.ethereum_block_hash,
+				block_number: log.block_number,
+				candidates: BTreeSet::from([candidate]),
+			}),
+		}
+	}
+	blocks
+}
@@ -171,6 +171,7 @@ where
 					.to_string()
 					.into())
 			}
+			Operation::Reset => unreachable!("rejected by `FrontierDbCmd::run`"),
 		}
 		Ok(())
 	}
@@ -147,6 +147,7 @@ impl<'a, B: BlockT, C: HeaderBackend<B>> MetaDb<'a, B, C> {
 					self.backend.meta().write_ethereum_schema(vec![])?;
 				}
 			},
+			Operation::Reset => unreachable!("rejected by `FrontierDbCmd::run`"),
 		}
 		Ok(())
 	}
@@ -18,6 +18,8 @@
 
 mod mapping_db;
 mod meta_db;
+#[cfg(feature = "sql")]
+mod sql_db;
 #[cfg(test)]
 mod tests;
 pub(crate) mod utils;
@@ -43,7 +45,10 @@ use self::{
 pub struct FrontierDbCmd {
 	/// Specify the operation to perform.
 	///
-	/// Can be one of `create | read | update | delete`.
+	/// Can be one of `create | read | update | delete | reset`.
+	///
+	/// `reset` is only supported by the SQL backend, on the `block` column with a block number
+	/// as key. It deletes the indexed blocks above that block, to be indexed again.
 	#[arg(value_enum, ignore_case = true, required = true)]
 	pub operation: Operation,
 
@@ -66,6 +71,10 @@ pub struct FrontierDbCmd {
 	#[arg(long)]
 	pub value: Option<PathBuf>,
 
+	/// Reset the SQL index even if the key isn't an indexed canonical block.
+	#[arg(long)]
+	pub force: bool,
+
 	/// Shared parameters
 	#[command(flatten)]
 	pub shared_params: SharedParams,
@@ -81,6 +90,7 @@ pub enum Operation {
 	Read,
 	Update,
 	Delete,
+	Reset,
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -108,6 +118,9 @@ impl FrontierDbCmd {
 		C: HeaderBackend<B> + ProvideRuntimeApi<B>,
 		C::Api: fp_rpc::EthereumRuntimeRPCApi<B>,
 	{
+		if let Operation::Reset = self.operation {
+			return Err("Reset operation is only supported by the SQL backend".into());
+		}
 		match self.column {
 			Column::Meta => {
 				// New meta db handler
@@ -148,6 +161,12 @@ impl FrontierDbCmd {
 		}
 		Ok(())
 	}
+
+	/// Runs the command against the SQL backend, which only supports the `reset` operation.
+	#[cfg(feature = "sql")]
+	pub fn run_sql<B: BlockT>(&self, backend: Arc<fc_db::sql::Backend<B>>) -> sc_cli::Result<()> {
+		sql_db::SqlDb::new(self, backend).query(&self.column, &self.key)
+	}
 }
 
 impl sc_cli::CliConfiguration for FrontierDbCmd {
@@ -0,0 +1,65 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+// Substrate
+use sp_runtime::traits::Block as BlockT;
+
+use super::{utils::FrontierDbMessage, Column, FrontierDbCmd, Operation};
+
+pub struct SqlDb<'a, B: BlockT> {
+	cmd: &'a FrontierDbCmd,
+	backend: Arc<fc_db::sql::Backend<B>>,
+}
+
+impl<'a, B: BlockT> SqlDb<'a, B> {
+	pub fn new(cmd: &'a FrontierDbCmd, backend: Arc<fc_db::sql::Backend<B>>) -> Self {
+		Self { cmd, backend }
+	}
+
+	pub fn query(&self, column: &Column, key: &str) -> sc_cli::Result<()> {
+		match (&self.cmd.operation, column) {
+			// Reset the index to the block number given as key.
+			(Operation::Reset, Column::Block) => {
+				let block_number: u32 = key
+					.parse()
+					.map_err(|_| format!("Key `{}` is not a block number", key))?;
+				self.confirmation_prompt(
+					&self.cmd.operation,
+					block_number,
+					&"indexed blocks above it",
+					&"deleted, to be indexed again",
+				)?;
+				futures::executor::block_on(
+					self.backend.reset_to_block(block_number, self.cmd.force),
+				)
+				.map_err(|err| format!("Failed to reset the index: {:?}", err))?;
+			}
+			(Operation::Reset, _) => return Err(self.key_column_error(key, column)),
+			_ => {
+				return Err("Only the reset operation is supported by the SQL backend"
+					.to_string()
+					.into())
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<'a, B: BlockT> FrontierDbMessage for SqlDb<'a, B> {}
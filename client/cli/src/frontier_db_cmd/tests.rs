@@ -79,6 +79,7 @@ fn cmd(key: String, value: Option<PathBuf>, operation: Operation, column: Column
 		column,
 		key,
 		value,
+		force: false,
 		shared_params: sc_cli::SharedParams {
 			chain: None,
 			dev: true,
@@ -270,6 +271,22 @@ fn schema_delete_works() {
 	assert_eq!(backend.meta().ethereum_schema(), Ok(Some(vec![])));
 }
 
+#[test]
+fn reset_is_not_supported_by_the_key_value_backend() {
+	let tmp = tempdir().expect("create a temporary directory");
+	// Test client.
+	let (client, _) = TestClientBuilder::new().build_with_native_executor::<RuntimeApi, _>(None);
+	let client = Arc::new(client);
+	// Create a temporary frontier secondary DB.
+	let backend = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.into_path())
+		.expect("a temporary db was created");
+
+	// Run the command, the key isn't parsed as a hash.
+	assert!(cmd("1".to_string(), None, Operation::Reset, Column::Block)
+		.run(client, backend)
+		.is_err());
+}
+
 #[test]
 fn tips_create_success_if_value_is_empty() {
 	let tmp = tempdir().expect("create a temporary directory");
@@ -0,0 +1,78 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Look up an Ethereum transaction or block hash in the key-value Frontier database of a node.
+//!
+//! ```text
+//! cargo run -p fc-client --example inspect_db -- <db path> <hash>
+//! ```
+//!
+//! The database path is the `frontier/db` directory of the node's base path. The node can keep
+//! running, the database is opened as a read-only secondary instance.
+
+use std::path::PathBuf;
+
+use ethereum_types::H256;
+use sp_runtime::{generic, traits::BlakeTwo256, OpaqueExtrinsic};
+
+use fc_client::{Error, OfflineDb};
+
+type Block = generic::Block<generic::Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+
+fn parse_hash(input: &str) -> Option<H256> {
+	let input = input.strip_prefix("0x").unwrap_or(input);
+	if input.len() != 64 {
+		return None;
+	}
+	let mut bytes = [0u8; 32];
+	for (i, byte) in bytes.iter_mut().enumerate() {
+		*byte = u8::from_str_radix(input.get(2 * i..2 * i + 2)?, 16).ok()?;
+	}
+	Some(H256(bytes))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+	let mut args = std::env::args().skip(1);
+	let (Some(path), Some(hash)) = (args.next(), args.next().as_deref().and_then(parse_hash))
+	else {
+		eprintln!("usage: inspect_db <db path> <transaction or block hash>");
+		std::process::exit(1);
+	};
+
+	let path = PathBuf::from(path);
+	let secondary_path = std::env::temp_dir().join("fc-client-inspect-db");
+	let db = OfflineDb::<Block>::open_key_value(&path, &secondary_path)?;
+
+	for metadata in db.transaction_metadata(hash).await? {
+		println!(
+			"transaction {:?}: index {} of Ethereum block {:?}, in Substrate block {:?}",
+			hash,
+			metadata.ethereum_index,
+			metadata.ethereum_block_hash,
+			metadata.substrate_block_hash,
+		);
+	}
+	for substrate_block_hash in db.substrate_block_hashes(hash).await? {
+		println!(
+			"Ethereum block {:?} in Substrate block {:?}",
+			hash, substrate_block_hash
+		);
+	}
+	Ok(())
+}
@@ -0,0 +1,132 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use ethereum_types::{Bloom, BloomInput, H160, H256};
+// Substrate
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fp_rpc::TransactionStatus;
+
+/// A log filter over a range of canonical blocks.
+#[derive(Clone, Debug, Default)]
+pub struct LogFilter {
+	/// The first block of the range.
+	pub from_block: u64,
+	/// The last block of the range, included.
+	pub to_block: u64,
+	/// The log must be emitted by one of these addresses. Empty matches any address.
+	pub addresses: Vec<H160>,
+	/// The topic at each position must be one of the given topics. `None`, an empty list or a
+	/// missing position matches any topic.
+	pub topics: Vec<Option<Vec<H256>>>,
+}
+
+impl LogFilter {
+	/// The matcher of the addresses and topics of the filter, whose topic combinations are the
+	/// products of the options of each position.
+	pub(crate) fn matcher(&self) -> fc_api::LogFilter {
+		let mut combinations = vec![Vec::new()];
+		for expected in &self.topics {
+			combinations = match expected {
+				Some(expected) if !expected.is_empty() => combinations
+					.iter()
+					.flat_map(|combination| {
+						expected.iter().map(move |topic| {
+							let mut combination = combination.clone();
+							combination.push(Some(*topic));
+							combination
+						})
+					})
+					.collect(),
+				_ => combinations
+					.into_iter()
+					.map(|mut combination| {
+						combination.push(None);
+						combination
+					})
+					.collect(),
+			};
+		}
+		// Only wildcards match any topic.
+		combinations.retain(|combination| combination.iter().any(Option::is_some));
+		fc_api::LogFilter::new(self.addresses.clone(), combinations)
+	}
+
+	/// Whether a block with the given logs bloom may contain matching logs.
+	pub(crate) fn may_match(&self, bloom: &Bloom) -> bool {
+		let contains = |input: &[u8]| bloom.contains_input(BloomInput::Raw(input));
+		let address_may_match = self.addresses.is_empty()
+			|| self
+				.addresses
+				.iter()
+				.any(|address| contains(address.as_bytes()));
+		let topics_may_match = self.topics.iter().flatten().all(|expected| {
+			expected.is_empty() || expected.iter().any(|topic| contains(topic.as_bytes()))
+		});
+		address_may_match && topics_may_match
+	}
+}
+
+/// Collect the logs of a block that match the filter, in block order.
+///
+/// Only the logs for which `is_candidate(transaction_index, transaction_log_index)` holds are
+/// matched.
+pub(crate) fn block_log_entries<B: BlockT>(
+	matcher: &fc_api::LogFilter,
+	substrate_block_hash: B::Hash,
+	ethereum_block_hash: H256,
+	block_number: u64,
+	transaction_statuses: &[TransactionStatus],
+	is_candidate: impl FnMut(u32, u32) -> bool,
+) -> Vec<LogEntry<B>> {
+	matcher
+		.block_logs(transaction_statuses, is_candidate)
+		.into_iter()
+		.map(|block_log| LogEntry {
+			substrate_block_hash,
+			ethereum_block_hash,
+			block_number,
+			transaction_hash: block_log.status.transaction_hash,
+			transaction_index: block_log.status.transaction_index,
+			log_index: block_log.log_index,
+			transaction_log_index: block_log.transaction_log_index,
+			log: block_log.log.clone(),
+		})
+		.collect()
+}
+
+/// A log of the canonical chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry<B: BlockT> {
+	/// The hash of the Substrate block including the log.
+	pub substrate_block_hash: B::Hash,
+	/// The hash of the Ethereum block including the log.
+	pub ethereum_block_hash: H256,
+	/// The number of the Ethereum block including the log.
+	pub block_number: u64,
+	/// The hash of the transaction emitting the log.
+	pub transaction_hash: H256,
+	/// The index of the transaction in its block.
+	pub transaction_index: u32,
+	/// The index of the log in its block.
+	pub log_index: u32,
+	/// The index of the log in its transaction.
+	pub transaction_log_index: u32,
+	/// The log itself.
+	pub log: ethereum::Log,
+}
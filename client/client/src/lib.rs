@@ -0,0 +1,254 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Read-only access to the Frontier backend for external tooling, such as custom indexers and
+//! analytics jobs.
+//!
+//! [`FrontierClient`] wraps the handles of a node: its client, its Frontier backend and its
+//! storage override. [`OfflineDb`] opens the Frontier database of a node that is not running,
+//! and only exposes what the database itself stores.
+//!
+//! The types exposed by this crate are meant to stay stable while the backend internals change.
+
+#![warn(unused_crate_dependencies)]
+
+mod filter;
+#[cfg(any(feature = "rocksdb", feature = "sql"))]
+mod offline;
+
+pub use self::filter::{LogEntry, LogFilter};
+#[cfg(any(feature = "rocksdb", feature = "sql"))]
+pub use self::offline::{IndexedLog, OfflineDb};
+pub use ethereum::{BlockV3 as EthereumBlock, ReceiptV4 as EthereumReceipt};
+pub use fc_api::TransactionMetadata;
+pub use fp_rpc::TransactionStatus;
+
+use std::sync::Arc;
+
+use ethereum_types::H256;
+// Substrate
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+// Frontier
+use fc_storage::StorageOverride;
+
+use self::filter::block_log_entries;
+
+// The dev-dependencies are used by the integration tests.
+#[cfg(test)]
+use {
+	fp_ethereum as _, fp_storage as _, scale_codec as _, sp_core as _,
+	substrate_test_runtime_client as _, tempfile as _, tokio as _,
+};
+
+/// Errors of the Frontier client.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The Frontier backend failed.
+	#[error("frontier backend error: {0}")]
+	Backend(String),
+	/// The Substrate blockchain backend failed.
+	#[error("blockchain error: {0}")]
+	Blockchain(#[from] sp_blockchain::Error),
+	/// The operation is not supported by the opened database.
+	#[error("unsupported operation: {0}")]
+	Unsupported(&'static str),
+}
+
+/// An Ethereum block of the canonical chain.
+#[derive(Clone, Debug)]
+pub struct BlockEntry<B: BlockT> {
+	/// The hash of the Substrate block including the Ethereum block.
+	pub substrate_block_hash: B::Hash,
+	/// The Ethereum block.
+	pub block: EthereumBlock,
+}
+
+/// Read-only access to the Frontier data of a node.
+pub struct FrontierClient<B: BlockT, C> {
+	client: Arc<C>,
+	backend: Arc<dyn fc_api::Backend<B>>,
+	storage_override: Arc<dyn StorageOverride<B>>,
+}
+
+impl<B: BlockT, C> Clone for FrontierClient<B, C> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			storage_override: self.storage_override.clone(),
+		}
+	}
+}
+
+impl<B, C> FrontierClient<B, C>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + 'static,
+{
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<dyn fc_api::Backend<B>>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			storage_override,
+		}
+	}
+
+	/// The hash of the canonical Substrate block including the given Ethereum block.
+	pub async fn substrate_block_hash(
+		&self,
+		ethereum_block_hash: H256,
+	) -> Result<Option<B::Hash>, Error> {
+		let substrate_hashes = self
+			.backend
+			.block_hash(&ethereum_block_hash)
+			.await
+			.map_err(Error::Backend)?
+			.unwrap_or_default();
+		Ok(substrate_hashes
+			.into_iter()
+			.find(|hash| self.is_canon(*hash)))
+	}
+
+	/// The Ethereum block included in the given Substrate block.
+	pub fn block(&self, substrate_block_hash: B::Hash) -> Option<EthereumBlock> {
+		self.storage_override.current_block(substrate_block_hash)
+	}
+
+	/// The transaction statuses of the Ethereum block included in the given Substrate block.
+	pub fn transaction_statuses(
+		&self,
+		substrate_block_hash: B::Hash,
+	) -> Option<Vec<TransactionStatus>> {
+		self.storage_override
+			.current_transaction_statuses(substrate_block_hash)
+	}
+
+	/// The receipts of the Ethereum block included in the given Substrate block.
+	pub fn receipts(&self, substrate_block_hash: B::Hash) -> Option<Vec<EthereumReceipt>> {
+		self.storage_override.current_receipts(substrate_block_hash)
+	}
+
+	/// Iterate the canonical Ethereum blocks from `from` to `to` included.
+	///
+	/// Heights without an Ethereum block are skipped, and the iteration stops at the best block.
+	pub fn blocks(
+		&self,
+		from: u64,
+		to: u64,
+	) -> impl Iterator<Item = Result<BlockEntry<B>, Error>> + '_ {
+		(from..=to)
+			.map_while(move |number| {
+				self.client
+					.hash(number.unique_saturated_into())
+					.map_err(Error::from)
+					.transpose()
+			})
+			.filter_map(move |hash| match hash {
+				Ok(substrate_block_hash) => self.block(substrate_block_hash).map(|block| {
+					Ok(BlockEntry {
+						substrate_block_hash,
+						block,
+					})
+				}),
+				Err(err) => Some(Err(err)),
+			})
+	}
+
+	/// The logs of the canonical chain matching the filter, in chain order.
+	///
+	/// The log index of the backend is used when available, the blocks of the range are scanned
+	/// otherwise.
+	pub async fn logs(&self, filter: &LogFilter) -> Result<Vec<LogEntry<B>>, Error> {
+		let matcher = filter.matcher();
+		let log_indexer = self.backend.log_indexer();
+		let mut logs = Vec::new();
+		if log_indexer.is_indexed() {
+			let (addresses, topics) = matcher.indexer_input();
+			let candidates = log_indexer
+				.filter_logs(filter.from_block, filter.to_block, addresses, topics)
+				.await
+				.map_err(Error::Backend)?;
+			for block in fc_api::group_indexed_logs(&candidates) {
+				let statuses = self
+					.transaction_statuses(block.substrate_block_hash)
+					.unwrap_or_default();
+				logs.extend(block_log_entries::<B>(
+					&matcher,
+					block.substrate_block_hash,
+					block.ethereum_block_hash,
+					block.block_number.into(),
+					&statuses,
+					|transaction_index, transaction_log_index| {
+						block
+							.candidates
+							.contains(&(transaction_index, transaction_log_index))
+					},
+				));
+			}
+		} else {
+			for entry in self.blocks(filter.from_block, filter.to_block) {
+				let BlockEntry {
+					substrate_block_hash,
+					block,
+				} = entry?;
+				if !filter.may_match(&block.header.logs_bloom) {
+					continue;
+				}
+				let statuses = self
+					.transaction_statuses(substrate_block_hash)
+					.unwrap_or_default();
+				logs.extend(block_log_entries::<B>(
+					&matcher,
+					substrate_block_hash,
+					block.header.hash(),
+					block.header.number.low_u64(),
+					&statuses,
+					|_, _| true,
+				));
+			}
+		}
+		Ok(logs)
+	}
+
+	/// The metadata of a transaction included in the canonical chain.
+	pub async fn transaction_metadata(
+		&self,
+		ethereum_transaction_hash: H256,
+	) -> Result<Option<TransactionMetadata<B>>, Error> {
+		let metadata = self
+			.backend
+			.transaction_metadata(&ethereum_transaction_hash)
+			.await
+			.map_err(Error::Backend)?;
+		Ok(metadata
+			.into_iter()
+			.find(|meta| self.is_canon(meta.substrate_block_hash)))
+	}
+
+	fn is_canon(&self, hash: B::Hash) -> bool {
+		match self.client.number(hash) {
+			Ok(Some(number)) => self.client.hash(number).ok().flatten() == Some(hash),
+			_ => false,
+		}
+	}
+}
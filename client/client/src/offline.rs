@@ -0,0 +1,204 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#[cfg(feature = "rocksdb")]
+use std::path::Path;
+
+use ethereum_types::H256;
+// Substrate
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fc_api::TransactionMetadata;
+
+use crate::{Error, LogFilter};
+
+/// The position of a log matching a filter, as stored by the log index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedLog<B: BlockT> {
+	/// The hash of the Substrate block including the log.
+	pub substrate_block_hash: B::Hash,
+	/// The hash of the Ethereum block including the log.
+	pub ethereum_block_hash: H256,
+	/// The number of the Ethereum block including the log.
+	pub block_number: u64,
+	/// The index of the transaction in its block.
+	pub transaction_index: u32,
+	/// The index of the log in its transaction.
+	pub transaction_log_index: u32,
+}
+
+enum Inner<B: BlockT<Hash = H256>> {
+	#[cfg(feature = "rocksdb")]
+	KeyValue(fc_db::kv::MappingDb<B>),
+	#[cfg(feature = "sql")]
+	Sql(fc_db::sql::Backend<B>),
+}
+
+/// Read-only access to the Frontier database of a node that is not running.
+///
+/// Without the Substrate database, the canonical chain is unknown: lookups return the entries
+/// of every known fork, and the Ethereum blocks themselves are not available.
+pub struct OfflineDb<B: BlockT<Hash = H256>> {
+	inner: Inner<B>,
+}
+
+impl<B: BlockT<Hash = H256>> OfflineDb<B> {
+	/// Open the key-value database at `path`.
+	///
+	/// The database is opened as a secondary instance keeping its own state in
+	/// `secondary_path`, so it can also be opened while the node is running.
+	#[cfg(feature = "rocksdb")]
+	pub fn open_key_value(path: &Path, secondary_path: &Path) -> Result<Self, Error> {
+		let db =
+			fc_db::kv::MappingDb::open_read_only(path, secondary_path).map_err(Error::Backend)?;
+		Ok(Self {
+			inner: Inner::KeyValue(db),
+		})
+	}
+
+	/// Open the SQL database at `path`, e.g. `sqlite:///path/to/frontier.db3`.
+	///
+	/// Must be called from within a Tokio runtime.
+	#[cfg(feature = "sql")]
+	pub async fn open_sql(path: &str) -> Result<Self, Error> {
+		let backend = fc_db::sql::Backend::open_read_only(
+			path,
+			1,
+			None,
+			std::sync::Arc::new(sql::NoStorageOverride),
+		)
+		.map_err(|err| Error::Backend(err.to_string()))?;
+		Ok(Self {
+			inner: Inner::Sql(backend),
+		})
+	}
+
+	/// The hashes of the Substrate blocks including the given Ethereum block, on any fork.
+	pub async fn substrate_block_hashes(
+		&self,
+		ethereum_block_hash: H256,
+	) -> Result<Vec<B::Hash>, Error> {
+		let hashes = match &self.inner {
+			#[cfg(feature = "rocksdb")]
+			Inner::KeyValue(db) => db.block_hash(&ethereum_block_hash),
+			#[cfg(feature = "sql")]
+			Inner::Sql(backend) => fc_api::Backend::block_hash(backend, &ethereum_block_hash).await,
+		};
+		Ok(hashes.map_err(Error::Backend)?.unwrap_or_default())
+	}
+
+	/// The metadata of a transaction, on any fork.
+	pub async fn transaction_metadata(
+		&self,
+		ethereum_transaction_hash: H256,
+	) -> Result<Vec<TransactionMetadata<B>>, Error> {
+		match &self.inner {
+			#[cfg(feature = "rocksdb")]
+			Inner::KeyValue(db) => db.transaction_metadata(&ethereum_transaction_hash),
+			#[cfg(feature = "sql")]
+			Inner::Sql(backend) => {
+				fc_api::Backend::transaction_metadata(backend, &ethereum_transaction_hash).await
+			}
+		}
+		.map_err(Error::Backend)
+	}
+
+	/// The positions of the canonical logs matching the filter, in chain order.
+	///
+	/// Only the SQL database indexes logs, and it stores the first four topics of each log.
+	pub async fn logs(&self, filter: &LogFilter) -> Result<Vec<IndexedLog<B>>, Error> {
+		match &self.inner {
+			#[cfg(feature = "rocksdb")]
+			Inner::KeyValue(_) => Err(Error::Unsupported(
+				"the key-value database does not index logs",
+			)),
+			#[cfg(feature = "sql")]
+			Inner::Sql(backend) => {
+				let (addresses, topics) = filter.matcher().indexer_input();
+				let logs = fc_api::LogIndexerBackend::filter_logs(
+					backend,
+					filter.from_block,
+					filter.to_block,
+					addresses,
+					topics,
+				)
+				.await
+				.map_err(Error::Backend)?;
+				Ok(logs
+					.into_iter()
+					.map(|log| IndexedLog {
+						substrate_block_hash: log.substrate_block_hash,
+						ethereum_block_hash: log.ethereum_block_hash,
+						block_number: log.block_number.into(),
+						transaction_index: log.transaction_index,
+						transaction_log_index: log.log_index,
+					})
+					.collect())
+			}
+		}
+	}
+}
+
+#[cfg(feature = "sql")]
+mod sql {
+	use ethereum_types::{Address, H256, U256};
+	// Substrate
+	use sp_runtime::{traits::Block as BlockT, Permill};
+	// Frontier
+	use fc_storage::StorageOverride;
+	use fp_rpc::TransactionStatus;
+
+	/// The SQL backend only reads the runtime storage when indexing, which an offline database
+	/// never does.
+	pub(super) struct NoStorageOverride;
+
+	impl<B: BlockT> StorageOverride<B> for NoStorageOverride {
+		fn account_code_at(&self, _at: B::Hash, _address: Address) -> Option<Vec<u8>> {
+			None
+		}
+
+		fn account_storage_at(
+			&self,
+			_at: B::Hash,
+			_address: Address,
+			_index: U256,
+		) -> Option<H256> {
+			None
+		}
+
+		fn current_block(&self, _at: B::Hash) -> Option<ethereum::BlockV3> {
+			None
+		}
+
+		fn current_receipts(&self, _at: B::Hash) -> Option<Vec<ethereum::ReceiptV4>> {
+			None
+		}
+
+		fn current_transaction_statuses(&self, _at: B::Hash) -> Option<Vec<TransactionStatus>> {
+			None
+		}
+
+		fn elasticity(&self, _at: B::Hash) -> Option<Permill> {
+			None
+		}
+
+		fn is_eip1559(&self, _at: B::Hash) -> bool {
+			false
+		}
+	}
+}
@@ -0,0 +1,260 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "rocksdb")]
+
+use std::sync::Arc;
+
+use ethereum_types::{H160, H256, H64, U256};
+use scale_codec::Encode;
+// Substrate
+use sp_blockchain::HeaderBackend;
+use sp_core::hashing::twox_128;
+use substrate_test_runtime_client::{
+	DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+};
+use tempfile::tempdir;
+// Frontier
+use fc_client::{
+	Error, EthereumBlock, EthereumReceipt, FrontierClient, LogFilter, OfflineDb,
+	TransactionMetadata, TransactionStatus,
+};
+use fc_storage::SchemaV3StorageOverride;
+use fp_storage::{constants::*, EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
+
+type OpaqueBlock = substrate_test_runtime_client::runtime::Block;
+
+fn storage_key(item: &[u8]) -> Vec<u8> {
+	[twox_128(PALLET_ETHEREUM), twox_128(item)].concat()
+}
+
+fn ethereum_log(address: H160, topics: Vec<H256>) -> ethereum::Log {
+	ethereum::Log {
+		address,
+		topics,
+		data: vec![0x42],
+	}
+}
+
+/// The Ethereum data stored by the genesis block of the test chain.
+fn genesis_data() -> (EthereumBlock, Vec<EthereumReceipt>, Vec<TransactionStatus>) {
+	let logs = vec![
+		ethereum_log(H160::repeat_byte(0x1), vec![H256::repeat_byte(0xa)]),
+		ethereum_log(H160::repeat_byte(0x2), vec![H256::repeat_byte(0xb)]),
+		ethereum_log(
+			H160::repeat_byte(0x3),
+			vec![H256::repeat_byte(0xa), H256::repeat_byte(0xc)],
+		),
+	];
+	let logs_bloom = fp_ethereum::logs_bloom(&logs);
+	let partial_header = ethereum::PartialHeader {
+		parent_hash: H256::default(),
+		beneficiary: H160::default(),
+		state_root: H256::default(),
+		receipts_root: H256::default(),
+		logs_bloom,
+		difficulty: U256::zero(),
+		number: U256::zero(),
+		gas_limit: U256::zero(),
+		gas_used: U256::zero(),
+		timestamp: 0u64,
+		extra_data: Vec::new(),
+		mix_hash: H256::default(),
+		nonce: H64::default(),
+	};
+	let block = ethereum::Block::new(partial_header, vec![], vec![]);
+	let receipts = vec![ethereum::ReceiptV4::Legacy(ethereum::EIP658ReceiptData {
+		status_code: 1,
+		used_gas: U256::from(21_000),
+		logs_bloom,
+		logs: logs.clone(),
+	})];
+	let statuses = vec![TransactionStatus {
+		transaction_hash: H256::repeat_byte(0x77),
+		transaction_index: 0,
+		logs,
+		logs_bloom,
+		..Default::default()
+	}];
+	(block, receipts, statuses)
+}
+
+#[tokio::test]
+async fn facade_reads_test_chain_data() {
+	let tmp = tempdir().expect("create a temporary directory");
+	let (block, receipts, statuses) = genesis_data();
+	let (client, _) = TestClientBuilder::new()
+		.add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			EthereumStorageSchema::V3.encode(),
+		)
+		.add_extra_storage(storage_key(ETHEREUM_CURRENT_BLOCK), block.encode())
+		.add_extra_storage(storage_key(ETHEREUM_CURRENT_RECEIPTS), receipts.encode())
+		.add_extra_storage(
+			storage_key(ETHEREUM_CURRENT_TRANSACTION_STATUSES),
+			statuses.encode(),
+		)
+		.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(None);
+	let client = Arc::new(client);
+	let genesis_hash = client.info().genesis_hash;
+	let ethereum_block_hash = block.header.hash();
+	let transaction_hash = statuses[0].transaction_hash;
+
+	let db_path = tmp.path().join("frontier");
+	let backend = Arc::new(
+		fc_db::kv::Backend::<OpaqueBlock, _>::new(
+			client.clone(),
+			&fc_db::kv::DatabaseSettings {
+				source: fc_db::DatabaseSource::RocksDb {
+					path: db_path.clone(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("a temporary db was created"),
+	);
+	backend
+		.mapping()
+		.write_hashes(fc_db::kv::MappingCommitment {
+			block_hash: genesis_hash,
+			ethereum_block_hash,
+			ethereum_transaction_hashes: vec![transaction_hash],
+		})
+		.expect("the mapping is written");
+
+	let frontier = FrontierClient::new(
+		client.clone(),
+		backend.clone(),
+		Arc::new(SchemaV3StorageOverride::new(client.clone())),
+	);
+
+	assert_eq!(
+		frontier
+			.substrate_block_hash(ethereum_block_hash)
+			.await
+			.unwrap(),
+		Some(genesis_hash)
+	);
+	assert_eq!(
+		frontier
+			.substrate_block_hash(H256::repeat_byte(0xff))
+			.await
+			.unwrap(),
+		None
+	);
+	assert_eq!(frontier.block(genesis_hash), Some(block));
+	assert_eq!(frontier.receipts(genesis_hash), Some(receipts));
+	assert_eq!(frontier.transaction_statuses(genesis_hash), Some(statuses));
+
+	let blocks = frontier
+		.blocks(0, 10)
+		.collect::<Result<Vec<_>, _>>()
+		.unwrap();
+	assert_eq!(blocks.len(), 1);
+	assert_eq!(blocks[0].substrate_block_hash, genesis_hash);
+
+	let logs = frontier
+		.logs(&LogFilter {
+			from_block: 0,
+			to_block: 10,
+			topics: vec![Some(vec![H256::repeat_byte(0xb)])],
+			..Default::default()
+		})
+		.await
+		.unwrap();
+	assert_eq!(logs.len(), 1);
+	assert_eq!(logs[0].transaction_hash, transaction_hash);
+	assert_eq!(logs[0].log_index, 1);
+	assert_eq!(logs[0].log.address, H160::repeat_byte(0x2));
+
+	// The topics are matched by position, a missing position matching any topic.
+	let logs = frontier
+		.logs(&LogFilter {
+			from_block: 0,
+			to_block: 10,
+			topics: vec![
+				None,
+				Some(vec![H256::repeat_byte(0xb), H256::repeat_byte(0xc)]),
+			],
+			..Default::default()
+		})
+		.await
+		.unwrap();
+	assert_eq!(logs.len(), 1);
+	assert_eq!(logs[0].log_index, 2);
+	let logs = frontier
+		.logs(&LogFilter {
+			from_block: 0,
+			to_block: 10,
+			addresses: vec![H160::repeat_byte(0x1), H160::repeat_byte(0x3)],
+			topics: vec![Some(vec![H256::repeat_byte(0xa)])],
+		})
+		.await
+		.unwrap();
+	assert_eq!(
+		logs.iter().map(|log| log.log_index).collect::<Vec<_>>(),
+		vec![0, 2]
+	);
+
+	let expected_metadata = TransactionMetadata {
+		substrate_block_hash: genesis_hash,
+		ethereum_block_hash,
+		ethereum_index: 0,
+	};
+	assert_eq!(
+		frontier
+			.transaction_metadata(transaction_hash)
+			.await
+			.unwrap(),
+		Some(expected_metadata.clone())
+	);
+
+	// The same database, opened offline.
+	drop(frontier);
+	drop(backend);
+	let offline = OfflineDb::<OpaqueBlock>::open_key_value(&db_path, &tmp.path().join("secondary"))
+		.expect("the db is opened read-only");
+	assert_eq!(
+		offline
+			.substrate_block_hashes(ethereum_block_hash)
+			.await
+			.unwrap(),
+		vec![genesis_hash]
+	);
+	assert_eq!(
+		offline
+			.transaction_metadata(transaction_hash)
+			.await
+			.unwrap(),
+		vec![expected_metadata]
+	);
+	assert!(matches!(
+		offline.logs(&LogFilter::default()).await,
+		Err(Error::Unsupported(_))
+	));
+}
+
+#[test]
+fn offline_db_does_not_create_missing_database() {
+	let tmp = tempdir().expect("create a temporary directory");
+	assert!(OfflineDb::<OpaqueBlock>::open_key_value(
+		&tmp.path().join("missing"),
+		&tmp.path().join("secondary"),
+	)
+	.is_err());
+}
@@ -33,7 +33,7 @@ pub use sc_client_db::DatabaseSource;
 use sp_blockchain::HeaderBackend;
 use sp_core::{H160, H256};
 pub use sp_database::Database;
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, NumberFor, One};
 // Frontier
 use fc_api::{FilteredLog, TransactionMetadata};
 use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA_CACHE};
@@ -59,6 +59,7 @@ pub(crate) mod columns {
 
 pub mod static_keys {
 	pub const CURRENT_SYNCING_TIPS: &[u8] = b"CURRENT_SYNCING_TIPS";
+	pub const INDEXED_HEAD: &[u8] = b"INDEXED_HEAD";
 }
 
 #[derive(Clone)]
@@ -97,6 +98,19 @@ impl<Block: BlockT, C: HeaderBackend<Block>> fc_api::Backend<Block> for Backend<
 	async fn latest_block_hash(&self) -> Result<Block::Hash, String> {
 		Ok(self.client.info().best_hash)
 	}
+
+	async fn latest_indexed_block(
+		&self,
+	) -> Result<Option<(NumberFor<Block>, Block::Hash)>, String> {
+		match self.meta.indexed_head()? {
+			Some(number) => Ok(self
+				.client
+				.hash(number)
+				.map_err(|e| format!("{:?}", e))?
+				.map(|hash| (number, hash))),
+			None => Ok(None),
+		}
+	}
 }
 
 #[derive(Clone, Default)]
@@ -182,6 +196,44 @@ impl<Block: BlockT, C: HeaderBackend<Block>> Backend<Block, C> {
 	pub fn meta(&self) -> &Arc<MetaDb<Block>> {
 		&self.meta
 	}
+
+	/// Extend the indexed head over the canonical blocks synced above it, by at most `max_steps`
+	/// blocks. Returns whether the head may extend further.
+	///
+	/// The mapping-sync worker syncs the new blocks first and walks back their ancestors, so the
+	/// head only moves once the gap below the synced blocks is closed. Without a head, the
+	/// blocks are counted from `sync_from`, below which the worker syncs nothing.
+	pub fn extend_indexed_head(
+		&self,
+		sync_from: NumberFor<Block>,
+		max_steps: usize,
+	) -> Result<bool, String> {
+		let best_number = self.client.info().best_number;
+		let head = self.meta.indexed_head()?;
+		let mut next = head.map_or(sync_from, |number| number + One::one());
+		let mut new_head = head;
+		let mut steps = 0;
+		loop {
+			if next > best_number {
+				break;
+			}
+			match self.client.hash(next).map_err(|e| format!("{:?}", e))? {
+				Some(hash) if self.mapping.is_synced(&hash)? => {
+					new_head = Some(next);
+					next += One::one();
+				}
+				_ => break,
+			}
+			steps += 1;
+			if steps == max_steps {
+				break;
+			}
+		}
+		if let Some(number) = new_head.filter(|_| new_head != head) {
+			self.meta.write_indexed_head(number)?;
+		}
+		Ok(steps == max_steps && next <= best_number)
+	}
 }
 
 pub struct MetaDb<Block> {
@@ -214,6 +266,34 @@ impl<Block: BlockT> MetaDb<Block> {
 		Ok(())
 	}
 
+	/// The highest block number such that the canonical blocks up to it are all synced.
+	pub fn indexed_head(&self) -> Result<Option<NumberFor<Block>>, String> {
+		match self.db.get(columns::META, static_keys::INDEXED_HEAD) {
+			Some(raw) => Ok(Some(
+				NumberFor::<Block>::decode(&mut &raw[..]).map_err(|e| e.to_string())?,
+			)),
+			None => Ok(None),
+		}
+	}
+
+	pub fn write_indexed_head(&self, number: NumberFor<Block>) -> Result<(), String> {
+		let mut transaction = sp_database::Transaction::new();
+
+		transaction.set(columns::META, static_keys::INDEXED_HEAD, &number.encode());
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
+
+	/// Lower the indexed head to `number`, e.g. when a reorg retracts the blocks above it.
+	pub fn lower_indexed_head(&self, number: NumberFor<Block>) -> Result<(), String> {
+		match self.indexed_head()? {
+			Some(head) if head > number => self.write_indexed_head(number),
+			_ => Ok(()),
+		}
+	}
+
 	pub fn ethereum_schema(&self) -> Result<Option<Vec<(EthereumStorageSchema, H256)>>, String> {
 		match self
 			.db
@@ -244,7 +324,7 @@ impl<Block: BlockT> MetaDb<Block> {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct MappingCommitment<Block: BlockT> {
 	pub block_hash: Block::Hash,
 	pub ethereum_block_hash: H256,
@@ -258,6 +338,20 @@ pub struct MappingDb<Block> {
 }
 
 impl<Block: BlockT> MappingDb<Block> {
+	/// Open the mapping of a RocksDB frontier database read-only, without a client.
+	///
+	/// The database is opened as a RocksDB secondary instance, which keeps its own state in
+	/// `secondary_path`, so it can be opened while a node still uses it. Writing through the
+	/// returned mapping fails.
+	#[cfg(feature = "rocksdb")]
+	pub fn open_read_only(path: &Path, secondary_path: &Path) -> Result<Self, String> {
+		Ok(Self {
+			db: utils::open_kvdb_rocksdb_read_only(path, secondary_path)?,
+			write_lock: Arc::new(Mutex::new(())),
+			_marker: PhantomData,
+		})
+	}
+
 	pub fn is_synced(&self, block_hash: &Block::Hash) -> Result<bool, String> {
 		match self.db.get(columns::SYNCED_MAPPING, &block_hash.encode()) {
 			Some(raw) => Ok(bool::decode(&mut &raw[..]).map_err(|e| format!("{:?}", e))?),
@@ -343,6 +437,8 @@ impl<Block: BlockT> MappingDb<Block> {
 			.enumerate()
 		{
 			let mut metadata = self.transaction_metadata(&ethereum_transaction_hash)?;
+			// The block may be synced again after a reorg re-enacts it.
+			metadata.retain(|meta| meta.substrate_block_hash != commitment.block_hash);
 			metadata.push(TransactionMetadata::<Block> {
 				substrate_block_hash: commitment.block_hash,
 				ethereum_block_hash: commitment.ethereum_block_hash,
@@ -365,4 +461,37 @@ impl<Block: BlockT> MappingDb<Block> {
 
 		Ok(())
 	}
+
+	/// Remove the transaction metadata of a block that was retracted by a reorg.
+	///
+	/// The block stays marked as synced, and its block hash mapping is kept.
+	pub fn prune_transaction_metadata(
+		&self,
+		commitment: MappingCommitment<Block>,
+	) -> Result<(), String> {
+		let _lock = self.write_lock.lock();
+
+		let mut transaction = sp_database::Transaction::new();
+
+		for ethereum_transaction_hash in commitment.ethereum_transaction_hashes {
+			let mut metadata = self.transaction_metadata(&ethereum_transaction_hash)?;
+			metadata.retain(|meta| meta.substrate_block_hash != commitment.block_hash);
+			if metadata.is_empty() {
+				transaction.remove(
+					columns::TRANSACTION_MAPPING,
+					&ethereum_transaction_hash.encode(),
+				);
+			} else {
+				transaction.set(
+					columns::TRANSACTION_MAPPING,
+					&ethereum_transaction_hash.encode(),
+					&metadata.encode(),
+				);
+			}
+		}
+
+		self.db.commit(transaction).map_err(|e| e.to_string())?;
+
+		Ok(())
+	}
 }
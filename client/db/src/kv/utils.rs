@@ -77,6 +77,21 @@ fn open_kvdb_rocksdb<Block: BlockT, C: HeaderBackend<Block>>(
 	Ok(sp_database::as_database(db))
 }
 
+/// Open a RocksDB frontier database as a read-only secondary instance. It can be opened while
+/// a node still uses the database, and keeps its own state in `secondary_path`.
+#[cfg(feature = "rocksdb")]
+pub fn open_kvdb_rocksdb_read_only(
+	path: &Path,
+	secondary_path: &Path,
+) -> Result<Arc<dyn Database<DbHash>>, String> {
+	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(super::columns::NUM_COLUMNS);
+	db_config.create_if_missing = false;
+	db_config.secondary = Some(secondary_path.to_path_buf());
+
+	let db = kvdb_rocksdb::Database::open(&db_config, path).map_err(|err| format!("{}", err))?;
+	Ok(sp_database::as_database(db))
+}
+
 #[cfg(not(feature = "rocksdb"))]
 fn open_kvdb_rocksdb<Block: BlockT, C: HeaderBackend<Block>>(
 	_client: Arc<C>,
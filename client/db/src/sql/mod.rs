@@ -19,6 +19,7 @@
 use std::{cmp::Ordering, collections::HashSet, num::NonZeroU32, str::FromStr, sync::Arc};
 
 use futures::TryStreamExt;
+use parking_lot::Mutex;
 use scale_codec::{Decode, Encode};
 use sqlx::{
 	query::Query,
@@ -34,7 +35,7 @@ use sp_blockchain::HeaderBackend;
 use sp_core::{H160, H256};
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto, Zero},
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, UniqueSaturatedInto, Zero},
 };
 // Frontier
 use fc_api::{FilteredLog, TransactionMetadata};
@@ -46,6 +47,12 @@ use fp_storage::EthereumStorageSchema;
 /// Maximum number to topics allowed to be filtered upon
 const MAX_TOPIC_COUNT: u16 = 4;
 
+/// Maximum number of candidate logs returned by a log query.
+///
+/// The candidates of a query are a superset of the logs matching the filter, so a query selecting
+/// more fails instead of returning a truncated list.
+const MAX_FILTERED_LOGS: usize = 10_000;
+
 /// Represents a log item.
 #[derive(Debug, Eq, PartialEq)]
 pub struct Log {
@@ -102,6 +109,9 @@ pub struct Backend<Block> {
 	/// The number of allowed operations for the Sqlite filter call.
 	/// A value of `0` disables the timeout.
 	num_ops_timeout: i32,
+
+	/// The head of the indexed canonical chain found last, to resume the search from.
+	indexed_head: Arc<Mutex<Option<(u32, H256)>>>,
 }
 
 impl<Block> Backend<Block>
@@ -128,6 +138,34 @@ where
 				.unwrap_or(0)
 				.try_into()
 				.unwrap_or(i32::MAX),
+			indexed_head: Default::default(),
+		})
+	}
+
+	/// Opens an existing database of the SQL backend read-only, e.g. to inspect the database of
+	/// a node without running it. Indexing through the returned backend fails.
+	pub fn open_read_only(
+		path: &str,
+		pool_size: u32,
+		num_ops_timeout: Option<NonZeroU32>,
+		storage_override: Arc<dyn StorageOverride<Block>>,
+	) -> Result<Self, Error> {
+		let options = SqliteConnectOptions::from_str(path)?
+			.read_only(true)
+			.busy_timeout(std::time::Duration::from_secs(8))
+			.disable_statement_logging();
+		let any_pool = SqlitePoolOptions::new()
+			.max_connections(pool_size)
+			.connect_lazy_with(options);
+		Ok(Self {
+			pool: any_pool,
+			storage_override,
+			num_ops_timeout: num_ops_timeout
+				.map(|n| n.get())
+				.unwrap_or(0)
+				.try_into()
+				.unwrap_or(i32::MAX),
+			indexed_head: Default::default(),
 		})
 	}
 
@@ -668,6 +706,120 @@ where
 		))
 	}
 
+	/// Retrieve the hash of the canonical block `block_number`, if it has had its logs indexed.
+	async fn indexed_canon_block_hash(&self, block_number: u32) -> Result<Option<H256>, Error> {
+		let row = sqlx::query(
+			"SELECT b.substrate_block_hash FROM blocks AS b
+			INNER JOIN sync_status AS s
+			ON s.substrate_block_hash = b.substrate_block_hash
+			WHERE b.block_number = ? AND b.is_canon = 1 AND s.status = 1",
+		)
+		.bind(block_number)
+		.fetch_optional(self.pool())
+		.await?;
+		Ok(row.map(|row| H256::from_slice(&row.get::<Vec<u8>, _>(0)[..])))
+	}
+
+	/// Retrieve the head of the run of canonical blocks that have had their logs indexed, that
+	/// starts with the genesis block.
+	///
+	/// The worker indexes new blocks first and walks back their ancestors, and indexes the logs
+	/// of a block after its metadata, so the highest indexed block doesn't imply the blocks below
+	/// are indexed. The search resumes from the head found last, as long as it is still
+	/// canonical and indexed.
+	async fn indexed_canon_head(&self) -> Result<Option<(u32, H256)>, Error> {
+		let last = *self.indexed_head.lock();
+		let from = match last {
+			Some((number, hash)) if self.indexed_canon_block_hash(number).await? == Some(hash) => {
+				number
+			}
+			_ if self.indexed_canon_block_hash(0).await?.is_some() => 0,
+			_ => {
+				*self.indexed_head.lock() = None;
+				return Ok(None);
+			}
+		};
+		let head = sqlx::query(
+			"SELECT b.block_number, b.substrate_block_hash FROM blocks AS b
+			INNER JOIN sync_status AS s
+			ON s.substrate_block_hash = b.substrate_block_hash
+			WHERE b.block_number >= ? AND b.is_canon = 1 AND s.status = 1 AND NOT EXISTS (
+				SELECT 1 FROM blocks AS b2
+				INNER JOIN sync_status AS s2
+				ON s2.substrate_block_hash = b2.substrate_block_hash
+				WHERE b2.block_number = b.block_number + 1 AND b2.is_canon = 1 AND s2.status = 1
+			)
+			ORDER BY b.block_number LIMIT 1",
+		)
+		.bind(from)
+		.fetch_optional(self.pool())
+		.await?
+		.map(|row| {
+			(
+				row.get::<i32, _>(0) as u32,
+				H256::from_slice(&row.get::<Vec<u8>, _>(1)[..]),
+			)
+		});
+		*self.indexed_head.lock() = head;
+		Ok(head)
+	}
+
+	/// Reset the index to the canonical block `block_number`, e.g. to recover from a corrupted
+	/// index. The blocks above it are deleted along with their transactions and logs, and the
+	/// sync worker indexes them again from there, as it walks back the ancestors of the new blocks
+	/// until it reaches an indexed one.
+	///
+	/// Unless `force` is set, the block must be an indexed canonical block, so that the index isn't
+	/// reset above its head.
+	pub async fn reset_to_block(&self, block_number: u32, force: bool) -> Result<(), Error> {
+		if !force {
+			let head: Option<u32> =
+				sqlx::query("SELECT MAX(block_number) FROM blocks WHERE is_canon = 1")
+					.fetch_one(self.pool())
+					.await?
+					.try_get(0)?;
+			match head {
+				None => return Err(Error::Protocol("No block is indexed".to_string())),
+				Some(head) if block_number > head => {
+					return Err(Error::Protocol(format!(
+						"Block #{block_number} is above the indexed head #{head}"
+					)))
+				}
+				Some(_) => {}
+			}
+
+			let indexed =
+				sqlx::query("SELECT 1 FROM blocks WHERE block_number = ? AND is_canon = 1")
+					.bind(block_number)
+					.fetch_optional(self.pool())
+					.await?
+					.is_some();
+			if !indexed {
+				return Err(Error::Protocol(format!(
+					"Block #{block_number} is not indexed"
+				)));
+			}
+		}
+
+		let mut tx = self.pool().begin().await?;
+		for statement in [
+			"DELETE FROM logs WHERE substrate_block_hash IN
+				(SELECT substrate_block_hash FROM blocks WHERE block_number > ?)",
+			"DELETE FROM transactions WHERE substrate_block_hash IN
+				(SELECT substrate_block_hash FROM blocks WHERE block_number > ?)",
+			"DELETE FROM sync_status WHERE substrate_block_hash IN
+				(SELECT substrate_block_hash FROM blocks WHERE block_number > ?)",
+			"DELETE FROM blocks WHERE block_number > ?",
+		] {
+			sqlx::query(statement)
+				.bind(block_number)
+				.execute(&mut *tx)
+				.await?;
+		}
+		log::info!(target: "frontier-sql", "Index reset to block #{block_number}");
+		tx.commit().await
+	}
+
 	/// Create the Sqlite database if it does not already exist.
 	async fn create_database_if_not_exists(pool: &SqlitePool) -> Result<SqliteQueryResult, Error> {
 		sqlx::query(
@@ -838,6 +990,15 @@ impl<Block: BlockT<Hash = H256>> fc_api::Backend<Block> for Backend<Block> {
 			.map(|row| H256::from_slice(&row.get::<Vec<u8>, _>(0)[..]))
 			.map_err(|e| format!("Failed to fetch best hash: {}", e))
 	}
+
+	async fn latest_indexed_block(
+		&self,
+	) -> Result<Option<(NumberFor<Block>, Block::Hash)>, String> {
+		self.indexed_canon_head()
+			.await
+			.map(|head| head.map(|(number, hash)| (number.into(), hash)))
+			.map_err(|e| format!("Failed to fetch latest indexed block: {}", e))
+	}
 }
 
 #[async_trait::async_trait]
@@ -937,6 +1098,11 @@ impl<Block: BlockT<Hash = H256>> fc_api::LogIndexerBackend<Block> for Backend<Bl
 			log::error!(target: "frontier-sql", "Failed to query sql db: {err:?} - {log_key}");
 			return Err("Failed to query sql db with statement".to_string());
 		}
+		if out.len() > MAX_FILTERED_LOGS {
+			return Err(format!(
+				"query selected more than {MAX_FILTERED_LOGS} candidate logs, narrow the block range or the filter"
+			));
+		}
 
 		log::info!(target: "frontier-sql", "FILTER remove handler - {log_key}");
 		Ok(out)
@@ -1005,11 +1171,12 @@ ON (b.block_number BETWEEN ",
 		}
 	}
 
-	qb.push(
+	qb.push(format!(
 		"
 ORDER BY b.block_number ASC, l.transaction_index ASC, l.log_index ASC
-LIMIT 10001",
-	);
+LIMIT {}",
+		MAX_FILTERED_LOGS + 1
+	));
 
 	qb.build()
 }
@@ -1099,7 +1266,7 @@ mod test {
 		}
 	}
 
-	async fn prepare() -> TestData {
+	async fn test_backend() -> Backend<OpaqueBlock> {
 		let tmp = tempdir().expect("create a temporary directory");
 		// Initialize storage with schema V3
 		let builder = TestClientBuilder::new().add_extra_storage(
@@ -1115,7 +1282,7 @@ mod test {
 		// Overrides
 		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
 		// Indexer backend
-		let indexer_backend = Backend::new(
+		Backend::new(
 			BackendConfig::Sqlite(SqliteBackendConfig {
 				path: Path::new("sqlite:///")
 					.join(tmp.path())
@@ -1128,10 +1295,14 @@ mod test {
 			}),
 			1,
 			None,
-			storage_override.clone(),
+			storage_override,
 		)
 		.await
-		.expect("indexer pool to be created");
+		.expect("indexer pool to be created")
+	}
+
+	async fn prepare() -> TestData {
+		let indexer_backend = test_backend().await;
 
 		// Prepare test db data
 		// Addresses
@@ -1387,6 +1558,88 @@ mod test {
 		assert_eq!(expected, actual);
 	}
 
+	#[tokio::test]
+	async fn latest_indexed_block_is_the_head_of_the_indexed_canon_chain() {
+		let backend = test_backend().await;
+		let hash = |number: u32| H256::repeat_byte(number as u8 + 1);
+		let insert_block = |number: u32| {
+			let pool = backend.pool().clone();
+			async move {
+				sqlx::query(
+					"INSERT INTO blocks(
+						block_number,
+						ethereum_block_hash,
+						substrate_block_hash,
+						ethereum_storage_schema,
+						is_canon
+					) VALUES (?, ?, ?, ?, 1)",
+				)
+				.bind(number)
+				.bind(H256::repeat_byte(number as u8 + 0x80).as_bytes())
+				.bind(hash(number).as_bytes())
+				.bind(EthereumStorageSchema::V3.encode())
+				.execute(&pool)
+				.await
+				.expect("insert should succeed");
+				sqlx::query("INSERT INTO sync_status(substrate_block_hash) VALUES (?)")
+					.bind(hash(number).as_bytes())
+					.execute(&pool)
+					.await
+					.expect("insert should succeed");
+			}
+		};
+		let index_logs = |number: u32| {
+			let pool = backend.pool().clone();
+			async move {
+				sqlx::query("UPDATE sync_status SET status = 1 WHERE substrate_block_hash = ?")
+					.bind(hash(number).as_bytes())
+					.execute(&pool)
+					.await
+					.expect("update should succeed");
+			}
+		};
+
+		// The genesis block is indexed on start.
+		assert_eq!(backend.latest_indexed_block().await, Ok(None));
+		insert_block(0).await;
+		assert_eq!(backend.latest_indexed_block().await, Ok(None));
+		index_logs(0).await;
+		assert_eq!(backend.latest_indexed_block().await, Ok(Some((0, hash(0)))));
+
+		// The worker indexes the new best block first, and walks back its missing ancestors. The
+		// logs are indexed afterwards, from the lowest pending block.
+		for number in (1..=5).rev() {
+			insert_block(number).await;
+			assert_eq!(backend.latest_indexed_block().await, Ok(Some((0, hash(0)))));
+		}
+		for number in 1..=5 {
+			index_logs(number).await;
+			assert_eq!(
+				backend.latest_indexed_block().await,
+				Ok(Some((number.into(), hash(number))))
+			);
+		}
+
+		// Blocks above a block pending its logs aren't reported.
+		sqlx::query("UPDATE sync_status SET status = 0 WHERE substrate_block_hash = ?")
+			.bind(hash(3).as_bytes())
+			.execute(backend.pool())
+			.await
+			.expect("update should succeed");
+		assert_eq!(backend.latest_indexed_block().await, Ok(Some((2, hash(2)))));
+		index_logs(3).await;
+		assert_eq!(backend.latest_indexed_block().await, Ok(Some((5, hash(5)))));
+
+		// Blocks retracted by a reorg aren't reported.
+		sqlx::query("UPDATE blocks SET is_canon = 0 WHERE substrate_block_hash = ?")
+			.bind(hash(5).as_bytes())
+			.execute(backend.pool())
+			.await
+			.expect("update should succeed");
+		assert_eq!(backend.latest_indexed_block().await, Ok(Some((4, hash(4)))));
+		assert_eq!(backend.best_indexed_block().await, Ok(4));
+	}
+
 	#[tokio::test]
 	async fn genesis_works() {
 		let TestData { backend, .. } = prepare().await;
@@ -1435,6 +1688,60 @@ mod test {
 			.expect_err("Invalid topic input. Maximum length is 4.");
 	}
 
+	#[tokio::test]
+	async fn too_many_candidate_logs_fails() {
+		let TestData {
+			backend,
+			alice,
+			bob,
+			topics_a,
+			substrate_hash_1,
+			..
+		} = prepare().await;
+		// Block 1 ends up with more logs of alice than a query may select.
+		sqlx::query(
+			"INSERT INTO logs(
+				address,
+				topic_1,
+				log_index,
+				transaction_index,
+				substrate_block_hash
+			)
+			WITH RECURSIVE indexes(i) AS (SELECT 3 UNION ALL SELECT i + 1 FROM indexes WHERE i < ?)
+			SELECT ?, ?, i, 1, ? FROM indexes",
+		)
+		.bind(MAX_FILTERED_LOGS as i64)
+		.bind(alice.as_bytes())
+		.bind(topics_a.as_bytes())
+		.bind(substrate_hash_1.as_bytes())
+		.execute(backend.pool())
+		.await
+		.expect("insert should succeed");
+
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![alice],
+			topics: vec![],
+			expected_result: vec![],
+		};
+		run_test_case(backend.clone(), &filter)
+			.await
+			.expect_err("the truncated candidates must not be returned");
+
+		let filter = TestFilter {
+			from_block: 0,
+			to_block: 3,
+			addresses: vec![bob],
+			topics: vec![vec![Some(topics_a)]],
+			expected_result: vec![],
+		};
+		assert_eq!(
+			run_test_case(backend, &filter).await.map(|logs| logs.len()),
+			Ok(2)
+		);
+	}
+
 	#[tokio::test]
 	async fn test_malformed_topic_cleans_invalid_options() {
 		let TestData {
@@ -29,7 +29,7 @@ use sc_client_api::backend::{Backend, StorageProvider};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::{Backend as _, HeaderBackend};
 use sp_consensus::SyncOracle;
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, Zero};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, One, Saturating, Zero};
 // Frontier
 use fc_storage::StorageOverride;
 use fp_consensus::{FindLogError, Hashes, Log, PostLog, PreLog};
@@ -37,11 +37,15 @@ use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{EthereumBlockNotification, EthereumBlockNotificationSinks, SyncStrategy};
 
-pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
+/// The number of synced blocks the indexed head extends over at most per sync round.
+const INDEXED_HEAD_MAX_STEPS: usize = 10_000;
+
+/// Build the mapping commitment of a block from its Frontier consensus digest, or `None` if the
+/// block has no Ethereum block.
+fn mapping_commitment<Block: BlockT>(
 	storage_override: Arc<dyn StorageOverride<Block>>,
-	backend: &fc_db::kv::Backend<Block, C>,
 	header: &Block::Header,
-) -> Result<(), String> {
+) -> Result<Option<fc_db::kv::MappingCommitment<Block>>, String> {
 	let substrate_block_hash = header.hash();
 	match fp_consensus::find_log(header.digest()) {
 		Ok(log) => {
@@ -58,19 +62,10 @@ pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
 			};
 
 			match log {
-				Log::Pre(PreLog::Block(block)) => {
-					let mapping_commitment = gen_from_block(block);
-					backend.mapping().write_hashes(mapping_commitment)
-				}
+				Log::Pre(PreLog::Block(block)) => Ok(Some(gen_from_block(block))),
 				Log::Post(post_log) => match post_log {
-					PostLog::Hashes(hashes) => {
-						let mapping_commitment = gen_from_hashes(hashes);
-						backend.mapping().write_hashes(mapping_commitment)
-					}
-					PostLog::Block(block) => {
-						let mapping_commitment = gen_from_block(block);
-						backend.mapping().write_hashes(mapping_commitment)
-					}
+					PostLog::Hashes(hashes) => Ok(Some(gen_from_hashes(hashes))),
+					PostLog::Block(block) => Ok(Some(gen_from_block(block))),
 					PostLog::BlockHash(expect_eth_block_hash) => {
 						let ethereum_block = storage_override.current_block(substrate_block_hash);
 						match ethereum_block {
@@ -83,21 +78,71 @@ pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
 										db state ({got_eth_block_hash:?})"
 									))
 								} else {
-									let mapping_commitment = gen_from_block(block);
-									backend.mapping().write_hashes(mapping_commitment)
+									Ok(Some(gen_from_block(block)))
 								}
 							}
-							None => backend.mapping().write_none(substrate_block_hash),
+							None => Ok(None),
 						}
 					}
 				},
 			}
 		}
-		Err(FindLogError::NotFound) => backend.mapping().write_none(substrate_block_hash),
+		Err(FindLogError::NotFound) => Ok(None),
 		Err(FindLogError::MultipleLogs) => Err("Multiple logs found".to_string()),
 	}
 }
 
+pub fn sync_block<Block: BlockT, C: HeaderBackend<Block>>(
+	storage_override: Arc<dyn StorageOverride<Block>>,
+	backend: &fc_db::kv::Backend<Block, C>,
+	header: &Block::Header,
+) -> Result<(), String> {
+	match mapping_commitment(storage_override, header)? {
+		Some(mapping_commitment) => backend.mapping().write_hashes(mapping_commitment),
+		None => backend.mapping().write_none(header.hash()),
+	}
+}
+
+/// Update the transaction metadata after a reorg.
+///
+/// The metadata of the retracted blocks is pruned, so that transaction lookups no longer see
+/// entries of the dead fork. Enacted blocks that were already synced are synced again, in case
+/// their metadata was pruned by an earlier reorg.
+pub fn sync_reorg<Block: BlockT, C: HeaderBackend<Block>>(
+	client: &C,
+	storage_override: Arc<dyn StorageOverride<Block>>,
+	backend: &fc_db::kv::Backend<Block, C>,
+	retracted: &[Block::Hash],
+	enacted: &[Block::Hash],
+) -> Result<(), String> {
+	let load_header = |hash: Block::Hash| -> Result<Block::Header, String> {
+		client
+			.header(hash)
+			.map_err(|e| format!("{:?}", e))?
+			.ok_or_else(|| "Header not found".to_string())
+	};
+
+	for hash in retracted {
+		let header = load_header(*hash)?;
+		// The enacted blocks replacing the retracted ones may not be synced yet.
+		backend
+			.meta()
+			.lower_indexed_head(header.number().saturating_sub(One::one()))?;
+		if let Some(mapping_commitment) = mapping_commitment(storage_override.clone(), &header)? {
+			backend
+				.mapping()
+				.prune_transaction_metadata(mapping_commitment)?;
+		}
+	}
+	for hash in enacted {
+		if backend.mapping().is_synced(hash)? {
+			sync_block(storage_override.clone(), backend, &load_header(*hash)?)?;
+		}
+	}
+
+	Ok(())
+}
+
 pub fn sync_genesis_block<Block: BlockT, C>(
 	client: &C,
 	backend: &fc_db::kv::Backend<Block, C>,
@@ -267,8 +312,9 @@ where
 				pubsub_notification_sinks.clone(),
 			)?;
 	}
+	let extending = frontier_backend.extend_indexed_head(sync_from, INDEXED_HEAD_MAX_STEPS)?;
 
-	Ok(synced_any)
+	Ok(synced_any || extending)
 }
 
 pub fn fetch_header<Block: BlockT, C, BE>(
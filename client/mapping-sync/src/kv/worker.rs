@@ -114,8 +114,29 @@ where
 		loop {
 			match Stream::poll_next(Pin::new(&mut self.import_notifications), cx) {
 				Poll::Pending => break,
-				Poll::Ready(Some(_)) => {
+				Poll::Ready(Some(notification)) => {
 					fire = true;
+					if let Some(tree_route) = notification.tree_route {
+						let retracted = tree_route
+							.retracted()
+							.iter()
+							.map(|hash_and_number| hash_and_number.hash)
+							.collect::<Vec<_>>();
+						let enacted = tree_route
+							.enacted()
+							.iter()
+							.map(|hash_and_number| hash_and_number.hash)
+							.collect::<Vec<_>>();
+						if let Err(e) = crate::kv::sync_reorg(
+							self.client.as_ref(),
+							self.storage_override.clone(),
+							self.frontier_backend.as_ref(),
+							&retracted,
+							&enacted,
+						) {
+							debug!(target: "mapping-sync", "Reorg syncing failed with error {:?}.", e);
+						}
+					}
 				}
 				Poll::Ready(None) => return Poll::Ready(None),
 			}
@@ -467,4 +488,86 @@ mod tests {
 			assert_eq!(sinks.len(), 0);
 		}
 	}
+
+	#[tokio::test]
+	async fn indexed_head_waits_for_the_synced_chain_to_reach_genesis() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		// Backend
+		let backend = builder.backend();
+		// Client
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let client = Arc::new(client);
+		// Overrides
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
+
+		let frontier_backend = fc_db::kv::Backend::<OpaqueBlock, _>::new(
+			client.clone(),
+			&fc_db::kv::DatabaseSettings {
+				source: sc_client_db::DatabaseSource::RocksDb {
+					path: tmp.path().to_path_buf(),
+					cache_size: 0,
+				},
+			},
+		)
+		.expect("frontier backend");
+
+		let import_blocks = |count: u64| {
+			let client = client.clone();
+			async move {
+				for _ in 0..count {
+					let chain_info = client.chain_info();
+					let builder = BlockBuilderBuilder::new(&*client)
+						.on_parent_block(chain_info.best_hash)
+						.with_parent_block_number(chain_info.best_number)
+						.with_inherent_digests(ethereum_digest())
+						.build()
+						.unwrap();
+					let block = builder.build().unwrap().block;
+					client.import(BlockOrigin::Own, block).await.unwrap();
+				}
+			}
+		};
+		// Sync a single block, in the order of the worker.
+		let sync_one_block = || {
+			crate::kv::sync_blocks(
+				client.as_ref(),
+				backend.as_ref(),
+				storage_override.clone(),
+				&frontier_backend,
+				1,
+				0,
+				SyncStrategy::Normal,
+				Arc::new(TestSyncOracleNotSyncing {}),
+				Default::default(),
+			)
+			.expect("sync a block")
+		};
+
+		// The worker syncs the best block first, and walks back to genesis.
+		import_blocks(5).await;
+		for number in (1..=5).rev() {
+			assert!(sync_one_block());
+			assert!(frontier_backend
+				.mapping()
+				.is_synced(&client.hash(number).unwrap().unwrap())
+				.unwrap());
+			assert_eq!(frontier_backend.meta().indexed_head(), Ok(None));
+		}
+		assert!(sync_one_block());
+		assert_eq!(frontier_backend.meta().indexed_head(), Ok(Some(5)));
+
+		// The new blocks are synced from the best one as well, and the head only moves once
+		// they connect with the synced chain.
+		import_blocks(2).await;
+		assert!(sync_one_block());
+		assert_eq!(frontier_backend.meta().indexed_head(), Ok(Some(5)));
+		assert!(sync_one_block());
+		assert_eq!(frontier_backend.meta().indexed_head(), Ok(Some(7)));
+		assert!(!sync_one_block());
+	}
 }
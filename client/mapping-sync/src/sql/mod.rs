@@ -1107,27 +1107,15 @@ mod test {
 		assert!(not_canon.iter().all(|h| hashes_to_be_orphaned.contains(h)));
 	}
 
-	#[tokio::test]
-	async fn resuming_from_last_indexed_block_works() {
-		let tmp = tempdir().expect("create a temporary directory");
-		// Initialize storage with schema V3
-		let builder = TestClientBuilder::new().add_extra_storage(
-			PALLET_ETHEREUM_SCHEMA.to_vec(),
-			Encode::encode(&EthereumStorageSchema::V3),
-		);
-		// Backend
-		let backend = builder.backend();
-		// Client
-		let (client, _) =
-			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
-		let client = Arc::new(client);
-		// Overrides
-		let storage_override = Arc::new(SchemaV3StorageOverride::new(client.clone()));
-		// Indexer backend
+	/// An SQL indexer backend, in a `test.db3` database created in `path`.
+	async fn sql_indexer_backend(
+		path: &Path,
+		storage_override: Arc<dyn fc_storage::StorageOverride<OpaqueBlock>>,
+	) -> Arc<fc_db::sql::Backend<OpaqueBlock>> {
 		let indexer_backend = fc_db::sql::Backend::new(
 			fc_db::sql::BackendConfig::Sqlite(fc_db::sql::SqliteBackendConfig {
 				path: Path::new("sqlite:///")
-					.join(tmp.path())
+					.join(path)
 					.join("test.db3")
 					.to_str()
 					.unwrap(),
@@ -1137,12 +1125,65 @@ mod test {
 			}),
 			100,
 			None,
-			storage_override.clone(),
+			storage_override,
 		)
 		.await
 		.expect("indexer pool to be created");
+		Arc::new(indexer_backend)
+	}
 
-		// Pool
+	/// Spawns the sync worker of `indexer_backend` on the blocks imported by `client`, with the
+	/// parachain strategy and an oracle that is never syncing.
+	fn spawn_sync_worker<Client, Backend>(
+		client: Arc<Client>,
+		backend: Arc<Backend>,
+		indexer_backend: Arc<fc_db::sql::Backend<OpaqueBlock>>,
+		read_notification_timeout: Duration,
+	) where
+		Client: ProvideRuntimeApi<OpaqueBlock> + BlockchainEvents<OpaqueBlock>,
+		Client::Api: EthereumRuntimeRPCApi<OpaqueBlock>,
+		Client: HeaderBackend<OpaqueBlock> + StorageProvider<OpaqueBlock, Backend> + 'static,
+		Client: Send + Sync,
+		Backend: BackendT<OpaqueBlock> + 'static,
+	{
+		let pubsub_notification_sinks: EthereumBlockNotificationSinks<
+			EthereumBlockNotification<OpaqueBlock>,
+		> = Default::default();
+		tokio::task::spawn(async move {
+			crate::sql::SyncWorker::run(
+				client.clone(),
+				backend,
+				indexer_backend,
+				client.import_notification_stream(),
+				SyncWorkerConfig {
+					read_notification_timeout,
+					check_indexed_blocks_interval: Duration::from_secs(60),
+				},
+				SyncStrategy::Parachain,
+				Arc::new(TestSyncOracleNotSyncing {}),
+				Arc::new(pubsub_notification_sinks),
+			)
+			.await
+		});
+	}
+
+	#[tokio::test]
+	async fn resuming_from_last_indexed_block_works() {
+		let tmp = tempdir().expect("create a temporary directory");
+		// Initialize storage with schema V3
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		let backend = builder.backend();
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let client = Arc::new(client);
+		let indexer_backend = sql_indexer_backend(
+			tmp.path(),
+			Arc::new(SchemaV3StorageOverride::new(client.clone())),
+		)
+		.await;
 		let pool = indexer_backend.pool().clone();
 
 		// Create 5 blocks, storing them newest first.
@@ -1184,31 +1225,7 @@ mod test {
 			.expect("sql query must succeed");
 
 		// Spawn indexer task
-		let test_sync_oracle = TestSyncOracleNotSyncing {};
-		let pubsub_notification_sinks: EthereumBlockNotificationSinks<
-			EthereumBlockNotification<OpaqueBlock>,
-		> = Default::default();
-		let pubsub_notification_sinks = Arc::new(pubsub_notification_sinks);
-
-		let pubsub_notification_sinks_inner = pubsub_notification_sinks.clone();
-
-		let client_inner = client.clone();
-		tokio::task::spawn(async move {
-			crate::sql::SyncWorker::run(
-				client_inner,
-				backend.clone(),
-				Arc::new(indexer_backend),
-				client.clone().import_notification_stream(),
-				SyncWorkerConfig {
-					read_notification_timeout: Duration::from_secs(10),
-					check_indexed_blocks_interval: Duration::from_secs(60),
-				},
-				SyncStrategy::Parachain,
-				Arc::new(test_sync_oracle),
-				pubsub_notification_sinks_inner,
-			)
-			.await
-		});
+		spawn_sync_worker(client, backend, indexer_backend, Duration::from_secs(10));
 		// Enough time for indexing
 		futures_timer::Delay::new(Duration::from_millis(1500)).await;
 
@@ -1225,6 +1242,121 @@ mod test {
 		assert_eq!(expected_imported_blocks, actual_imported_blocks);
 	}
 
+	/// The indexed blocks, with their sync status, and logs, ordered by block number.
+	async fn indexed_data(
+		pool: &sqlx::SqlitePool,
+	) -> (
+		Vec<(i32, Vec<u8>, Vec<u8>, i32, i32)>,
+		Vec<(i32, i32, i32, Vec<u8>)>,
+	) {
+		let blocks = sqlx::query(
+			"SELECT b.block_number, b.substrate_block_hash, b.ethereum_block_hash, b.is_canon, s.status
+			FROM blocks AS b INNER JOIN sync_status AS s
+			ON s.substrate_block_hash = b.substrate_block_hash
+			ORDER BY b.block_number ASC",
+		)
+		.fetch_all(pool)
+		.await
+		.expect("test query result")
+		.iter()
+		.map(|row| (row.get(0), row.get(1), row.get(2), row.get(3), row.get(4)))
+		.collect();
+		let logs = sqlx::query(
+			"SELECT b.block_number, l.transaction_index, l.log_index, l.address
+			FROM logs AS l INNER JOIN blocks AS b
+			ON l.substrate_block_hash = b.substrate_block_hash
+			ORDER BY b.block_number ASC, l.transaction_index ASC, l.log_index ASC",
+		)
+		.fetch_all(pool)
+		.await
+		.expect("test query result")
+		.iter()
+		.map(|row| (row.get(0), row.get(1), row.get(2), row.get(3)))
+		.collect();
+		(blocks, logs)
+	}
+
+	#[tokio::test]
+	async fn reindexing_after_a_reset_works() {
+		let tmp = tempdir().expect("create a temporary directory");
+		// Initialize storage with schema V3
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		let backend = builder.backend();
+		let (client, _) =
+			builder.build_with_native_executor::<frontier_template_runtime::RuntimeApi, _>(None);
+		let client = Arc::new(client);
+		let indexer_backend = sql_indexer_backend(
+			tmp.path(),
+			Arc::new(SchemaV3StorageOverride::new(client.clone())),
+		)
+		.await;
+		let pool = indexer_backend.pool().clone();
+
+		// Create 6 blocks, with 1 log each
+		for block_number in 1..=6u64 {
+			let chain = client.chain_info();
+			let mut builder = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(chain.best_hash)
+				.with_parent_block_number(chain.best_number)
+				.build()
+				.unwrap();
+			builder
+				.push_deposit_log_digest_item(ethereum_digest())
+				.expect("deposit log");
+			let receipts = Encode::encode(&vec![ethereum::ReceiptV4::EIP1559(
+				ethereum::EIP1559ReceiptData {
+					status_code: 0u8,
+					used_gas: U256::zero(),
+					logs_bloom: ethereum_types::Bloom::zero(),
+					logs: vec![ethereum::Log {
+						address: H160::from_low_u64_be(block_number),
+						topics: vec![],
+						data: vec![],
+					}],
+				},
+			)]);
+			builder
+				.push_storage_change(
+					storage_prefix_build(PALLET_ETHEREUM, ETHEREUM_CURRENT_RECEIPTS),
+					Some(receipts),
+				)
+				.unwrap();
+			let block = builder.build().unwrap().block;
+			executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		}
+
+		spawn_sync_worker(
+			client,
+			backend,
+			indexer_backend.clone(),
+			Duration::from_secs(1),
+		);
+
+		// Enough time for interval to run
+		futures_timer::Delay::new(Duration::from_millis(1500)).await;
+		let (indexed_blocks, indexed_logs) = indexed_data(&pool).await;
+		assert_eq!(indexed_blocks.last().map(|block| block.0), Some(6));
+		assert_eq!(indexed_logs.len(), 6);
+
+		// The index can't be reset above its head, unless forced.
+		assert!(indexer_backend.reset_to_block(7, false).await.is_err());
+		indexer_backend
+			.reset_to_block(3, false)
+			.await
+			.expect("index reset");
+		let (blocks, logs) = indexed_data(&pool).await;
+		assert_eq!(blocks[..], indexed_blocks[..blocks.len()]);
+		assert_eq!(blocks.last().map(|block| block.0), Some(3));
+		assert_eq!(logs[..], indexed_logs[..3]);
+
+		// The next interval indexes the leaves again, down to the block the index was reset to.
+		futures_timer::Delay::new(Duration::from_millis(1500)).await;
+		assert_eq!(indexed_data(&pool).await, (indexed_blocks, indexed_logs));
+	}
+
 	struct TestSyncOracle {
 		sync_status: Arc<Mutex<bool>>,
 	}
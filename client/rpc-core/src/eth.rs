@@ -85,12 +85,22 @@ pub trait EthApi {
 		number_or_hash: BlockNumberOrHash,
 	) -> RpcResult<Option<U256>>;
 
+	/// Returns `count` transactions of a block from the given transaction index, so that the
+	/// transactions of large blocks can be fetched in pages. Nodes cap the size of a page.
+	#[method(name = "eth_getBlockTransactions")]
+	async fn block_transactions(
+		&self,
+		number_or_hash: BlockNumberOrHash,
+		offset: Index,
+		count: Index,
+	) -> RpcResult<Option<Vec<Transaction>>>;
+
 	/// Returns the receipts of a block by number or hash.
 	#[method(name = "eth_getBlockReceipts")]
 	async fn block_transaction_receipts(
 		&self,
 		number_or_hash: BlockNumberOrHash,
-	) -> RpcResult<Option<Vec<Receipt>>>;
+	) -> RpcResult<Option<Vec<FormattedReceipt>>>;
 
 	/// Returns the number of uncles in a block with given hash.
 	#[method(name = "eth_getUncleCountByBlockHash")]
@@ -142,7 +152,11 @@ pub trait EthApi {
 
 	/// Returns transaction receipt by transaction hash.
 	#[method(name = "eth_getTransactionReceipt")]
-	async fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>>;
+	async fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<FormattedReceipt>>;
+
+	/// Returns the gas price paid by a mined transaction.
+	#[method(name = "eth_getTransactionEffectiveGasPrice")]
+	async fn transaction_effective_gas_price(&self, hash: H256) -> RpcResult<Option<U256>>;
 
 	// ########################################################################
 	// State
@@ -232,7 +246,8 @@ pub trait EthApi {
 	// Mining
 	// ########################################################################
 
-	/// Returns true if client is actively mining new blocks.
+	/// Returns true if client is actively mining new blocks. Always false, since the node does not
+	/// mine work submitted by clients.
 	#[method(name = "eth_mining")]
 	fn is_mining(&self) -> RpcResult<bool>;
 
@@ -264,6 +279,16 @@ pub trait EthApi {
 	/// Sends signed transaction, returning its hash.
 	#[method(name = "eth_sendRawTransaction")]
 	async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256>;
+
+	/// Signs data as an EIP-191 personal message with the given account, returning the 65 bytes
+	/// signature. Only available on development nodes, with a signer of the account.
+	#[method(name = "eth_sign")]
+	fn sign(&self, address: H160, data: Bytes) -> RpcResult<Bytes>;
+
+	/// Signs transaction without sending it, returning the encoded signed transaction. Only
+	/// available on development nodes, with a signer of the account.
+	#[method(name = "eth_signTransaction")]
+	async fn sign_transaction(&self, request: TransactionRequest) -> RpcResult<Bytes>;
 }
 
 /// Eth filters rpc api (polling).
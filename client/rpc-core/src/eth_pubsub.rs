@@ -18,7 +18,7 @@
 
 //! Eth PUB-SUB rpc interface.
 
-use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
 use crate::types::pubsub;
 
@@ -32,4 +32,10 @@ pub trait EthPubSubApi {
 		item = pubsub::Result
 	)]
 	fn subscribe(&self, kind: pubsub::Kind, params: Option<pubsub::Params>);
+
+	/// Returns the active `eth_subscribe` subscriptions of the calling connection, to help
+	/// finding the ones a client forgot to cancel. Node-local, so it is served in the
+	/// `frontier_` namespace rather than in the Ethereum one.
+	#[method(name = "frontier_activeSubscriptions", with_extensions)]
+	fn active_subscriptions(&self) -> RpcResult<Vec<pubsub::ActiveSubscription>>;
 }
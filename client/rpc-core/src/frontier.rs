@@ -0,0 +1,48 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Frontier rpc interface.
+
+use ethereum_types::U256;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+use crate::types::{pubsub, IndexedBlock};
+
+/// Frontier rpc interface.
+#[rpc(server)]
+#[async_trait]
+pub trait FrontierApi {
+	/// Returns the number of the most recent finalized Ethereum block.
+	#[method(name = "frontier_finalizedBlockNumber")]
+	fn finalized_block_number(&self) -> RpcResult<U256>;
+
+	/// Returns the latest Ethereum block whose transactions and logs the node can serve.
+	///
+	/// It lags behind the chain head while the node indexes the blocks, e.g. during the initial
+	/// sync. Cheap enough to be used by health probes.
+	#[method(name = "frontier_latestIndexedBlock")]
+	async fn latest_indexed_block(&self) -> RpcResult<Option<IndexedBlock>>;
+
+	/// Subscribe to Frontier subscription.
+	#[subscription(
+		name = "frontier_subscribe" => "frontier_subscription",
+		unsubscribe = "frontier_unsubscribe",
+		item = pubsub::PubSubResult
+	)]
+	fn subscribe(&self, kind: pubsub::FrontierKind);
+}
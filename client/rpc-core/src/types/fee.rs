@@ -41,6 +41,13 @@ pub struct FeeHistory {
 	/// An array of effective priority fee per gas data points from a single
 	/// block. All zeroes are returned if the block is empty.
 	pub reward: Option<Vec<Vec<U256>>>,
+	/// An array of block base fees per blob gas, including the next block after
+	/// the newest of the returned range. Zeroes are returned as blob transactions
+	/// are not supported.
+	pub base_fee_per_blob_gas: Vec<U256>,
+	/// An array of block blob gas used ratios. Zeroes are returned as blob
+	/// transactions are not supported.
+	pub blob_gas_used_ratio: Vec<f64>,
 }
 
 pub type FeeHistoryCache = Arc<Mutex<BTreeMap<u64, FeeHistoryCacheItem>>>;
@@ -52,3 +59,32 @@ pub struct FeeHistoryCacheItem {
 	pub gas_used_ratio: f64,
 	pub rewards: Vec<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fee_history_serialize() {
+		let fee_history = FeeHistory {
+			oldest_block: U256::from(1),
+			base_fee_per_gas: vec![U256::from(7), U256::from(8)],
+			gas_used_ratio: vec![0.5],
+			reward: None,
+			base_fee_per_blob_gas: vec![U256::zero(), U256::zero()],
+			blob_gas_used_ratio: vec![0.0],
+		};
+
+		assert_eq!(
+			serde_json::to_value(&fee_history).unwrap(),
+			serde_json::json!({
+				"oldestBlock": "0x1",
+				"baseFeePerGas": ["0x7", "0x8"],
+				"gasUsedRatio": [0.5],
+				"reward": null,
+				"baseFeePerBlobGas": ["0x0", "0x0"],
+				"blobGasUsedRatio": [0.0],
+			})
+		);
+	}
+}
@@ -57,10 +57,10 @@ pub use self::{
 	},
 	index::Index,
 	log::Log,
-	receipt::Receipt,
+	receipt::{serialize_receipt_v1, FormattedReceipt, Receipt, ReceiptFormat},
 	sync::{
-		ChainStatus, EthProtocolInfo, PeerCount, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
-		Peers, PipProtocolInfo, SyncInfo, SyncStatus, TransactionStats,
+		ChainStatus, EthProtocolInfo, IndexedBlock, PeerCount, PeerInfo, PeerNetworkInfo,
+		PeerProtocolsInfo, Peers, PipProtocolInfo, SyncInfo, SyncStatus, TransactionStats,
 	},
 	transaction::{LocalTransactionStatus, RichRawTransaction, Transaction},
 	transaction_request::{TransactionMessage, TransactionRequest},
@@ -32,7 +32,7 @@ use sp_crypto_hashing::keccak_256;
 use crate::types::{Bytes, Filter, FilteredParams, Header, Log, Rich, RichHeader};
 
 /// Subscription kind.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "camelCase")]
 pub enum Kind {
@@ -46,6 +46,24 @@ pub enum Kind {
 	Syncing,
 }
 
+/// An active subscription of a connection.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ActiveSubscription {
+	/// Subscription id.
+	pub id: String,
+	/// Subscription kind.
+	pub kind: Kind,
+}
+
+/// Frontier subscription kind.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub enum FrontierKind {
+	/// Finalized block headers subscription.
+	FinalizedHeads,
+}
+
 /// Subscription kind.
 #[derive(Clone, Debug, Eq, PartialEq, Default, Hash)]
 pub enum Params {
@@ -16,8 +16,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use std::str::FromStr;
+
 use ethereum_types::{Bloom as H2048, H160, H256, U256, U64};
-use serde::Serialize;
+use serde::{ser::Error as _, Serialize, Serializer};
 
 use crate::types::Log;
 
@@ -61,3 +63,170 @@ pub struct Receipt {
 	#[serde(rename = "type")]
 	pub transaction_type: U256,
 }
+
+/// JSON format of the receipts, by version. New versions are added when Ethereum changes the
+/// format, so that the receipts can be served in the format expected by a given fork.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReceiptFormat {
+	/// The format of geth v1.13.
+	V1,
+	/// The [`V1`](Self::V1) format along with the `transactionLogIndex` of the logs, served by
+	/// Frontier so far.
+	#[default]
+	Frontier,
+}
+
+impl ReceiptFormat {
+	/// Wraps the `receipt` so that it serializes in this format.
+	pub fn format(&self, receipt: Receipt) -> FormattedReceipt {
+		FormattedReceipt {
+			format: *self,
+			receipt,
+		}
+	}
+
+	/// Serializes the `receipt` in this format.
+	pub fn serialize(&self, receipt: &Receipt) -> serde_json::Value {
+		serde_json::to_value(self.format(receipt.clone())).expect("receipts serialize to JSON")
+	}
+}
+
+impl FromStr for ReceiptFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"v1" => Ok(Self::V1),
+			"frontier" => Ok(Self::Frontier),
+			_ => Err(format!(
+				"unknown receipt format `{s}`, expected `v1` or `frontier`"
+			)),
+		}
+	}
+}
+
+/// A receipt along with the format it is served in.
+#[derive(Clone, Debug)]
+pub struct FormattedReceipt {
+	/// Format of the receipt
+	pub format: ReceiptFormat,
+	/// Receipt
+	pub receipt: Receipt,
+}
+
+impl Serialize for FormattedReceipt {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self.format {
+			ReceiptFormat::V1 => {
+				// The logs of the geth format have no `transactionLogIndex`.
+				let mut receipt = serde_json::to_value(&self.receipt).map_err(S::Error::custom)?;
+				if let Some(logs) = receipt["logs"].as_array_mut() {
+					for log in logs.iter_mut().filter_map(|log| log.as_object_mut()) {
+						log.remove("transactionLogIndex");
+					}
+				}
+				receipt.serialize(serializer)
+			}
+			ReceiptFormat::Frontier => self.receipt.serialize(serializer),
+		}
+	}
+}
+
+/// Serializes the `receipt` in the [`ReceiptFormat::V1`] format.
+pub fn serialize_receipt_v1(receipt: &Receipt) -> serde_json::Value {
+	ReceiptFormat::V1.serialize(receipt)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::Bytes;
+	use ethereum_types::BloomInput;
+
+	/// An ERC-20 transfer of 1 token from `0xaa..aa` to `0xcc..cc`.
+	fn transfer_receipt() -> Receipt {
+		let transaction_hash = H256::repeat_byte(0x11);
+		let block_hash = H256::repeat_byte(0x22);
+		let token = H160::repeat_byte(0xbb);
+		let log = Log {
+			address: token,
+			topics: vec![
+				// keccak256("Transfer(address,address,uint256)")
+				H256::from_str("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")
+					.unwrap(),
+				H256::from(H160::repeat_byte(0xaa)),
+				H256::from(H160::repeat_byte(0xcc)),
+			],
+			data: Bytes(
+				H256::from_low_u64_be(1_000_000_000_000_000_000)
+					.as_bytes()
+					.to_vec(),
+			),
+			block_hash: Some(block_hash),
+			block_number: Some(U256::from(0x10)),
+			transaction_hash: Some(transaction_hash),
+			transaction_index: Some(U256::one()),
+			log_index: Some(U256::from(2)),
+			transaction_log_index: Some(U256::zero()),
+			removed: false,
+		};
+		let mut logs_bloom = H2048::zero();
+		logs_bloom.accrue(BloomInput::Raw(log.address.as_bytes()));
+		for topic in &log.topics {
+			logs_bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+		}
+		Receipt {
+			transaction_hash: Some(transaction_hash),
+			transaction_index: Some(U256::one()),
+			block_hash: Some(block_hash),
+			from: Some(H160::repeat_byte(0xaa)),
+			to: Some(token),
+			block_number: Some(U256::from(0x10)),
+			cumulative_gas_used: U256::from(72_234),
+			gas_used: Some(U256::from(51_234)),
+			contract_address: None,
+			logs: vec![log],
+			state_root: None,
+			logs_bloom,
+			status_code: Some(U64::one()),
+			effective_gas_price: U256::from(1_500_000_000),
+			transaction_type: U256::from(2),
+		}
+	}
+
+	fn golden_receipt() -> serde_json::Value {
+		serde_json::from_str(include_str!("./res/receipt_v1.json")).unwrap()
+	}
+
+	#[test]
+	fn receipt_v1_format_is_unchanged() {
+		assert_eq!(serialize_receipt_v1(&transfer_receipt()), golden_receipt());
+	}
+
+	#[test]
+	fn frontier_format_adds_the_transaction_log_index() {
+		let mut expected = golden_receipt();
+		expected["logs"][0]["transactionLogIndex"] = "0x0".into();
+		assert_eq!(
+			ReceiptFormat::Frontier.serialize(&transfer_receipt()),
+			expected
+		);
+	}
+
+	#[test]
+	fn frontier_format_keeps_unknown_transaction_log_indexes() {
+		let mut receipt = transfer_receipt();
+		receipt.logs[0].transaction_log_index = None;
+		let mut expected = golden_receipt();
+		expected["logs"][0]["transactionLogIndex"] = serde_json::Value::Null;
+		assert_eq!(ReceiptFormat::Frontier.serialize(&receipt), expected);
+		assert_eq!(serialize_receipt_v1(&receipt), golden_receipt());
+	}
+
+	#[test]
+	fn receipt_formats_parse() {
+		assert_eq!("v1".parse(), Ok(ReceiptFormat::V1));
+		assert_eq!("frontier".parse(), Ok(ReceiptFormat::Frontier));
+		assert!("v2".parse::<ReceiptFormat>().is_err());
+	}
+}
@@ -18,7 +18,7 @@
 
 use std::collections::BTreeMap;
 
-use ethereum_types::{H512, U256};
+use ethereum_types::{H256, H512, U256};
 use serde::{Serialize, Serializer};
 
 /// Sync info
@@ -37,6 +37,15 @@ pub struct SyncInfo {
 	pub warp_chunks_processed: Option<U256>,
 }
 
+/// The latest Ethereum block indexed by the node
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct IndexedBlock {
+	/// Block number
+	pub number: U256,
+	/// Block hash
+	pub hash: H256,
+}
+
 /// Peers info
 #[derive(Debug, Default, Serialize)]
 pub struct Peers {
@@ -58,6 +58,9 @@ pub struct Transaction {
 	/// The miner's tip.
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub max_priority_fee_per_gas: Option<U256>,
+	/// The gas price paid by the transaction, once mined.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub effective_gas_price: Option<U256>,
 	/// Data
 	pub input: Bytes,
 	/// Creates contract
@@ -107,6 +110,7 @@ impl BuildFrom for Transaction {
 				gas_price: Some(t.gas_price),
 				max_fee_per_gas: None,
 				max_priority_fee_per_gas: None,
+				effective_gas_price: None,
 				input: Bytes(t.input.clone()),
 				creates: None,
 				chain_id: t.signature.chain_id().map(U64::from),
@@ -134,6 +138,7 @@ impl BuildFrom for Transaction {
 				gas_price: Some(t.gas_price),
 				max_fee_per_gas: None,
 				max_priority_fee_per_gas: None,
+				effective_gas_price: None,
 				input: Bytes(t.input.clone()),
 				creates: None,
 				chain_id: Some(U64::from(t.chain_id)),
@@ -162,6 +167,7 @@ impl BuildFrom for Transaction {
 				gas_price: Some(t.max_fee_per_gas),
 				max_fee_per_gas: Some(t.max_fee_per_gas),
 				max_priority_fee_per_gas: Some(t.max_priority_fee_per_gas),
+				effective_gas_price: None,
 				input: Bytes(t.input.clone()),
 				creates: None,
 				chain_id: Some(U64::from(t.chain_id)),
@@ -189,6 +195,7 @@ impl BuildFrom for Transaction {
 				gas_price: Some(t.max_fee_per_gas),
 				max_fee_per_gas: Some(t.max_fee_per_gas),
 				max_priority_fee_per_gas: Some(t.max_priority_fee_per_gas),
+				effective_gas_price: None,
 				input: Bytes(t.data.clone()),
 				creates: None,
 				chain_id: Some(U64::from(t.chain_id)),
@@ -293,3 +300,99 @@ pub struct RichRawTransaction {
 	#[serde(rename = "tx")]
 	pub transaction: Transaction,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::{
+		eip2930::TransactionSignature, legacy::TransactionSignature as LegacyTransactionSignature,
+		EIP1559Transaction, EIP2930Transaction, EIP7702Transaction, LegacyTransaction,
+	};
+	use serde_json::json;
+
+	#[test]
+	fn typed_transaction_serializes_y_parity_and_v() {
+		let transaction = EthereumTransaction::EIP1559(EIP1559Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			max_priority_fee_per_gas: U256::from(1),
+			max_fee_per_gas: U256::from(2),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0x11)),
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			signature: TransactionSignature::new(true, H256::repeat_byte(1), H256::repeat_byte(2))
+				.unwrap(),
+		});
+
+		let serialized =
+			serde_json::to_value(Transaction::build_from(H160::zero(), &transaction)).unwrap();
+		assert_eq!(serialized["type"], json!("0x2"));
+		assert_eq!(serialized["yParity"], json!("0x1"));
+		assert_eq!(serialized["v"], json!("0x1"));
+		assert_eq!(serialized["chainId"], json!("0x2a"));
+	}
+
+	#[test]
+	fn legacy_transaction_serializes_eip155_v_only() {
+		let transaction = EthereumTransaction::Legacy(LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0x11)),
+			value: U256::zero(),
+			input: vec![],
+			// EIP-155: `chain_id * 2 + 35` for an even y.
+			signature: LegacyTransactionSignature::new(
+				42 * 2 + 35,
+				H256::repeat_byte(1),
+				H256::repeat_byte(2),
+			)
+			.unwrap(),
+		});
+
+		let serialized =
+			serde_json::to_value(Transaction::build_from(H160::zero(), &transaction)).unwrap();
+		assert_eq!(serialized["type"], json!("0x0"));
+		assert_eq!(serialized["v"], json!("0x77"));
+		assert_eq!(serialized["chainId"], json!("0x2a"));
+		assert!(serialized.get("yParity").is_none());
+	}
+
+	#[test]
+	fn typed_transactions_serialize_their_type() {
+		let signature =
+			TransactionSignature::new(false, H256::repeat_byte(1), H256::repeat_byte(2)).unwrap();
+		let eip2930 = EthereumTransaction::EIP2930(EIP2930Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0x11)),
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			signature: signature.clone(),
+		});
+		let eip7702 = EthereumTransaction::EIP7702(EIP7702Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			max_priority_fee_per_gas: U256::from(1),
+			max_fee_per_gas: U256::from(2),
+			gas_limit: U256::from(21_000),
+			destination: TransactionAction::Call(H160::repeat_byte(0x11)),
+			value: U256::zero(),
+			data: vec![],
+			access_list: vec![],
+			authorization_list: vec![],
+			signature,
+		});
+
+		for (transaction, transaction_type) in [(eip2930, "0x1"), (eip7702, "0x4")] {
+			let serialized =
+				serde_json::to_value(Transaction::build_from(H160::zero(), &transaction)).unwrap();
+			assert_eq!(serialized["type"], json!(transaction_type));
+		}
+	}
+}
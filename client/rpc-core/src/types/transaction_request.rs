@@ -446,4 +446,25 @@ mod tests {
 			}
 		);
 	}
+
+	#[test]
+	fn data_is_the_input_of_the_transaction_message() {
+		let data = json!({
+			"from": "0x60be2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"to": "0x13fe2d1d3665660d22ff9624b7be0551ee1ac91b",
+			"gasPrice": "0x10",
+			"gas": "0x40",
+			"data": "0x123abc",
+			"nonce": "0x60"
+		});
+
+		let args = serde_json::from_value::<TransactionRequest>(data).unwrap();
+		assert_eq!(args.data(), Some(&Bytes::from(vec![0x12, 0x3a, 0xbc])));
+		match Option::<TransactionMessage>::from(args) {
+			Some(TransactionMessage::Legacy(message)) => {
+				assert_eq!(message.input, vec![0x12, 0x3a, 0xbc]);
+			}
+			_ => panic!("expected a legacy transaction message"),
+		}
+	}
 }
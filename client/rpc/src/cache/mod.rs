@@ -318,9 +318,8 @@ where
 			};
 			if let (Some(block), Some(receipts)) = (block, receipts) {
 				block_number = Some(UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.number));
-				let gas_used = UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.gas_used) as f64;
-				let gas_limit = UniqueSaturatedInto::<u64>::unique_saturated_into(block.header.gas_limit) as f64;
-				result.gas_used_ratio = gas_used / gas_limit;
+				// Use the gas limit in effect for this block, it may change over time.
+				result.gas_used_ratio = gas_used_ratio(block.header.gas_used, block.header.gas_limit);
 
 				let mut previous_cumulative_gas = U256::zero();
 				let used_gas = |current: U256, previous: &mut U256| -> u64 {
@@ -430,3 +429,39 @@ where
 		}
 	}
 }
+
+/// Ratio of the gas used by a block to its gas limit, zero for a block without gas limit.
+fn gas_used_ratio(gas_used: U256, gas_limit: U256) -> f64 {
+	let gas_used = UniqueSaturatedInto::<u64>::unique_saturated_into(gas_used) as f64;
+	let gas_limit = UniqueSaturatedInto::<u64>::unique_saturated_into(gas_limit) as f64;
+	if gas_limit == 0f64 {
+		return 0f64;
+	}
+	gas_used / gas_limit
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn gas_used_ratio_follows_block_gas_limit() {
+		// The gas limit is raised mid-range, the same gas used gives a lower ratio.
+		let blocks = [
+			(U256::from(7_500_000), U256::from(15_000_000)),
+			(U256::from(7_500_000), U256::from(15_000_000)),
+			(U256::from(7_500_000), U256::from(30_000_000)),
+			(U256::from(30_000_000), U256::from(30_000_000)),
+		];
+		let ratios: Vec<f64> = blocks
+			.iter()
+			.map(|(gas_used, gas_limit)| gas_used_ratio(*gas_used, *gas_limit))
+			.collect();
+		assert_eq!(ratios, vec![0.5, 0.5, 0.25, 1.0]);
+	}
+
+	#[test]
+	fn gas_used_ratio_is_zero_without_gas_limit() {
+		assert_eq!(gas_used_ratio(U256::zero(), U256::zero()), 0f64);
+	}
+}
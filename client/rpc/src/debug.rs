@@ -95,7 +95,6 @@ impl<B: BlockT, C, BE> Debug<B, C, BE> {
 			self.client.as_ref(),
 			self.backend.as_ref(),
 			transaction_hash,
-			true,
 		)
 		.await?
 		{
@@ -22,7 +22,7 @@ use ethereum_types::{H256, U256};
 use jsonrpsee::core::RpcResult;
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
-use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
+use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_core::hashing::keccak_256;
@@ -32,7 +32,10 @@ use fc_rpc_core::types::*;
 use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{
-	eth::{rich_block_build, BlockInfo, Eth},
+	eth::{
+		block_transactions_build, check_block_transactions_page, pending, rich_block_build,
+		BlockInfo, Eth,
+	},
 	frontier_backend_client, internal_err,
 };
 
@@ -50,12 +53,12 @@ where
 			block,
 			statuses,
 			substrate_hash,
-			base_fee,
 			..
 		} = self.block_info_by_eth_block_hash(hash).await?;
 
 		match (block, statuses) {
 			(Some(block), Some(statuses)) => {
+				let base_fee = self.included_base_fee(&block, substrate_hash).await?;
 				let mut rich_block = rich_block_build(
 					block,
 					statuses.into_iter().map(Option::Some).collect(),
@@ -98,26 +101,29 @@ where
 		.await?
 		{
 			Some(id) => {
-				let substrate_hash = client
-					.expect_block_hash_from_id(&id)
-					.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+				let substrate_hash = match client
+					.block_hash_from_id(&id)
+					.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?
+				{
+					Some(hash) => hash,
+					None => return Ok(None),
+				};
 
 				let block = block_data_cache.current_block(substrate_hash).await;
 				let statuses = block_data_cache
 					.current_transaction_statuses(substrate_hash)
 					.await;
 
-				let base_fee = client.runtime_api().gas_price(substrate_hash).ok();
-
 				match (block, statuses) {
 					(Some(block), Some(statuses)) => {
+						let base_fee = self.included_base_fee(&block, substrate_hash).await?;
 						let hash = H256::from(keccak_256(&rlp::encode(&block.header)));
 						let mut rich_block = rich_block_build(
 							block,
 							statuses.into_iter().map(Option::Some).collect(),
 							Some(hash),
 							full,
-							base_fee,
+							Some(base_fee),
 							false,
 						);
 
@@ -136,36 +142,11 @@ where
 				}
 			}
 			None if number_or_hash == BlockNumberOrHash::Pending => {
-				let api = client.runtime_api();
-				let best_hash = client.info().best_hash;
-
-				// Get current in-pool transactions
-				let mut xts: Vec<<B as BlockT>::Extrinsic> = Vec::new();
-				// ready validated pool
-				xts.extend(
-					graph
-						.ready()
-						.map(|in_pool_tx| in_pool_tx.data().as_ref().clone())
-						.collect::<Vec<<B as BlockT>::Extrinsic>>(),
-				);
-
-				// future validated pool
-				xts.extend(
-					graph
-						.futures()
-						.iter()
-						.map(|in_pool_tx| in_pool_tx.data().as_ref().clone())
-						.collect::<Vec<<B as BlockT>::Extrinsic>>(),
-				);
-
-				let (block, statuses) = api
-					.pending_block(best_hash, xts)
-					.map_err(|_| internal_err(format!("Runtime access error at {}", best_hash)))?;
-
-				let base_fee = api.gas_price(best_hash).ok();
+				let (best_hash, pending) = pending::pending_block(client.as_ref(), graph.as_ref())?;
+				let base_fee = client.runtime_api().gas_price(best_hash).ok();
 
-				match (block, statuses) {
-					(Some(block), Some(statuses)) => Ok(Some(rich_block_build(
+				match pending {
+					Some((block, statuses)) => Ok(Some(rich_block_build(
 						block,
 						statuses.into_iter().map(Option::Some).collect(),
 						None,
@@ -173,7 +154,7 @@ where
 						base_fee,
 						true,
 					))),
-					_ => Ok(None),
+					None => Ok(None),
 				}
 			}
 			None => Ok(None),
@@ -204,6 +185,36 @@ where
 		}
 	}
 
+	pub async fn block_transactions(
+		&self,
+		number_or_hash: BlockNumberOrHash,
+		offset: Index,
+		count: Index,
+	) -> RpcResult<Option<Vec<Transaction>>> {
+		let count = check_block_transactions_page(count.value(), self.max_block_transactions_page)?;
+		let BlockInfo {
+			block,
+			statuses,
+			substrate_hash,
+			..
+		} = self.block_info_by_number(number_or_hash).await?;
+
+		match (block, statuses) {
+			(Some(block), Some(statuses)) => {
+				let base_fee = self.included_base_fee(&block, substrate_hash).await?;
+				let statuses: Vec<_> = statuses.into_iter().map(Option::Some).collect();
+				Ok(Some(block_transactions_build(
+					&block,
+					&statuses,
+					Some(base_fee),
+					offset.value(),
+					count,
+				)))
+			}
+			_ => Ok(None),
+		}
+	}
+
 	pub async fn block_transaction_receipts(
 		&self,
 		number_or_hash: BlockNumberOrHash,
@@ -68,13 +68,16 @@ where
 		}
 	}
 
+	/// The author of the best block, as recorded in its Ethereum header. Falls back to the zero
+	/// address when the best block has no Ethereum block yet (e.g. before the Ethereum pallet
+	/// was added to the runtime).
 	pub fn author(&self) -> RpcResult<H160> {
 		let hash = self.client.info().best_hash;
-		let current_block = self
+		Ok(self
 			.storage_override
 			.current_block(hash)
-			.ok_or_else(|| internal_err("fetching author through override failed"))?;
-		Ok(current_block.header.beneficiary)
+			.map(|block| block.header.beneficiary)
+			.unwrap_or_default())
 	}
 
 	pub fn accounts(&self) -> RpcResult<Vec<H160>> {
@@ -98,6 +101,33 @@ where
 			.runtime_api()
 			.chain_id(hash)
 			.map_err(|err| internal_err(format!("fetch runtime chain id failed: {err:?}")))?;
-		Ok(Some(U64::from(chain_id)))
+		configured_chain_id(chain_id, self.allow_zero_chain_id).map(Some)
+	}
+}
+
+/// The chain id of the runtime, unless it is zero and zero isn't allowed: a zero chain id is
+/// most likely a missing configuration.
+fn configured_chain_id(chain_id: u64, allow_zero_chain_id: bool) -> RpcResult<U64> {
+	if chain_id == 0 && !allow_zero_chain_id {
+		return Err(internal_err("chain id is not configured"));
+	}
+	Ok(U64::from(chain_id))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn configured_chain_id_is_served() {
+		assert_eq!(configured_chain_id(42, false), Ok(U64::from(42)));
+		assert_eq!(configured_chain_id(42, true), Ok(U64::from(42)));
+	}
+
+	#[test]
+	fn zero_chain_id_must_be_allowed() {
+		let err = configured_chain_id(0, false).unwrap_err();
+		assert_eq!(err.message(), "chain id is not configured");
+		assert_eq!(configured_chain_id(0, true), Ok(U64::zero()));
 	}
 }
@@ -20,7 +20,10 @@ use std::{cell::RefCell, collections::BTreeMap, sync::Arc};
 
 use ethereum_types::{H160, H256, U256};
 use evm::{ExitError, ExitReason};
-use jsonrpsee::{core::RpcResult, types::error::CALL_EXECUTION_FAILED_CODE};
+use jsonrpsee::{
+	core::RpcResult,
+	types::error::{CALL_EXECUTION_FAILED_CODE, INVALID_PARAMS_CODE},
+};
 use scale_codec::{Decode, Encode};
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
@@ -44,7 +47,7 @@ use fp_storage::constants::{EVM_ACCOUNT_CODES, EVM_ACCOUNT_STORAGES, PALLET_EVM}
 
 use crate::{
 	eth::{Eth, EthConfig},
-	frontier_backend_client, internal_err,
+	frontier_backend_client, internal_err, runtime_api,
 };
 
 /// Allow to adapt a request for `estimate_gas`.
@@ -134,41 +137,21 @@ where
 		let ext = sp_trie::proof_size_extension::ProofSizeExt::new(recorder.clone());
 		api.register_extension(ext);
 
-		let api_version = if let Ok(Some(api_version)) =
-			api.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash)
-		{
-			api_version
-		} else {
-			return Err(internal_err("failed to retrieve Runtime Api version"));
-		};
+		let api_version = self.api_versions.version(&*api, substrate_hash)?;
 
-		let block = if api_version > 1 {
-			api.current_block(substrate_hash)
-				.map_err(|err| internal_err(format!("runtime error: {err}")))?
-		} else {
-			#[allow(deprecated)]
-			let legacy_block = api
-				.current_block_before_version_2(substrate_hash)
-				.map_err(|err| internal_err(format!("runtime error: {err}")))?;
-			legacy_block.map(|block| block.into())
-		};
+		let block = runtime_api::current_block(&*api, substrate_hash, api_version)
+			.map_err(|err| internal_err(format!("runtime error: {err}")))?;
 
 		let block_gas_limit = block
 			.ok_or_else(|| internal_err("block unavailable, cannot query gas limit"))?
 			.header
 			.gas_limit;
-		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
+		let max_gas_limit = max_gas_limit(block_gas_limit, self.execute_gas_limit_multiplier);
 
 		// use given gas limit or query current block's limit
 		let gas_limit = match gas {
 			Some(amount) => {
-				if amount > max_gas_limit {
-					return Err(internal_err(format!(
-						"provided gas limit is too high (can be up to {}x the block gas limit)",
-						self.execute_gas_limit_multiplier
-					)));
-				}
-				amount
+				check_gas_limit(amount, max_gas_limit, self.execute_gas_limit_multiplier)?
 			}
 			// If gas limit is not specified in the request we either use the multiplier if supported
 			// or fallback to the block gas limit.
@@ -593,18 +576,12 @@ where
 				.gas_limit
 		};
 
-		let max_gas_limit = block_gas_limit * self.execute_gas_limit_multiplier;
+		let max_gas_limit = max_gas_limit(block_gas_limit, self.execute_gas_limit_multiplier);
 
 		// Determine the highest possible gas limits
 		let mut highest = match request.gas {
 			Some(amount) => {
-				if amount > max_gas_limit {
-					return Err(internal_err(format!(
-						"provided gas limit is too high (can be up to {}x the block gas limit)",
-						self.execute_gas_limit_multiplier
-					)));
-				}
-				amount
+				check_gas_limit(amount, max_gas_limit, self.execute_gas_limit_multiplier)?
 			}
 			// If gas limit is not specified in the request we either use the multiplier if supported
 			// or fallback to the block gas limit.
@@ -630,30 +607,12 @@ where
 
 		// Recap the highest gas allowance with account's balance.
 		if let Some(from) = request.from {
-			if fee_cap > U256::zero() {
+			if self.estimate_balance_check && fee_cap > U256::zero() {
 				let balance = api
 					.account_basic(substrate_hash, from)
 					.map_err(|err| internal_err(format!("runtime error: {err}")))?
 					.balance;
-				let mut available = balance;
-				if let Some(value) = request.value {
-					if value > available {
-						return Err(internal_err("insufficient funds for transfer"));
-					}
-					available -= value;
-				}
-				let allowance = available / fee_cap;
-				if highest > allowance {
-					log::warn!(
-							"Gas estimation capped by limited funds original {} balance {} sent {} feecap {} fundable {}",
-							highest,
-							balance,
-							request.value.unwrap_or_default(),
-							fee_cap,
-							allowance
-						);
-					highest = allowance;
-				}
+				highest = gas_allowance(highest, balance, request.value, fee_cap)?;
 			}
 		}
 
@@ -1081,15 +1040,9 @@ where
 					used_gas,
 				})
 			};
-		let api_version = if let Ok(Some(api_version)) =
-			client
-				.runtime_api()
-				.api_version::<dyn EthereumRuntimeRPCApi<B>>(substrate_hash)
-		{
-			api_version
-		} else {
-			return Err(internal_err("failed to retrieve Runtime Api version"));
-		};
+		let api_version = self
+			.api_versions
+			.version(&*client.runtime_api(), substrate_hash)?;
 
 		// Verify that the transaction succeed with the highest capacity
 		let cap = highest;
@@ -1331,6 +1284,31 @@ struct FeeDetails {
 	fee_cap: U256,
 }
 
+/// The highest gas limit accepted for calls and gas estimations.
+fn max_gas_limit(block_gas_limit: U256, execute_gas_limit_multiplier: u64) -> U256 {
+	block_gas_limit.saturating_mul(U256::from(execute_gas_limit_multiplier))
+}
+
+/// Reject gas limits above the maximum with an invalid params error, so that the gas limit and
+/// its product with the gas price stay in range downstream.
+fn check_gas_limit(
+	gas_limit: U256,
+	max_gas_limit: U256,
+	execute_gas_limit_multiplier: u64,
+) -> RpcResult<U256> {
+	if gas_limit > max_gas_limit {
+		return Err(crate::err(
+			INVALID_PARAMS_CODE,
+			format!(
+				"provided gas limit is too high (can be up to {execute_gas_limit_multiplier}x the \
+				 block gas limit)"
+			),
+			None,
+		));
+	}
+	Ok(gas_limit)
+}
+
 fn fee_details(
 	request_gas_price: Option<U256>,
 	request_max_fee_per_gas: Option<U256>,
@@ -1341,30 +1319,28 @@ fn fee_details(
 		request_max_fee_per_gas,
 		request_priority_fee_per_gas,
 	) {
-		(Some(_), Some(_), Some(_)) => Err(internal_err(
+		(Some(_), Some(_), _) | (Some(_), _, Some(_)) => Err(internal_err(
 			"both gasPrice and (maxFeePerGas or maxPriorityFeePerGas) specified",
 		)),
+		(None, Some(max_fee), Some(max_priority)) if max_priority > max_fee => Err(internal_err(
+			"Invalid input: `max_priority_fee_per_gas` greater than `max_fee_per_gas`",
+		)),
+		// A zero fee is the same as no fee, once the request is validated: the call doesn't pay
+		// for gas, so users without funds can estimate their transactions.
 		// Legacy or EIP-2930 transaction.
-		(gas_price, None, None) if gas_price.is_some() => Ok(FeeDetails {
-			gas_price,
+		(Some(gas_price), None, None) if !gas_price.is_zero() => Ok(FeeDetails {
+			gas_price: Some(gas_price),
 			max_fee_per_gas: None,
 			max_priority_fee_per_gas: None,
-			fee_cap: gas_price.unwrap_or_default(),
+			fee_cap: gas_price,
 		}),
 		// EIP-1559 transaction
-		(None, Some(max_fee), Some(max_priority)) => {
-			if max_priority > max_fee {
-				return Err(internal_err(
-					"Invalid input: `max_priority_fee_per_gas` greater than `max_fee_per_gas`",
-				));
-			}
-			Ok(FeeDetails {
-				gas_price: None,
-				max_fee_per_gas: Some(max_fee),
-				max_priority_fee_per_gas: Some(max_priority),
-				fee_cap: max_fee,
-			})
-		}
+		(None, Some(max_fee), Some(max_priority)) if !max_fee.is_zero() => Ok(FeeDetails {
+			gas_price: None,
+			max_fee_per_gas: Some(max_fee),
+			max_priority_fee_per_gas: Some(max_priority),
+			fee_cap: max_fee,
+		}),
 		// Default to EIP-1559 transaction
 		_ => Ok(FeeDetails {
 			gas_price: None,
@@ -1375,3 +1351,139 @@ fn fee_details(
 		}),
 	}
 }
+
+/// Caps the gas limit of an estimation to the gas the sender can pay for at `fee_cap`, once
+/// the transferred `value` is paid. Fails if the sender can't pay for any gas.
+fn gas_allowance(
+	highest: U256,
+	balance: U256,
+	value: Option<U256>,
+	fee_cap: U256,
+) -> RpcResult<U256> {
+	if fee_cap.is_zero() {
+		return Ok(highest);
+	}
+	let value = value.unwrap_or_default();
+	let available = balance
+		.checked_sub(value)
+		.ok_or_else(|| internal_err("insufficient funds for transfer"))?;
+	let allowance = available / fee_cap;
+	if allowance.is_zero() {
+		return Err(internal_err("insufficient funds for gas * price + value"));
+	}
+	if highest > allowance {
+		log::warn!(
+			"Gas estimation capped by limited funds original {} balance {} sent {} feecap {} fundable {}",
+			highest,
+			balance,
+			value,
+			fee_cap,
+			allowance
+		);
+		return Ok(allowance);
+	}
+	Ok(highest)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn zero_fee_is_no_fee() {
+		for details in [
+			fee_details(None, None, None).unwrap(),
+			fee_details(Some(U256::zero()), None, None).unwrap(),
+			fee_details(None, Some(U256::zero()), Some(U256::zero())).unwrap(),
+		] {
+			assert_eq!(details.gas_price, None);
+			assert_eq!(details.max_fee_per_gas, None);
+			assert_eq!(details.fee_cap, U256::zero());
+		}
+
+		let details = fee_details(Some(U256::from(10)), None, None).unwrap();
+		assert_eq!(details.gas_price, Some(U256::from(10)));
+		assert_eq!(details.fee_cap, U256::from(10));
+	}
+
+	#[test]
+	fn zero_fee_is_validated_first() {
+		// A zero max fee doesn't hide a greater priority fee.
+		let err = fee_details(None, Some(U256::zero()), Some(U256::from(5))).unwrap_err();
+		assert_eq!(
+			err.message(),
+			"Invalid input: `max_priority_fee_per_gas` greater than `max_fee_per_gas`"
+		);
+
+		// Nor does it hide a gas price given along with the EIP-1559 fees.
+		for (max_fee, max_priority) in [
+			(Some(U256::zero()), Some(U256::from(5))),
+			(Some(U256::zero()), None),
+			(None, Some(U256::zero())),
+		] {
+			let err = fee_details(Some(U256::from(10)), max_fee, max_priority).unwrap_err();
+			assert_eq!(
+				err.message(),
+				"both gasPrice and (maxFeePerGas or maxPriorityFeePerGas) specified"
+			);
+		}
+	}
+
+	#[test]
+	fn estimate_without_fee_ignores_the_balance() {
+		let highest = U256::from(1_000_000);
+		assert_eq!(
+			gas_allowance(highest, U256::zero(), None, U256::zero()).ok(),
+			Some(highest)
+		);
+	}
+
+	#[test]
+	fn estimate_with_fee_is_capped_by_the_balance() {
+		let highest = U256::from(1_000_000);
+		let fee_cap = U256::from(1_000_000_000);
+
+		// A sender without funds can't pay for any gas.
+		assert_eq!(
+			gas_allowance(highest, U256::zero(), None, fee_cap)
+				.unwrap_err()
+				.message(),
+			"insufficient funds for gas * price + value"
+		);
+		assert_eq!(
+			gas_allowance(highest, U256::zero(), Some(U256::one()), fee_cap)
+				.unwrap_err()
+				.message(),
+			"insufficient funds for transfer"
+		);
+
+		// The transferred value is paid first.
+		let balance = fee_cap * 30_000 + 5;
+		assert_eq!(
+			gas_allowance(highest, balance, Some(U256::from(5)), fee_cap).ok(),
+			Some(U256::from(30_000))
+		);
+		assert_eq!(
+			gas_allowance(highest, U256::MAX, None, fee_cap).ok(),
+			Some(highest)
+		);
+	}
+
+	#[test]
+	fn gas_limit_above_maximum_is_invalid_params() {
+		let max_gas_limit = max_gas_limit(U256::from(15_000_000), 10);
+		assert_eq!(max_gas_limit, U256::from(150_000_000));
+
+		let err = check_gas_limit(U256::from(u64::MAX), max_gas_limit, 10).unwrap_err();
+		assert_eq!(err.code(), INVALID_PARAMS_CODE);
+		assert_eq!(
+			check_gas_limit(max_gas_limit, max_gas_limit, 10).ok(),
+			Some(max_gas_limit)
+		);
+	}
+
+	#[test]
+	fn max_gas_limit_saturates() {
+		assert_eq!(max_gas_limit(U256::MAX, u64::MAX), U256::MAX);
+	}
+}
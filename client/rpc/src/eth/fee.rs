@@ -87,6 +87,8 @@ where
 					base_fee_per_gas: Vec::new(),
 					gas_used_ratio: Vec::new(),
 					reward: None,
+					base_fee_per_blob_gas: Vec::new(),
+					blob_gas_used_ratio: Vec::new(),
 				};
 				let mut rewards = Vec::new();
 				// Iterate over the requested block range.
@@ -159,6 +161,10 @@ where
 							.push(U256::from(last_fee_per_gas as u64));
 					}
 				}
+				// Blob transactions are not supported, blob fields are zero-filled.
+				response.base_fee_per_blob_gas =
+					vec![U256::zero(); response.base_fee_per_gas.len()];
+				response.blob_gas_used_ratio = vec![0f64; response.gas_used_ratio.len()];
 				return Ok(response);
 			} else {
 				return Err(internal_err("Failed to read fee history cache."));
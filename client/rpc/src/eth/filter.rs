@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use std::{
-	collections::{BTreeMap, HashSet},
+	collections::HashSet,
 	marker::PhantomData,
 	sync::Arc,
 	time::{Duration, Instant},
@@ -40,7 +40,11 @@ use sp_runtime::{
 use fc_rpc_core::{types::*, EthFilterApiServer};
 use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
-use crate::{cache::EthBlockDataCacheTask, frontier_backend_client, internal_err};
+use crate::{
+	cache::EthBlockDataCacheTask,
+	eth::{log_filter, pending},
+	frontier_backend_client, internal_err,
+};
 
 pub struct EthFilter<B: BlockT, C, BE, P> {
 	client: Arc<C>,
@@ -50,6 +54,8 @@ pub struct EthFilter<B: BlockT, C, BE, P> {
 	max_stored_filters: usize,
 	max_past_logs: u32,
 	block_data_cache: Arc<EthBlockDataCacheTask<B>>,
+	/// Maximum size of the JSON encoding of the logs of a query, if limited.
+	max_response_size: Option<usize>,
 	_marker: PhantomData<BE>,
 }
 
@@ -71,9 +77,17 @@ impl<B: BlockT, C, BE, P: TransactionPool> EthFilter<B, C, BE, P> {
 			max_stored_filters,
 			max_past_logs,
 			block_data_cache,
+			max_response_size: None,
 			_marker: PhantomData,
 		}
 	}
+
+	/// Fails the log queries and filter changes whose JSON encoding is larger than
+	/// `max_response_size` bytes, with an error asking to narrow the query.
+	pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+		self.max_response_size = Some(max_response_size);
+		self
+	}
 }
 
 impl<B, C, BE, P> EthFilter<B, C, BE, P>
@@ -84,6 +98,21 @@ where
 	C: HeaderBackend<B> + 'static,
 	P: TransactionPool<Block = B, Hash = B::Hash> + 'static,
 {
+	/// The logs of the pending block, built from the transactions of the pool on top of the best
+	/// block. The pending block has no hash yet, so neither do its logs.
+	fn pending_block_logs(&self, filter: &Filter) -> RpcResult<Vec<Log>> {
+		let (_, pending) = pending::pending_block(self.client.as_ref(), self.graph.as_ref())?;
+
+		Ok(match pending {
+			Some((block, statuses)) => log_filter::filter_pending_block_logs(
+				&log_filter::matcher(filter),
+				block.header.number,
+				&statuses,
+			),
+			None => Vec::new(),
+		})
+	}
+
 	fn create_filter(&self, filter_type: FilterType) -> RpcResult<U256> {
 		let info = self.client.info();
 		let best_hash = info.best_hash;
@@ -313,8 +342,8 @@ where
 		let block_data_cache = Arc::clone(&self.block_data_cache);
 		let max_past_logs = self.max_past_logs;
 
-		match path {
-			FuturePath::Error(err) => Err(err),
+		let changes = match path {
+			FuturePath::Error(err) => return Err(err),
 			FuturePath::Block { last, next } => {
 				let mut ethereum_hashes: Vec<H256> = Vec::new();
 				for n in last..next {
@@ -328,9 +357,9 @@ where
 						ethereum_hashes.push(block.header.hash())
 					}
 				}
-				Ok(FilterChanges::Hashes(ethereum_hashes))
+				FilterChanges::Hashes(ethereum_hashes)
 			}
-			FuturePath::PendingTransaction { new_hashes } => Ok(FilterChanges::Hashes(new_hashes)),
+			FuturePath::PendingTransaction { new_hashes } => FilterChanges::Hashes(new_hashes),
 			FuturePath::Log {
 				filter,
 				from_number,
@@ -362,9 +391,11 @@ where
 					.await?;
 				}
 
-				Ok(FilterChanges::Logs(ret))
+				FilterChanges::Logs(ret)
 			}
-		}
+		};
+		crate::ensure_response_size(&changes, self.max_response_size)?;
+		Ok(changes)
 	}
 
 	async fn filter_logs(&self, index: Index) -> RpcResult<Vec<Log>> {
@@ -417,6 +448,12 @@ where
 
 		let mut ret: Vec<Log> = Vec::new();
 		if backend.is_indexed() {
+			frontier_backend_client::ensure_indexed::<B, C>(
+				client.as_ref(),
+				backend.as_ref(),
+				current_number,
+			)
+			.await?;
 			let _ = filter_range_logs_indexed(
 				client.as_ref(),
 				backend.log_indexer(),
@@ -440,6 +477,7 @@ where
 			)
 			.await?;
 		}
+		crate::ensure_response_size(&ret, self.max_response_size)?;
 		Ok(ret)
 	}
 
@@ -486,6 +524,8 @@ where
 			if let (Some(block), Some(statuses)) = (block, statuses) {
 				filter_block_logs(&mut ret, &filter, block, statuses);
 			}
+		} else if matches!(filter.from_block, Some(BlockNumberOrHash::Pending)) {
+			// Only the pending block is requested.
 		} else {
 			let best_number = client.info().best_number;
 			let mut current_number = filter
@@ -505,6 +545,12 @@ where
 				.unwrap_or(best_number);
 
 			if backend.is_indexed() {
+				frontier_backend_client::ensure_indexed::<B, C>(
+					client.as_ref(),
+					backend.as_ref(),
+					current_number,
+				)
+				.await?;
 				let _ = filter_range_logs_indexed(
 					client.as_ref(),
 					backend.log_indexer(),
@@ -529,6 +575,21 @@ where
 				.await?;
 			}
 		}
+
+		// The pending block is built from the pool on every query, its logs are never
+		// cached.
+		if filter.block_hash.is_none()
+			&& matches!(filter.to_block, Some(BlockNumberOrHash::Pending))
+		{
+			ret.extend(self.pending_block_logs(&filter)?);
+			if ret.len() as u32 > max_past_logs {
+				return Err(internal_err(format!(
+					"query returned more than {} results",
+					max_past_logs
+				)));
+			}
+		}
+		crate::ensure_response_size(&ret, self.max_response_size)?;
 		Ok(ret)
 	}
 }
@@ -557,32 +618,13 @@ where
 	let max_duration = Duration::from_secs(10);
 	let begin_request = Instant::now();
 
-	let topics_input = if filter.topics.is_some() {
-		let filtered_params = FilteredParams::new(Some(filter.clone()));
-		Some(filtered_params.flat_topics)
-	} else {
-		None
-	};
-
-	// Normalize filter data
-	let addresses = match &filter.address {
-		Some(VariadicValue::Single(item)) => vec![*item],
-		Some(VariadicValue::Multiple(items)) => items.clone(),
-		_ => vec![],
-	};
-	let topics = topics_input
-		.unwrap_or_default()
-		.iter()
-		.map(|flat| match flat {
-			VariadicValue::Single(item) => vec![*item],
-			VariadicValue::Multiple(items) => items.clone(),
-			_ => vec![],
-		})
-		.collect::<Vec<Vec<Option<H256>>>>();
+	// The backend selects candidate logs, which are then matched like in the KV backend.
+	let matcher = log_filter::matcher(filter);
+	let (addresses, topics) = matcher.indexer_input();
 
 	let time_prepare = timer_prepare.elapsed().as_millis();
 	let timer_fetch = Instant::now();
-	if let Ok(logs) = backend
+	let logs = backend
 		.filter_logs(
 			UniqueSaturatedInto::<u64>::unique_saturated_into(from),
 			UniqueSaturatedInto::<u64>::unique_saturated_into(to),
@@ -590,81 +632,50 @@ where
 			topics,
 		)
 		.await
-	{
-		let time_fetch = timer_fetch.elapsed().as_millis();
-		let timer_post = Instant::now();
-
-		let mut statuses_cache: BTreeMap<B::Hash, Option<Vec<TransactionStatus>>> = BTreeMap::new();
-
-		for log in logs.iter() {
-			let substrate_hash = log.substrate_block_hash;
-
-			let ethereum_block_hash = log.ethereum_block_hash;
-			let block_number = log.block_number;
-			let db_transaction_index = log.transaction_index;
-			let db_log_index = log.log_index;
-
-			let statuses = if let Some(statuses) = statuses_cache.get(&log.substrate_block_hash) {
-				statuses.clone()
-			} else {
-				let statuses = block_data_cache
-					.current_transaction_statuses(substrate_hash)
-					.await;
-				statuses_cache.insert(log.substrate_block_hash, statuses.clone());
-				statuses
-			};
-			if let Some(statuses) = statuses {
-				let mut block_log_index: u32 = 0;
-				for status in statuses.iter() {
-					let mut transaction_log_index: u32 = 0;
-					let transaction_hash = status.transaction_hash;
-					let transaction_index = status.transaction_index;
-					for ethereum_log in &status.logs {
-						if transaction_index == db_transaction_index
-							&& transaction_log_index == db_log_index
-						{
-							ret.push(Log {
-								address: ethereum_log.address,
-								topics: ethereum_log.topics.clone(),
-								data: Bytes(ethereum_log.data.clone()),
-								block_hash: Some(ethereum_block_hash),
-								block_number: Some(U256::from(block_number)),
-								transaction_hash: Some(transaction_hash),
-								transaction_index: Some(U256::from(transaction_index)),
-								log_index: Some(U256::from(block_log_index)),
-								transaction_log_index: Some(U256::from(transaction_log_index)),
-								removed: false,
-							});
-						}
-						transaction_log_index += 1;
-						block_log_index += 1;
-					}
-				}
-			}
-			// Check for restrictions
-			if ret.len() as u32 > max_past_logs {
-				return Err(internal_err(format!(
-					"query returned more than {} results",
-					max_past_logs
-				)));
-			}
-			if begin_request.elapsed() > max_duration {
-				return Err(internal_err(format!(
-					"query timeout of {} seconds exceeded",
-					max_duration.as_secs()
-				)));
-			}
+		.map_err(internal_err)?;
+	let time_fetch = timer_fetch.elapsed().as_millis();
+	let timer_post = Instant::now();
+
+	for block in log_filter::group_indexed_logs(&logs) {
+		let statuses = block_data_cache
+			.current_transaction_statuses(block.substrate_block_hash)
+			.await;
+		if let Some(statuses) = statuses {
+			ret.extend(log_filter::filter_block_logs(
+				&matcher,
+				block.ethereum_block_hash,
+				U256::from(block.block_number),
+				&statuses,
+				|transaction_index, transaction_log_index| {
+					block
+						.candidates
+						.contains(&(transaction_index, transaction_log_index))
+				},
+			));
 		}
+		// Check for restrictions
+		if ret.len() as u32 > max_past_logs {
+			return Err(internal_err(format!(
+				"query returned more than {} results",
+				max_past_logs
+			)));
+		}
+		if begin_request.elapsed() > max_duration {
+			return Err(internal_err(format!(
+				"query timeout of {} seconds exceeded",
+				max_duration.as_secs()
+			)));
+		}
+	}
 
-		let time_post = timer_post.elapsed().as_millis();
+	let time_post = timer_post.elapsed().as_millis();
 
-		log::info!(
-			target: "frontier-sql",
-			"OUTER-TIMER fetch={}, post={}",
-			time_fetch,
-			time_post,
-		);
-	}
+	log::info!(
+		target: "frontier-sql",
+		"OUTER-TIMER fetch={}, post={}",
+		time_fetch,
+		time_post,
+	);
 
 	log::info!(
 		target: "frontier-sql",
@@ -756,56 +767,13 @@ fn filter_block_logs<'a>(
 	block: EthereumBlock,
 	transaction_statuses: Vec<TransactionStatus>,
 ) -> &'a Vec<Log> {
-	let params = FilteredParams::new(Some(filter.clone()));
-	let mut block_log_index: u32 = 0;
 	let block_hash = H256::from(keccak_256(&rlp::encode(&block.header)));
-	for status in transaction_statuses.iter() {
-		let mut transaction_log_index: u32 = 0;
-		let transaction_hash = status.transaction_hash;
-		for ethereum_log in &status.logs {
-			let mut log = Log {
-				address: ethereum_log.address,
-				topics: ethereum_log.topics.clone(),
-				data: Bytes(ethereum_log.data.clone()),
-				block_hash: None,
-				block_number: None,
-				transaction_hash: None,
-				transaction_index: None,
-				log_index: None,
-				transaction_log_index: None,
-				removed: false,
-			};
-			let mut add: bool = true;
-			match (filter.address.clone(), filter.topics.clone()) {
-				(Some(_), Some(_)) => {
-					if !params.filter_address(&log.address) || !params.filter_topics(&log.topics) {
-						add = false;
-					}
-				}
-				(Some(_), _) => {
-					if !params.filter_address(&log.address) {
-						add = false;
-					}
-				}
-				(_, Some(_)) => {
-					if !params.filter_topics(&log.topics) {
-						add = false;
-					}
-				}
-				_ => {}
-			}
-			if add {
-				log.block_hash = Some(block_hash);
-				log.block_number = Some(block.header.number);
-				log.transaction_hash = Some(transaction_hash);
-				log.transaction_index = Some(U256::from(status.transaction_index));
-				log.log_index = Some(U256::from(block_log_index));
-				log.transaction_log_index = Some(U256::from(transaction_log_index));
-				ret.push(log);
-			}
-			transaction_log_index += 1;
-			block_log_index += 1;
-		}
-	}
+	ret.extend(log_filter::filter_block_logs(
+		&log_filter::matcher(filter),
+		block_hash,
+		block.header.number,
+		&transaction_statuses,
+		|_, _| true,
+	));
 	ret
 }
@@ -0,0 +1,433 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Log filter matching of the RPC filters.
+//!
+//! The filters are matched with [`fc_api::LogFilter`], like in the other users of the log
+//! backends, and the matching logs are converted to RPC logs.
+
+use ethereum_types::{H256, U256};
+// Frontier
+pub use fc_api::{group_indexed_logs, IndexedBlockLogs, LogFilter};
+use fc_rpc_core::types::{Bytes, Filter, FilteredParams, Log, VariadicValue};
+use fp_rpc::TransactionStatus;
+
+/// The matcher of the addresses and topics of an RPC filter.
+pub fn matcher(filter: &Filter) -> LogFilter {
+	// An empty list of addresses is a wildcard, like a missing one.
+	let addresses = match &filter.address {
+		Some(VariadicValue::Single(address)) => vec![*address],
+		Some(VariadicValue::Multiple(addresses)) => addresses.clone(),
+		_ => vec![],
+	};
+	let topics = FilteredParams::new(Some(filter.clone()))
+		.flat_topics
+		.into_iter()
+		.map(|flat| match flat {
+			VariadicValue::Single(topic) => vec![topic],
+			VariadicValue::Multiple(topics) => topics,
+			VariadicValue::Null => vec![],
+		})
+		.collect::<Vec<_>>();
+	LogFilter::new(addresses, topics)
+}
+
+/// Collect the logs of a block that match the filter, in block order.
+///
+/// Only the logs for which `is_candidate(transaction_index, transaction_log_index)` holds are
+/// matched.
+pub fn filter_block_logs(
+	filter: &LogFilter,
+	block_hash: H256,
+	block_number: U256,
+	transaction_statuses: &[TransactionStatus],
+	is_candidate: impl FnMut(u32, u32) -> bool,
+) -> Vec<Log> {
+	filter
+		.block_logs(transaction_statuses, is_candidate)
+		.into_iter()
+		.map(|block_log| Log {
+			address: block_log.log.address,
+			topics: block_log.log.topics.clone(),
+			data: Bytes(block_log.log.data.clone()),
+			block_hash: Some(block_hash),
+			block_number: Some(block_number),
+			transaction_hash: Some(block_log.status.transaction_hash),
+			transaction_index: Some(U256::from(block_log.status.transaction_index)),
+			log_index: Some(U256::from(block_log.log_index)),
+			transaction_log_index: Some(U256::from(block_log.transaction_log_index)),
+			removed: false,
+		})
+		.collect()
+}
+
+/// Collect the logs of the pending block that match the filter, in block order.
+///
+/// The pending block changes with the transaction pool and has no hash, so its logs have none
+/// either.
+pub fn filter_pending_block_logs(
+	filter: &LogFilter,
+	block_number: U256,
+	transaction_statuses: &[TransactionStatus],
+) -> Vec<Log> {
+	let mut logs = filter_block_logs(
+		filter,
+		H256::zero(),
+		block_number,
+		transaction_statuses,
+		|_, _| true,
+	);
+	for log in &mut logs {
+		log.block_hash = None;
+	}
+	logs
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+	use ethereum_types::H160;
+	use scale_codec::Encode;
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+	};
+	use substrate_test_runtime_client::{
+		DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+	};
+	use tempfile::tempdir;
+	// Frontier
+	use fc_api::LogIndexerBackend as _;
+	use fc_rpc_core::types::Topic;
+	use fc_storage::SchemaV3StorageOverride;
+	use fp_storage::{EthereumStorageSchema, PALLET_ETHEREUM_SCHEMA};
+
+	type OpaqueBlock =
+		Block<Header<u64, BlakeTwo256>, substrate_test_runtime_client::runtime::Extrinsic>;
+
+	struct TestBlock {
+		number: u32,
+		substrate_hash: H256,
+		ethereum_hash: H256,
+		statuses: Vec<TransactionStatus>,
+	}
+
+	fn topic(byte: u8) -> H256 {
+		H256::repeat_byte(byte)
+	}
+
+	fn address(byte: u8) -> H160 {
+		H160::repeat_byte(byte)
+	}
+
+	fn status(transaction_index: u32, logs: Vec<(H160, Vec<H256>)>) -> TransactionStatus {
+		TransactionStatus {
+			transaction_hash: H256::from_low_u64_be(transaction_index as u64 + 1),
+			transaction_index,
+			logs: logs
+				.into_iter()
+				.map(|(address, topics)| ethereum::Log {
+					address,
+					topics,
+					data: vec![transaction_index as u8],
+				})
+				.collect(),
+			..Default::default()
+		}
+	}
+
+	fn test_blocks() -> Vec<TestBlock> {
+		let (a, b, c, d) = (topic(0xa), topic(0xb), topic(0xc), topic(0xd));
+		(1..=3u8)
+			.map(|number| TestBlock {
+				number: number as u32,
+				substrate_hash: H256::repeat_byte(0x10 + number),
+				ethereum_hash: H256::repeat_byte(0x20 + number),
+				statuses: vec![
+					status(
+						0,
+						vec![
+							(address(number), vec![]),
+							(address(1), vec![a]),
+							(address(2), vec![a, b, c, d]),
+						],
+					),
+					status(1, vec![]),
+					status(
+						2,
+						vec![
+							(address(2), vec![b, a]),
+							(address(3), vec![a, c]),
+							(address(1), vec![d, c, b, a]),
+							(address(number), vec![c, b]),
+						],
+					),
+				],
+			})
+			.collect()
+	}
+
+	fn filter(address: Option<VariadicValue<H160>>, topics: Option<Topic>) -> Filter {
+		Filter {
+			from_block: None,
+			to_block: None,
+			block_hash: None,
+			address,
+			topics,
+		}
+	}
+
+	fn topics(positions: Vec<Option<Vec<H256>>>) -> Option<Topic> {
+		Some(VariadicValue::Multiple(
+			positions
+				.into_iter()
+				.map(|position| {
+					position.map(|options| {
+						VariadicValue::Multiple(options.into_iter().map(Some).collect())
+					})
+				})
+				.collect(),
+		))
+	}
+
+	async fn sql_backend(
+		blocks: &[TestBlock],
+	) -> (fc_db::sql::Backend<OpaqueBlock>, tempfile::TempDir) {
+		let tmp = tempdir().expect("create a temporary directory");
+		let builder = TestClientBuilder::new().add_extra_storage(
+			PALLET_ETHEREUM_SCHEMA.to_vec(),
+			Encode::encode(&EthereumStorageSchema::V3),
+		);
+		let (client, _) = builder
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+				None,
+			);
+		let storage_override = Arc::new(SchemaV3StorageOverride::new(Arc::new(client)));
+		let backend = fc_db::sql::Backend::new(
+			fc_db::sql::BackendConfig::Sqlite(fc_db::sql::SqliteBackendConfig {
+				path: Path::new("sqlite:///")
+					.join(tmp.path())
+					.join("test.db3")
+					.to_str()
+					.unwrap(),
+				create_if_missing: true,
+				cache_size: 20480,
+				thread_count: 4,
+			}),
+			1,
+			None,
+			storage_override,
+		)
+		.await
+		.expect("indexer pool to be created");
+
+		// Index the same data the KV backend reads, like the SQL indexer does.
+		for block in blocks {
+			sqlx::query(
+				"INSERT INTO blocks(
+					block_number,
+					ethereum_block_hash,
+					substrate_block_hash,
+					ethereum_storage_schema,
+					is_canon
+				) VALUES (?, ?, ?, ?, 1)",
+			)
+			.bind(block.number as i32)
+			.bind(block.ethereum_hash.as_bytes().to_owned())
+			.bind(block.substrate_hash.as_bytes().to_owned())
+			.bind(EthereumStorageSchema::V3.encode())
+			.execute(backend.pool())
+			.await
+			.expect("insert should succeed");
+			for status in &block.statuses {
+				for (log_index, log) in status.logs.iter().enumerate() {
+					let topic = |i: usize| log.topics.get(i).map(|t| t.as_bytes().to_owned());
+					sqlx::query(
+						"INSERT INTO logs(
+							address,
+							topic_1,
+							topic_2,
+							topic_3,
+							topic_4,
+							log_index,
+							transaction_index,
+							substrate_block_hash
+						) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+					)
+					.bind(log.address.as_bytes().to_owned())
+					.bind(topic(0))
+					.bind(topic(1))
+					.bind(topic(2))
+					.bind(topic(3))
+					.bind(log_index as i32)
+					.bind(status.transaction_index as i32)
+					.bind(block.substrate_hash.as_bytes().to_owned())
+					.execute(backend.pool())
+					.await
+					.expect("insert should succeed");
+				}
+			}
+		}
+
+		(backend, tmp)
+	}
+
+	fn kv_logs(blocks: &[TestBlock], filter: &Filter) -> Vec<Log> {
+		let matcher = matcher(filter);
+		blocks
+			.iter()
+			.flat_map(|block| {
+				filter_block_logs(
+					&matcher,
+					block.ethereum_hash,
+					U256::from(block.number),
+					&block.statuses,
+					|_, _| true,
+				)
+			})
+			.collect()
+	}
+
+	async fn sql_logs(
+		backend: &fc_db::sql::Backend<OpaqueBlock>,
+		blocks: &[TestBlock],
+		filter: &Filter,
+	) -> Vec<Log> {
+		let statuses = blocks
+			.iter()
+			.map(|block| (block.substrate_hash, &block.statuses))
+			.collect::<BTreeMap<_, _>>();
+		let matcher = matcher(filter);
+		let (addresses, topics) = matcher.indexer_input();
+		let candidates = backend
+			.filter_logs(1, 3, addresses, topics)
+			.await
+			.expect("filter must succeed");
+		group_indexed_logs(&candidates)
+			.into_iter()
+			.flat_map(|block| {
+				filter_block_logs(
+					&matcher,
+					block.ethereum_block_hash,
+					U256::from(block.block_number),
+					statuses[&block.substrate_block_hash],
+					|transaction_index, transaction_log_index| {
+						block
+							.candidates
+							.contains(&(transaction_index, transaction_log_index))
+					},
+				)
+			})
+			.collect()
+	}
+
+	#[tokio::test]
+	async fn kv_and_sql_backends_return_identical_logs() {
+		let (a, b, c, d) = (topic(0xa), topic(0xb), topic(0xc), topic(0xd));
+		let blocks = test_blocks();
+		let (backend, _tmp) = sql_backend(&blocks).await;
+
+		let filters = vec![
+			filter(None, None),
+			filter(Some(VariadicValue::Single(address(1))), None),
+			filter(
+				Some(VariadicValue::Multiple(vec![address(2), address(3)])),
+				None,
+			),
+			// An empty address list matches any address.
+			filter(Some(VariadicValue::Multiple(vec![])), None),
+			filter(None, topics(vec![])),
+			filter(None, topics(vec![Some(vec![a])])),
+			filter(None, topics(vec![None, Some(vec![c])])),
+			filter(None, topics(vec![Some(vec![a, b])])),
+			filter(None, topics(vec![Some(vec![a, d]), None, Some(vec![c, b])])),
+			// The combinations with a wildcard in second position leave it unconstrained.
+			filter(None, topics(vec![Some(vec![a, b]), Some(vec![a]), None])),
+			filter(None, topics(vec![Some(vec![a]), None, None, Some(vec![d])])),
+			// A trailing wildcard does not require the log to have more topics.
+			filter(None, topics(vec![Some(vec![c]), None])),
+			// No log has five topics.
+			filter(None, topics(vec![None, None, None, None, Some(vec![a])])),
+			filter(
+				Some(VariadicValue::Multiple(vec![address(1), address(2)])),
+				topics(vec![Some(vec![a, d])]),
+			),
+		];
+
+		for filter in filters {
+			let kv_logs = kv_logs(&blocks, &filter);
+			let sql_logs = sql_logs(&backend, &blocks, &filter).await;
+			assert_eq!(kv_logs, sql_logs, "backends differ for {:?}", filter);
+		}
+	}
+
+	#[test]
+	fn combinations_with_wildcards_match_any_topic() {
+		let (a, b, c) = (topic(0xa), topic(0xb), topic(0xc));
+		// `[[A, B], [A], null]` flattens to `[A, A]` and `[B, A]`.
+		let matcher = matcher(&filter(
+			None,
+			Some(VariadicValue::Multiple(vec![
+				Some(VariadicValue::Multiple(vec![Some(a), Some(b)])),
+				Some(VariadicValue::Single(Some(a))),
+				None,
+			])),
+		));
+
+		assert!(matcher.matches(&address(1), &[a, a]));
+		assert!(matcher.matches(&address(1), &[b, a, c]));
+		assert!(!matcher.matches(&address(1), &[a]));
+		assert!(!matcher.matches(&address(1), &[c, a]));
+		assert_eq!(
+			matcher.indexer_input(),
+			(
+				vec![],
+				vec![vec![Some(a)], vec![Some(b)], vec![None, Some(a)]]
+			)
+		);
+	}
+
+	#[test]
+	fn pending_logs_have_no_block_hash() {
+		let a = topic(0xa);
+		let statuses = vec![
+			status(0, vec![(address(1), vec![a]), (address(2), vec![a])]),
+			status(1, vec![(address(1), vec![])]),
+		];
+
+		let logs = filter_pending_block_logs(
+			&matcher(&filter(Some(VariadicValue::Single(address(1))), None)),
+			U256::from(7),
+			&statuses,
+		);
+
+		assert_eq!(logs.len(), 2);
+		for log in &logs {
+			assert_eq!(log.address, address(1));
+			assert_eq!(log.block_hash, None);
+			assert_eq!(log.block_number, Some(U256::from(7)));
+			assert!(!log.removed);
+		}
+		// The logs are indexed within the pending block.
+		assert_eq!(logs[0].log_index, Some(U256::zero()));
+		assert_eq!(logs[1].log_index, Some(U256::from(2)));
+		assert_eq!(logs[1].transaction_hash, Some(statuses[1].transaction_hash));
+	}
+}
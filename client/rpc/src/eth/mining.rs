@@ -30,7 +30,8 @@ where
 	B: BlockT,
 {
 	pub fn is_mining(&self) -> RpcResult<bool> {
-		Ok(self.is_authority)
+		// Web3 clients take a mining node as one to submit work to.
+		Ok(false)
 	}
 
 	pub fn hashrate(&self) -> RpcResult<U256> {
@@ -22,6 +22,7 @@ mod execute;
 mod fee;
 mod filter;
 pub mod format;
+mod log_filter;
 mod mining;
 pub mod pending;
 mod state;
@@ -52,8 +53,8 @@ use fp_rpc::{
 };
 
 use crate::{
-	cache::EthBlockDataCacheTask, frontier_backend_client, internal_err, public_key,
-	signer::EthSigner,
+	cache::EthBlockDataCacheTask, execution_pool::ExecutionPool, frontier_backend_client,
+	internal_err, public_key, runtime_api::EthereumRuntimeApiVersions, signer::EthSigner,
 };
 
 pub use self::{execute::EstimateGasAdapter, filter::EthFilter};
@@ -69,6 +70,9 @@ impl<B: BlockT, C> EthConfig<B, C> for () {
 	type RuntimeStorageOverride = ();
 }
 
+/// Default maximum number of transactions served by one `eth_getBlockTransactions` request.
+pub const DEFAULT_MAX_BLOCK_TRANSACTIONS_PAGE: usize = 1_000;
+
 /// Eth API implementation.
 pub struct Eth<B: BlockT, C, P, CT, BE, CIDP, EC> {
 	pool: Arc<P>,
@@ -76,7 +80,6 @@ pub struct Eth<B: BlockT, C, P, CT, BE, CIDP, EC> {
 	client: Arc<C>,
 	convert_transaction: Option<CT>,
 	sync: Arc<SyncingService<B>>,
-	is_authority: bool,
 	signers: Vec<Box<dyn EthSigner>>,
 	storage_override: Arc<dyn StorageOverride<B>>,
 	backend: Arc<dyn fc_api::Backend<B>>,
@@ -90,6 +93,22 @@ pub struct Eth<B: BlockT, C, P, CT, BE, CIDP, EC> {
 	/// Something that can create the inherent data providers for pending state.
 	pending_create_inherent_data_providers: CIDP,
 	pending_consensus_data_provider: Option<Box<dyn pending::ConsensusDataProvider<B>>>,
+	/// Maximum size of the JSON encoding of the full blocks, if limited.
+	max_response_size: Option<usize>,
+	/// Whether a zero chain id is a configured one, rather than a missing configuration.
+	allow_zero_chain_id: bool,
+	/// Whether `eth_estimateGas` caps the gas of a request with a fee by the balance of the
+	/// sender.
+	estimate_balance_check: bool,
+	/// Runs the EVM executions of eth_call and eth_estimateGas.
+	execution_pool: ExecutionPool,
+	/// JSON format of the served receipts.
+	receipt_format: ReceiptFormat,
+	/// Maximum number of transactions served by one `eth_getBlockTransactions` request.
+	max_block_transactions_page: usize,
+	/// Whether `eth_sign` and `eth_signTransaction` are served, for development nodes.
+	dev_signing: bool,
+	api_versions: EthereumRuntimeApiVersions<B>,
 	_marker: PhantomData<(BE, EC)>,
 }
 
@@ -101,6 +120,8 @@ where
 	C: HeaderBackend<B> + StorageProvider<B, BE> + 'static,
 	BE: Backend<B> + 'static,
 {
+	/// `_is_authority` is unused and only kept for compatibility: `eth_mining` is false even on
+	/// authorities, since they don't mine work submitted by clients.
 	pub fn new(
 		client: Arc<C>,
 		pool: Arc<P>,
@@ -110,7 +131,7 @@ where
 		signers: Vec<Box<dyn EthSigner>>,
 		storage_override: Arc<dyn StorageOverride<B>>,
 		backend: Arc<dyn fc_api::Backend<B>>,
-		is_authority: bool,
+		_is_authority: bool,
 		block_data_cache: Arc<EthBlockDataCacheTask<B>>,
 		fee_history_cache: FeeHistoryCache,
 		fee_history_cache_limit: FeeHistoryCacheLimit,
@@ -125,7 +146,6 @@ where
 			graph,
 			convert_transaction,
 			sync,
-			is_authority,
 			signers,
 			storage_override,
 			backend,
@@ -136,10 +156,68 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			max_response_size: None,
+			allow_zero_chain_id: false,
+			estimate_balance_check: false,
+			execution_pool: ExecutionPool::default(),
+			receipt_format: ReceiptFormat::default(),
+			max_block_transactions_page: DEFAULT_MAX_BLOCK_TRANSACTIONS_PAGE,
+			dev_signing: false,
+			api_versions: EthereumRuntimeApiVersions::default(),
 			_marker: PhantomData,
 		}
 	}
 
+	/// Fails the requests of full blocks and pages of block transactions whose JSON encoding is
+	/// larger than `max_response_size` bytes, with an error asking to narrow the query.
+	pub fn with_max_response_size(mut self, max_response_size: usize) -> Self {
+		self.max_response_size = Some(max_response_size);
+		self
+	}
+
+	/// Runs the EVM executions of eth_call and eth_estimateGas in the given pool, rather than in
+	/// one with as many slots as there are CPUs.
+	pub fn with_execution_pool(mut self, execution_pool: ExecutionPool) -> Self {
+		self.execution_pool = execution_pool;
+		self
+	}
+
+	/// Serves a zero chain id, for the chains which actually use it. By default a zero chain id
+	/// is treated as missing and `eth_chainId` fails, since transactions signed for it would be
+	/// replayable.
+	pub fn allow_zero_chain_id(mut self) -> Self {
+		self.allow_zero_chain_id = true;
+		self
+	}
+
+	/// Caps the gas of the `eth_estimateGas` requests with a non-zero fee by what the sender
+	/// can pay for, failing for senders who can't pay for any gas. By default the balance of
+	/// the sender isn't checked when estimating, so users can estimate without funds.
+	pub fn with_estimate_balance_check(mut self, estimate_balance_check: bool) -> Self {
+		self.estimate_balance_check = estimate_balance_check;
+		self
+	}
+
+	/// Serves the receipts in the given format, rather than in [`ReceiptFormat::Frontier`].
+	pub fn with_receipt_format(mut self, receipt_format: ReceiptFormat) -> Self {
+		self.receipt_format = receipt_format;
+		self
+	}
+
+	/// Fails the `eth_getBlockTransactions` requests for more than `max_block_transactions_page`
+	/// transactions, rather than [`DEFAULT_MAX_BLOCK_TRANSACTIONS_PAGE`].
+	pub fn with_max_block_transactions_page(mut self, max_block_transactions_page: usize) -> Self {
+		self.max_block_transactions_page = max_block_transactions_page;
+		self
+	}
+
+	/// Serves `eth_sign` and `eth_signTransaction` with the signers, for development nodes. By
+	/// default both fail, since they hand out signatures of the node accounts to any caller.
+	pub fn with_dev_signing(mut self, dev_signing: bool) -> Self {
+		self.dev_signing = dev_signing;
+		self
+	}
+
 	pub async fn block_info_by_number(
 		&self,
 		number_or_hash: BlockNumberOrHash,
@@ -155,10 +233,14 @@ where
 			None => return Ok(BlockInfo::default()),
 		};
 
-		let substrate_hash = self
+		let substrate_hash = match self
 			.client
-			.expect_block_hash_from_id(&id)
-			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?;
+			.block_hash_from_id(&id)
+			.map_err(|_| internal_err(format!("Expect block number from id: {}", id)))?
+		{
+			Some(hash) => hash,
+			None => return Ok(BlockInfo::default()),
+		};
 
 		self.block_info_by_substrate_hash(substrate_hash).await
 	}
@@ -190,7 +272,6 @@ where
 			self.client.as_ref(),
 			self.backend.as_ref(),
 			ethereum_tx_hash,
-			true,
 		)
 		.await
 		.map_err(|err| internal_err(format!("{:?}", err)))?
@@ -228,11 +309,6 @@ where
 			.await;
 		let receipts = self.storage_override.current_receipts(substrate_hash);
 		let is_eip1559 = self.storage_override.is_eip1559(substrate_hash);
-		let base_fee = self
-			.client
-			.runtime_api()
-			.gas_price(substrate_hash)
-			.unwrap_or_default();
 
 		Ok(BlockInfo::new(
 			block,
@@ -240,9 +316,38 @@ where
 			statuses,
 			substrate_hash,
 			is_eip1559,
-			base_fee,
 		))
 	}
+
+	/// The base fee the transactions of a block were charged with, which is the one set by
+	/// its parent block.
+	pub async fn included_base_fee(
+		&self,
+		block: &EthereumBlock,
+		substrate_hash: B::Hash,
+	) -> RpcResult<U256> {
+		let parent_eth_hash = block.header.parent_hash;
+		let base_fee_block_substrate_hash = if parent_eth_hash.is_zero() {
+			substrate_hash
+		} else {
+			frontier_backend_client::load_hash::<B, C>(
+				self.client.as_ref(),
+				self.backend.as_ref(),
+				parent_eth_hash,
+			)
+			.await
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or(internal_err(
+				"Failed to retrieve substrate parent block hash",
+			))?
+		};
+
+		Ok(self
+			.client
+			.runtime_api()
+			.gas_price(base_fee_block_substrate_hash)
+			.unwrap_or_default())
+	}
 }
 
 impl<B, C, P, CT, BE, CIDP, EC> Eth<B, C, P, CT, BE, CIDP, EC>
@@ -257,7 +362,6 @@ where
 			graph,
 			convert_transaction,
 			sync,
-			is_authority,
 			signers,
 			storage_override,
 			backend,
@@ -268,6 +372,14 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			max_response_size,
+			allow_zero_chain_id,
+			estimate_balance_check,
+			execution_pool,
+			receipt_format,
+			max_block_transactions_page,
+			dev_signing,
+			api_versions,
 			_marker: _,
 		} = self;
 
@@ -277,7 +389,6 @@ where
 			graph,
 			convert_transaction,
 			sync,
-			is_authority,
 			signers,
 			storage_override,
 			backend,
@@ -288,6 +399,14 @@ where
 			forced_parent_hashes,
 			pending_create_inherent_data_providers,
 			pending_consensus_data_provider,
+			max_response_size,
+			allow_zero_chain_id,
+			estimate_balance_check,
+			execution_pool,
+			receipt_format,
+			max_block_transactions_page,
+			dev_signing,
+			api_versions,
 			_marker: PhantomData,
 		}
 	}
@@ -339,7 +458,11 @@ where
 	// ########################################################################
 
 	async fn block_by_hash(&self, hash: H256, full: bool) -> RpcResult<Option<RichBlock>> {
-		self.block_by_hash(hash, full).await
+		let block = self.block_by_hash(hash, full).await?;
+		if full {
+			crate::ensure_response_size(&block, self.max_response_size)?;
+		}
+		Ok(block)
 	}
 
 	async fn block_by_number(
@@ -347,7 +470,11 @@ where
 		number_or_hash: BlockNumberOrHash,
 		full: bool,
 	) -> RpcResult<Option<RichBlock>> {
-		self.block_by_number(number_or_hash, full).await
+		let block = self.block_by_number(number_or_hash, full).await?;
+		if full {
+			crate::ensure_response_size(&block, self.max_response_size)?;
+		}
+		Ok(block)
 	}
 
 	async fn block_transaction_count_by_hash(&self, hash: H256) -> RpcResult<Option<U256>> {
@@ -361,11 +488,30 @@ where
 		self.block_transaction_count_by_number(number_or_hash).await
 	}
 
+	async fn block_transactions(
+		&self,
+		number_or_hash: BlockNumberOrHash,
+		offset: Index,
+		count: Index,
+	) -> RpcResult<Option<Vec<Transaction>>> {
+		let transactions = self
+			.block_transactions(number_or_hash, offset, count)
+			.await?;
+		crate::ensure_response_size(&transactions, self.max_response_size)?;
+		Ok(transactions)
+	}
+
 	async fn block_transaction_receipts(
 		&self,
 		number_or_hash: BlockNumberOrHash,
-	) -> RpcResult<Option<Vec<Receipt>>> {
-		self.block_transaction_receipts(number_or_hash).await
+	) -> RpcResult<Option<Vec<FormattedReceipt>>> {
+		let receipts = self.block_transaction_receipts(number_or_hash).await?;
+		Ok(receipts.map(|receipts| {
+			receipts
+				.into_iter()
+				.map(|receipt| self.receipt_format.format(receipt))
+				.collect()
+		}))
 	}
 
 	fn block_uncles_count_by_hash(&self, hash: H256) -> RpcResult<U256> {
@@ -417,9 +563,14 @@ where
 			.await
 	}
 
-	async fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<Receipt>> {
+	async fn transaction_receipt(&self, hash: H256) -> RpcResult<Option<FormattedReceipt>> {
 		let (block_info, index) = self.block_info_by_eth_transaction_hash(hash).await?;
-		self.transaction_receipt(&block_info, hash, index).await
+		let receipt = self.transaction_receipt(&block_info, hash, index).await?;
+		Ok(receipt.map(|receipt| self.receipt_format.format(receipt)))
+	}
+
+	async fn transaction_effective_gas_price(&self, hash: H256) -> RpcResult<Option<U256>> {
+		self.transaction_effective_gas_price(hash).await
 	}
 
 	// ########################################################################
@@ -473,7 +624,9 @@ where
 		number_or_hash: Option<BlockNumberOrHash>,
 		state_overrides: Option<BTreeMap<H160, CallStateOverride>>,
 	) -> RpcResult<Bytes> {
-		self.call(request, number_or_hash, state_overrides).await
+		self.execution_pool
+			.run(self.call(request, number_or_hash, state_overrides))
+			.await
 	}
 
 	async fn estimate_gas(
@@ -481,7 +634,9 @@ where
 		request: TransactionRequest,
 		number_or_hash: Option<BlockNumberOrHash>,
 	) -> RpcResult<U256> {
-		self.estimate_gas(request, number_or_hash).await
+		self.execution_pool
+			.run(self.estimate_gas(request, number_or_hash))
+			.await
 	}
 
 	// ########################################################################
@@ -541,6 +696,14 @@ where
 	async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
 		self.send_raw_transaction(bytes).await
 	}
+
+	fn sign(&self, address: H160, data: Bytes) -> RpcResult<Bytes> {
+		self.sign(address, data)
+	}
+
+	async fn sign_transaction(&self, request: TransactionRequest) -> RpcResult<Bytes> {
+		self.sign_transaction(request).await
+	}
 }
 
 fn rich_block_build(
@@ -586,21 +749,13 @@ fn rich_block_build(
 			uncles: vec![],
 			transactions: {
 				if full_transactions {
-					BlockTransactions::Full(
-						block
-							.transactions
-							.iter()
-							.enumerate()
-							.map(|(index, transaction)| {
-								transaction_build(
-									transaction,
-									Some(&block),
-									statuses[index].as_ref(),
-									base_fee,
-								)
-							})
-							.collect(),
-					)
+					BlockTransactions::Full(block_transactions_build(
+						&block,
+						&statuses,
+						base_fee,
+						0,
+						block.transactions.len(),
+					))
 				} else {
 					BlockTransactions::Hashes(
 						block
@@ -618,6 +773,56 @@ fn rich_block_build(
 	}
 }
 
+/// Reject pages of more than `max_count` transactions with an invalid params error, before the
+/// block is loaded.
+fn check_block_transactions_page(count: usize, max_count: usize) -> RpcResult<usize> {
+	if count > max_count {
+		return Err(crate::err(
+			jsonrpsee::types::error::INVALID_PARAMS_CODE,
+			format!("count is too high (can be up to {max_count} transactions per page)"),
+			None,
+		));
+	}
+	Ok(count)
+}
+
+/// Reject the signing requests with a method not found error, unless dev signing is enabled.
+fn check_dev_signing(dev_signing: bool, method: &str) -> RpcResult<()> {
+	if !dev_signing {
+		return Err(crate::err(
+			jsonrpsee::types::error::METHOD_NOT_FOUND_CODE,
+			format!("{method} is only available on development nodes"),
+			None,
+		));
+	}
+	Ok(())
+}
+
+/// Builds the transactions of a block from index `offset`, at most `count` of them.
+fn block_transactions_build(
+	block: &EthereumBlock,
+	statuses: &[Option<TransactionStatus>],
+	base_fee: Option<U256>,
+	offset: usize,
+	count: usize,
+) -> Vec<Transaction> {
+	block
+		.transactions
+		.iter()
+		.enumerate()
+		.skip(offset)
+		.take(count)
+		.map(|(index, transaction)| {
+			transaction_build(
+				transaction,
+				Some(block),
+				statuses.get(index).and_then(Option::as_ref),
+				base_fee,
+			)
+		})
+		.collect()
+}
+
 fn transaction_build(
 	ethereum_transaction: &EthereumTransaction,
 	block: Option<&EthereumBlock>,
@@ -640,21 +845,13 @@ fn transaction_build(
 
 	let mut transaction: Transaction = Transaction::build_from(from, ethereum_transaction);
 
-	if let EthereumTransaction::EIP1559(_) = ethereum_transaction {
-		if block.is_none() && status.is_none() {
-			// If transaction is not mined yet, gas price is considered just max fee per gas.
-		} else {
-			let base_fee = base_fee.unwrap_or_default();
-			let max_priority_fee_per_gas = transaction.max_priority_fee_per_gas.unwrap_or_default();
-			let max_fee_per_gas = transaction.max_fee_per_gas.unwrap_or_default();
-			// If transaction is already mined, gas price is the effective gas price.
-			transaction.gas_price = Some(
-				base_fee
-					.checked_add(max_priority_fee_per_gas)
-					.unwrap_or_else(U256::max_value)
-					.min(max_fee_per_gas),
-			);
-		}
+	// If transaction is not mined yet, gas price is considered just max fee per gas.
+	if block.is_some() || status.is_some() {
+		let effective_gas_price =
+			effective_gas_price(ethereum_transaction, base_fee.unwrap_or_default());
+		// If transaction is already mined, gas price is the effective gas price.
+		transaction.gas_price = Some(effective_gas_price);
+		transaction.effective_gas_price = Some(effective_gas_price);
 	}
 
 	// Block hash.
@@ -673,6 +870,23 @@ fn transaction_build(
 	transaction
 }
 
+/// The gas price paid by a transaction included in a block with the given base fee.
+///
+/// It is the gas price of legacy and EIP-2930 transactions, and
+/// `min(max_fee_per_gas, base_fee + max_priority_fee_per_gas)` for the EIP-1559 style ones.
+pub(crate) fn effective_gas_price(transaction: &EthereumTransaction, base_fee: U256) -> U256 {
+	let (max_priority_fee_per_gas, max_fee_per_gas) = match transaction {
+		EthereumTransaction::Legacy(t) => return t.gas_price,
+		EthereumTransaction::EIP2930(t) => return t.gas_price,
+		EthereumTransaction::EIP1559(t) => (t.max_priority_fee_per_gas, t.max_fee_per_gas),
+		EthereumTransaction::EIP7702(t) => (t.max_priority_fee_per_gas, t.max_fee_per_gas),
+	};
+	base_fee
+		.checked_add(max_priority_fee_per_gas)
+		.unwrap_or_else(U256::max_value)
+		.min(max_fee_per_gas)
+}
+
 /// The most commonly used block information in the rpc interfaces.
 #[derive(Clone, Default)]
 pub struct BlockInfo<H> {
@@ -681,7 +895,6 @@ pub struct BlockInfo<H> {
 	statuses: Option<Vec<TransactionStatus>>,
 	substrate_hash: H,
 	is_eip1559: bool,
-	base_fee: U256,
 }
 
 impl<H> BlockInfo<H> {
@@ -691,7 +904,6 @@ impl<H> BlockInfo<H> {
 		statuses: Option<Vec<TransactionStatus>>,
 		substrate_hash: H,
 		is_eip1559: bool,
-		base_fee: U256,
 	) -> Self {
 		Self {
 			block,
@@ -699,7 +911,251 @@ impl<H> BlockInfo<H> {
 			statuses,
 			substrate_hash,
 			is_eip1559,
-			base_fee,
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use ethereum::{
+		eip2930::TransactionSignature as EIP2930TransactionSignature,
+		legacy::TransactionSignature as LegacyTransactionSignature, TransactionAction,
+	};
+
+	use super::*;
+
+	fn eip2930_signature() -> EIP2930TransactionSignature {
+		EIP2930TransactionSignature::new(false, H256::repeat_byte(1), H256::repeat_byte(1))
+			.expect("the signature is well-formed")
+	}
+
+	fn legacy(gas_price: u64) -> EthereumTransaction {
+		EthereumTransaction::Legacy(ethereum::LegacyTransaction {
+			nonce: U256::zero(),
+			gas_price: gas_price.into(),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0xde)),
+			value: U256::zero(),
+			input: vec![],
+			signature: LegacyTransactionSignature::new(
+				27,
+				H256::repeat_byte(1),
+				H256::repeat_byte(1),
+			)
+			.expect("the signature is well-formed"),
+		})
+	}
+
+	fn eip2930(gas_price: u64) -> EthereumTransaction {
+		EthereumTransaction::EIP2930(ethereum::EIP2930Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			gas_price: gas_price.into(),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0xde)),
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			signature: eip2930_signature(),
+		})
+	}
+
+	fn eip1559(max_priority_fee_per_gas: U256, max_fee_per_gas: U256) -> EthereumTransaction {
+		EthereumTransaction::EIP1559(ethereum::EIP1559Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			max_priority_fee_per_gas,
+			max_fee_per_gas,
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0xde)),
+			value: U256::zero(),
+			input: vec![],
+			access_list: vec![],
+			signature: eip2930_signature(),
+		})
+	}
+
+	fn eip7702(max_priority_fee_per_gas: U256, max_fee_per_gas: U256) -> EthereumTransaction {
+		EthereumTransaction::EIP7702(ethereum::EIP7702Transaction {
+			chain_id: 42,
+			nonce: U256::zero(),
+			max_priority_fee_per_gas,
+			max_fee_per_gas,
+			gas_limit: U256::from(21_000),
+			destination: TransactionAction::Call(H160::repeat_byte(0xde)),
+			value: U256::zero(),
+			data: vec![],
+			access_list: vec![],
+			authorization_list: vec![],
+			signature: eip2930_signature(),
+		})
+	}
+
+	#[test]
+	fn effective_gas_price_is_the_gas_price_of_legacy_transactions() {
+		let base_fee = U256::from(1_000);
+		assert_eq!(effective_gas_price(&legacy(3_000), base_fee), 3_000.into());
+		assert_eq!(effective_gas_price(&eip2930(3_000), base_fee), 3_000.into());
+		// The base fee doesn't matter.
+		assert_eq!(
+			effective_gas_price(&legacy(3_000), U256::max_value()),
+			3_000.into()
+		);
+	}
+
+	#[test]
+	fn effective_gas_price_of_eip1559_style_transactions() {
+		let base_fee = U256::from(1_000);
+		for build in [eip1559, eip7702] {
+			// The full priority fee fits under the max fee.
+			let transaction = build(100.into(), 2_000.into());
+			assert_eq!(effective_gas_price(&transaction, base_fee), 1_100.into());
+
+			// The priority fee is capped by the max fee.
+			let transaction = build(1_500.into(), 2_000.into());
+			assert_eq!(effective_gas_price(&transaction, base_fee), 2_000.into());
+
+			// Overflows saturate to the max fee.
+			let transaction = build(U256::max_value(), 2_000.into());
+			assert_eq!(effective_gas_price(&transaction, base_fee), 2_000.into());
+		}
+	}
+
+	#[test]
+	fn mined_transactions_report_their_effective_gas_price() {
+		let transaction = eip1559(100.into(), 2_000.into());
+
+		let pending = transaction_build(&transaction, None, None, Some(1_000.into()));
+		assert_eq!(pending.gas_price, Some(2_000.into()));
+		assert_eq!(pending.effective_gas_price, None);
+
+		let status = TransactionStatus::default();
+		let mined = transaction_build(&transaction, None, Some(&status), Some(1_000.into()));
+		assert_eq!(mined.gas_price, Some(1_100.into()));
+		assert_eq!(mined.effective_gas_price, Some(1_100.into()));
+
+		let mined = transaction_build(&legacy(3_000), None, Some(&status), Some(1_000.into()));
+		assert_eq!(mined.gas_price, Some(3_000.into()));
+		assert_eq!(mined.effective_gas_price, Some(3_000.into()));
+	}
+
+	#[test]
+	fn legacy_transactions_report_their_sender() {
+		use fc_rpc_core::types::TransactionMessage;
+
+		use crate::{EthDevSigner, EthSigner};
+
+		let signer = EthDevSigner::new();
+		let sender = signer.accounts()[0];
+		let message = TransactionMessage::Legacy(ethereum::LegacyTransactionMessage {
+			nonce: U256::zero(),
+			gas_price: U256::from(1_000),
+			gas_limit: U256::from(21_000),
+			action: TransactionAction::Call(H160::repeat_byte(0xde)),
+			value: U256::zero(),
+			input: vec![],
+			chain_id: Some(42),
+		});
+		let transaction = signer.sign(message, &sender).expect("the dev signer signs");
+
+		// Pending transactions have no status, the sender is recovered from the signature.
+		let pending = transaction_build(&transaction, None, None, None);
+		assert_eq!(pending.from, sender);
+
+		// Mined transactions report the sender stored with their status.
+		let status = TransactionStatus {
+			from: sender,
+			..Default::default()
+		};
+		let mined = transaction_build(&legacy(1_000), None, Some(&status), None);
+		assert_eq!(mined.from, sender);
+	}
+
+	#[test]
+	fn block_transaction_pages_concatenate_to_the_full_block() {
+		let transactions: Vec<_> = (1..=5).map(|gas_price| legacy(gas_price * 1_000)).collect();
+		let partial_header = ethereum::PartialHeader {
+			parent_hash: H256::default(),
+			beneficiary: H160::default(),
+			state_root: H256::default(),
+			receipts_root: H256::default(),
+			logs_bloom: ethereum_types::Bloom::default(),
+			difficulty: U256::zero(),
+			number: U256::one(),
+			gas_limit: U256::zero(),
+			gas_used: U256::zero(),
+			timestamp: 0u64,
+			extra_data: Vec::new(),
+			mix_hash: H256::default(),
+			nonce: H64::default(),
+		};
+		let block = EthereumBlock::new(partial_header, transactions, vec![]);
+		let statuses: Vec<_> = block
+			.transactions
+			.iter()
+			.enumerate()
+			.map(|(index, transaction)| {
+				Some(TransactionStatus {
+					transaction_hash: transaction.hash(),
+					transaction_index: index as u32,
+					..Default::default()
+				})
+			})
+			.collect();
+		let base_fee = Some(U256::from(1_000));
+
+		let full =
+			match rich_block_build(block.clone(), statuses.clone(), None, true, base_fee, false)
+				.inner
+				.transactions
+			{
+				BlockTransactions::Full(transactions) => transactions,
+				_ => unreachable!("full transactions were requested"),
+			};
+
+		let mut paged = Vec::new();
+		for offset in (0..block.transactions.len()).step_by(2) {
+			let page = block_transactions_build(&block, &statuses, base_fee, offset, 2);
+			assert!(page.len() <= 2);
+			paged.extend(page);
+		}
+		assert_eq!(paged, full);
+		assert_eq!(
+			paged
+				.iter()
+				.map(|transaction| transaction.transaction_index)
+				.collect::<Vec<_>>(),
+			(0..5u64)
+				.map(|index| Some(U256::from(index)))
+				.collect::<Vec<_>>()
+		);
+
+		// Pages past the end are empty.
+		assert!(block_transactions_build(&block, &statuses, base_fee, 5, 2).is_empty());
+	}
+
+	#[test]
+	fn block_transactions_page_is_capped() {
+		assert_eq!(check_block_transactions_page(0, 2).unwrap(), 0);
+		assert_eq!(check_block_transactions_page(2, 2).unwrap(), 2);
+
+		let err = check_block_transactions_page(3, 2).unwrap_err();
+		assert_eq!(err.code(), jsonrpsee::types::error::INVALID_PARAMS_CODE);
+		assert_eq!(
+			err.message(),
+			"count is too high (can be up to 2 transactions per page)"
+		);
+	}
+
+	#[test]
+	fn signing_is_dev_only() {
+		assert!(check_dev_signing(true, "eth_sign").is_ok());
+
+		let err = check_dev_signing(false, "eth_sign").unwrap_err();
+		assert_eq!(err.code(), jsonrpsee::types::error::METHOD_NOT_FOUND_CODE);
+		assert_eq!(
+			err.message(),
+			"eth_sign is only available on development nodes"
+		);
+	}
+}
@@ -16,6 +16,8 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use ethereum::BlockV3 as EthereumBlock;
+use jsonrpsee::core::RpcResult;
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
@@ -29,8 +31,8 @@ use sp_runtime::{
 	TransactionOutcome,
 };
 
-use crate::eth::Eth;
-use fp_rpc::EthereumRuntimeRPCApi;
+use crate::{eth::Eth, internal_err};
+use fp_rpc::{EthereumRuntimeRPCApi, TransactionStatus};
 
 const LOG_TARGET: &str = "eth-pending";
 
@@ -141,6 +143,46 @@ where
 	}
 }
 
+/// The extrinsics of the pending block: the ready transactions of the pool, then the future
+/// ones.
+pub(crate) fn pending_extrinsics<B, P>(graph: &P) -> Vec<<B as BlockT>::Extrinsic>
+where
+	B: BlockT,
+	P: TransactionPool<Block = B, Hash = B::Hash>,
+{
+	graph
+		.ready()
+		.map(|in_pool_tx| in_pool_tx.data().as_ref().clone())
+		.chain(
+			graph
+				.futures()
+				.iter()
+				.map(|in_pool_tx| in_pool_tx.data().as_ref().clone()),
+		)
+		.collect()
+}
+
+/// Builds the pending Ethereum block and its transaction statuses from the transactions of the
+/// pool, on top of the best block whose hash is returned along.
+pub(crate) fn pending_block<B, C, P>(
+	client: &C,
+	graph: &P,
+) -> RpcResult<(B::Hash, Option<(EthereumBlock, Vec<TransactionStatus>)>)>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	P: TransactionPool<Block = B, Hash = B::Hash>,
+{
+	let best_hash = client.info().best_hash;
+	let (block, statuses) = client
+		.runtime_api()
+		.pending_block(best_hash, pending_extrinsics(graph))
+		.map_err(|_| internal_err(format!("Runtime access error at {}", best_hash)))?;
+
+	Ok((best_hash, block.zip(statuses)))
+}
+
 /// Consensus data provider, pending api uses this trait object for authoring blocks valid for any runtime.
 pub trait ConsensusDataProvider<B: BlockT>: Send + Sync {
 	/// Attempt to create a consensus digest.
@@ -223,3 +265,51 @@ mod aura {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+
+	use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+	use substrate_test_runtime_client::{
+		runtime::{Extrinsic, Transfer},
+		DefaultTestClientBuilderExt, Sr25519Keyring, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	use super::*;
+
+	fn transfer(nonce: u64) -> Extrinsic {
+		Transfer {
+			from: Sr25519Keyring::Alice.into(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 1,
+			nonce,
+		}
+		.into_unchecked_extrinsic()
+	}
+
+	#[tokio::test]
+	async fn pending_extrinsics_are_the_ready_then_the_future_transactions() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let pool = sc_transaction_pool::Builder::new(
+			sp_core::testing::TaskExecutor::new(),
+			client.clone(),
+			true.into(),
+		)
+		.build();
+		let best_hash = client.info().best_hash;
+
+		// The nonce 1 is missing, so the transaction with the nonce 2 is a future one.
+		let future = transfer(2);
+		let ready = transfer(0);
+		for xt in [future.clone(), ready.clone()] {
+			pool.submit_one(best_hash, TransactionSource::External, xt)
+				.await
+				.expect("the transaction is valid");
+		}
+		assert_eq!(pool.status().ready, 1);
+		assert_eq!(pool.status().future, 1);
+
+		assert_eq!(pending_extrinsics(&pool), vec![ready, future]);
+	}
+}
@@ -129,18 +129,11 @@ where
 				.map_err(|err| internal_err(format!("Fetch account nonce failed: {err}")))?
 				.nonce;
 
-			let mut current_nonce = nonce;
-			let mut current_tag = (address, nonce).encode();
-			for tx in self.pool.ready() {
-				// since transactions in `ready()` need to be ordered by nonce
-				// it's fine to continue with current iterator.
-				if tx.provides().first() == Some(&current_tag) {
-					current_nonce = current_nonce.saturating_add(1.into());
-					current_tag = (address, current_nonce).encode();
-				}
-			}
-
-			return Ok(current_nonce);
+			return Ok(pending_nonce(
+				address,
+				nonce,
+				self.pool.ready().map(|tx| tx.provides().first().cloned()),
+			));
 		}
 
 		let id = match frontier_backend_client::native_block_id::<B, C>(
@@ -203,3 +196,69 @@ where
 		}
 	}
 }
+
+/// The nonce following the ready transactions of `address` which directly follow its state
+/// `nonce`, given the first tag provided by each ready transaction.
+fn pending_nonce(
+	address: H160,
+	nonce: U256,
+	provides: impl Iterator<Item = Option<Vec<u8>>>,
+) -> U256 {
+	let mut current_nonce = nonce;
+	let mut current_tag = (address, nonce).encode();
+	for tag in provides {
+		// since transactions in `ready()` need to be ordered by nonce
+		// it's fine to continue with current iterator.
+		if tag.as_ref() == Some(&current_tag) {
+			current_nonce = current_nonce.saturating_add(1.into());
+			current_tag = (address, current_nonce).encode();
+		}
+	}
+	current_nonce
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tag(address: H160, nonce: u64) -> Option<Vec<u8>> {
+		Some((address, U256::from(nonce)).encode())
+	}
+
+	#[test]
+	fn pending_nonce_follows_the_ready_transactions() {
+		let alice = H160::repeat_byte(0xaa);
+		let bob = H160::repeat_byte(0xbb);
+		let ready = vec![
+			tag(alice, 0),
+			tag(bob, 7),
+			tag(alice, 1),
+			None,
+			tag(alice, 2),
+		];
+
+		assert_eq!(
+			pending_nonce(alice, U256::zero(), ready.clone().into_iter()),
+			U256::from(3)
+		);
+		assert_eq!(
+			pending_nonce(bob, U256::from(7), ready.into_iter()),
+			U256::from(8)
+		);
+	}
+
+	#[test]
+	fn pending_nonce_stops_at_a_gap() {
+		let alice = H160::repeat_byte(0xaa);
+		let ready = vec![tag(alice, 0), tag(alice, 2)];
+
+		assert_eq!(
+			pending_nonce(alice, U256::zero(), ready.into_iter()),
+			U256::one()
+		);
+		assert_eq!(
+			pending_nonce(alice, U256::from(5), std::iter::empty()),
+			U256::from(5)
+		);
+	}
+}
@@ -33,8 +33,8 @@ use fc_rpc_core::types::*;
 use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
 
 use crate::{
-	eth::{format, Eth},
-	internal_err, public_key,
+	eth::{check_dev_signing, format, Eth},
+	internal_err, public_key, runtime_api,
 };
 
 impl<B, C, P, CT, BE, CIDP, EC> Eth<B, C, P, CT, BE, CIDP, EC>
@@ -49,6 +49,43 @@ where
 	CIDP: CreateInherentDataProviders<B, ()> + Send + 'static,
 {
 	pub async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<H256> {
+		let transaction = self.sign_request(request).await?;
+		let transaction_hash = transaction.hash();
+
+		let block_hash = self.client.info().best_hash;
+		let extrinsic = self.convert_transaction(block_hash, transaction)?;
+
+		self.pool
+			.submit_one(block_hash, TransactionSource::Local, extrinsic)
+			.map_ok(move |_| transaction_hash)
+			.map_err(|err| internal_err(format::Geth::pool_error(err)))
+			.await
+	}
+
+	pub async fn sign_transaction(&self, request: TransactionRequest) -> RpcResult<Bytes> {
+		check_dev_signing(self.dev_signing, "eth_signTransaction")?;
+		let transaction = self.sign_request(request).await?;
+		Ok(Bytes::new(
+			ethereum::EnvelopedEncodable::encode(&transaction).to_vec(),
+		))
+	}
+
+	pub fn sign(&self, address: H160, data: Bytes) -> RpcResult<Bytes> {
+		check_dev_signing(self.dev_signing, "eth_sign")?;
+		let signer = self
+			.signers
+			.iter()
+			.find(|signer| signer.accounts().contains(&address))
+			.ok_or_else(|| internal_err("no signer available"))?;
+		let signature = signer.sign_message(&data.into_vec(), &address)?;
+		Ok(Bytes::new(signature.to_vec()))
+	}
+
+	/// Fills the missing fields of the request and signs it with the signer of its sender.
+	async fn sign_request(
+		&self,
+		request: TransactionRequest,
+	) -> RpcResult<ethereum::TransactionV3> {
 		let from = match request.from {
 			Some(from) => from,
 			None => {
@@ -87,7 +124,10 @@ where
 		let gas_limit = match request.gas {
 			Some(gas_limit) => gas_limit,
 			None => {
-				if let Ok(Some(block)) = self.client.runtime_api().current_block(block_hash) {
+				let api = self.client.runtime_api();
+				let api_version = self.api_versions.version(&*api, block_hash)?;
+				if let Ok(Some(block)) = runtime_api::current_block(&*api, block_hash, api_version)
+				{
 					block.header.gas_limit
 				} else {
 					return Err(internal_err("block unavailable, cannot query gas limit"));
@@ -139,19 +179,10 @@ where
 			}
 		}
 
-		let transaction = match transaction {
-			Some(transaction) => transaction,
-			None => return Err(internal_err("no signer available")),
-		};
-		let transaction_hash = transaction.hash();
-
-		let extrinsic = self.convert_transaction(block_hash, transaction)?;
-
-		self.pool
-			.submit_one(block_hash, TransactionSource::Local, extrinsic)
-			.map_ok(move |_| transaction_hash)
-			.map_err(|err| internal_err(format::Geth::pool_error(err)))
-			.await
+		match transaction {
+			Some(transaction) => Ok(transaction),
+			None => Err(internal_err("no signer available")),
+		}
 	}
 
 	pub async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<H256> {
@@ -200,21 +231,11 @@ where
 		let best_block = self.client.info().best_hash;
 		let api = self.client.runtime_api();
 
-		let api_version = api
-			.api_version::<dyn EthereumRuntimeRPCApi<B>>(best_block)
-			.map_err(|err| internal_err(format!("Failed to get API version: {}", err)))?
-			.ok_or_else(|| internal_err("Failed to get API version"))?;
-
-		let ethereum_txs = if api_version > 1 {
-			api.extrinsic_filter(best_block, all_extrinsics)
-				.map_err(|err| internal_err(format!("Runtime call failed: {}", err)))?
-		} else {
-			#[allow(deprecated)]
-			let legacy = api
-				.extrinsic_filter_before_version_2(best_block, all_extrinsics)
+		let api_version = self.api_versions.version(&*api, best_block)?;
+
+		let ethereum_txs =
+			runtime_api::extrinsic_filter(&*api, best_block, api_version, all_extrinsics)
 				.map_err(|err| internal_err(format!("Runtime call failed: {}", err)))?;
-			legacy.into_iter().map(|tx| tx.into()).collect()
-		};
 
 		let transactions = ethereum_txs
 			.into_iter()
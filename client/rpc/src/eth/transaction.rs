@@ -24,7 +24,7 @@ use jsonrpsee::core::RpcResult;
 // Substrate
 use sc_client_api::backend::{Backend, StorageProvider};
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
-use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_core::hashing::keccak_256;
 use sp_runtime::traits::Block as BlockT;
@@ -33,8 +33,8 @@ use fc_rpc_core::types::*;
 use fp_rpc::EthereumRuntimeRPCApi;
 
 use crate::{
-	eth::{transaction_build, BlockInfo, Eth},
-	frontier_backend_client, internal_err,
+	eth::{effective_gas_price, transaction_build, BlockInfo, Eth},
+	frontier_backend_client, internal_err, runtime_api,
 };
 
 impl<B, C, P, CT, BE, CIDP, EC> Eth<B, C, P, CT, BE, CIDP, EC>
@@ -55,7 +55,6 @@ where
 			client.as_ref(),
 			backend.as_ref(),
 			hash,
-			true,
 		)
 		.await
 		.map_err(|err| internal_err(format!("{:?}", err)))?
@@ -65,13 +64,7 @@ where
 				let api = client.runtime_api();
 				let best_block = client.info().best_hash;
 
-				let api_version = if let Ok(Some(api_version)) =
-					api.api_version::<dyn EthereumRuntimeRPCApi<B>>(best_block)
-				{
-					api_version
-				} else {
-					return Err(internal_err("failed to retrieve Runtime Api version"));
-				};
+				let api_version = self.api_versions.version(&*api, best_block)?;
 				// If the transaction is not yet mapped in the frontier db,
 				// check for it in the transaction pool.
 				let mut xts: Vec<<B as BlockT>::Extrinsic> = Vec::new();
@@ -92,21 +85,15 @@ where
 						.collect::<Vec<<B as BlockT>::Extrinsic>>(),
 				);
 
-				let ethereum_transactions: Vec<EthereumTransaction> = if api_version > 1 {
-					api.extrinsic_filter(best_block, xts).map_err(|err| {
-						internal_err(format!("fetch runtime extrinsic filter failed: {:?}", err))
-					})?
-				} else {
-					#[allow(deprecated)]
-					let legacy = api.extrinsic_filter_before_version_2(best_block, xts)
-						.map_err(|err| {
+				let ethereum_transactions: Vec<EthereumTransaction> =
+					runtime_api::extrinsic_filter(&*api, best_block, api_version, xts).map_err(
+						|err| {
 							internal_err(format!(
 								"fetch runtime extrinsic filter failed: {:?}",
 								err
 							))
-						})?;
-					legacy.into_iter().map(|tx| tx.into()).collect()
-				};
+						},
+					)?;
 
 				for txn in ethereum_transactions {
 					let inner_hash = txn.hash();
@@ -122,16 +109,19 @@ where
 		let BlockInfo {
 			block,
 			statuses,
-			base_fee,
+			substrate_hash,
 			..
 		} = self.block_info_by_eth_block_hash(eth_block_hash).await?;
 		match (block, statuses) {
-			(Some(block), Some(statuses)) => Ok(Some(transaction_build(
-				&block.transactions[index],
-				Some(&block),
-				Some(&statuses[index]),
-				Some(base_fee),
-			))),
+			(Some(block), Some(statuses)) => {
+				let base_fee = self.included_base_fee(&block, substrate_hash).await?;
+				Ok(Some(transaction_build(
+					&block.transactions[index],
+					Some(&block),
+					Some(&statuses[index]),
+					Some(base_fee),
+				)))
+			}
 			_ => Ok(None),
 		}
 	}
@@ -145,7 +135,7 @@ where
 		let BlockInfo {
 			block,
 			statuses,
-			base_fee,
+			substrate_hash,
 			..
 		} = self.block_info_by_eth_block_hash(hash).await?;
 
@@ -154,6 +144,7 @@ where
 				if let (Some(transaction), Some(status)) =
 					(block.transactions.get(index), statuses.get(index))
 				{
+					let base_fee = self.included_base_fee(&block, substrate_hash).await?;
 					Ok(Some(transaction_build(
 						transaction,
 						Some(&block),
@@ -177,7 +168,7 @@ where
 		let BlockInfo {
 			block,
 			statuses,
-			base_fee,
+			substrate_hash,
 			..
 		} = self.block_info_by_number(number).await?;
 
@@ -186,6 +177,7 @@ where
 				if let (Some(transaction), Some(status)) =
 					(block.transactions.get(index), statuses.get(index))
 				{
+					let base_fee = self.included_base_fee(&block, substrate_hash).await?;
 					Ok(Some(transaction_build(
 						transaction,
 						Some(&block),
@@ -200,6 +192,25 @@ where
 		}
 	}
 
+	pub async fn transaction_effective_gas_price(&self, hash: H256) -> RpcResult<Option<U256>> {
+		let (block_info, index) = self.block_info_by_eth_transaction_hash(hash).await?;
+		let BlockInfo {
+			block,
+			substrate_hash,
+			..
+		} = block_info;
+		match block {
+			Some(block) => match block.transactions.get(index) {
+				Some(transaction) => {
+					let base_fee = self.included_base_fee(&block, substrate_hash).await?;
+					Ok(Some(effective_gas_price(transaction, base_fee)))
+				}
+				None => Err(internal_err(format!("{:?} is out of bounds", index))),
+			},
+			_ => Ok(None),
+		}
+	}
+
 	pub async fn transaction_receipt(
 		&self,
 		block_info: &BlockInfo<B::Hash>,
@@ -282,58 +293,8 @@ where
 				let status = statuses[index].clone();
 				let mut cumulative_receipts = receipts;
 				cumulative_receipts.truncate((status.transaction_index + 1) as usize);
-				let transaction = block.transactions[index].clone();
-				// Helper closure for EIP1559-style effective gas price calculation (used by EIP1559 and EIP7702)
-				let calculate_eip1559_effective_gas_price =
-					|max_priority_fee_per_gas: U256, max_fee_per_gas: U256| async move {
-						let parent_eth_hash = block.header.parent_hash;
-						let base_fee_block_substrate_hash = if parent_eth_hash.is_zero() {
-							substrate_hash
-						} else {
-							frontier_backend_client::load_hash::<B, C>(
-								self.client.as_ref(),
-								self.backend.as_ref(),
-								parent_eth_hash,
-							)
-							.await
-							.map_err(|err| internal_err(format!("{:?}", err)))?
-							.ok_or(internal_err(
-								"Failed to retrieve substrate parent block hash",
-							))?
-						};
-
-						let base_fee = self
-							.client
-							.runtime_api()
-							.gas_price(base_fee_block_substrate_hash)
-							.unwrap_or_default();
-
-						Ok::<ethereum_types::U256, jsonrpsee::types::error::ErrorObjectOwned>(
-							base_fee
-								.checked_add(max_priority_fee_per_gas)
-								.unwrap_or_else(U256::max_value)
-								.min(max_fee_per_gas),
-						)
-					};
-
-				let effective_gas_price = match &transaction {
-					EthereumTransaction::Legacy(t) => t.gas_price,
-					EthereumTransaction::EIP2930(t) => t.gas_price,
-					EthereumTransaction::EIP1559(t) => {
-						calculate_eip1559_effective_gas_price(
-							t.max_priority_fee_per_gas,
-							t.max_fee_per_gas,
-						)
-						.await?
-					}
-					EthereumTransaction::EIP7702(t) => {
-						calculate_eip1559_effective_gas_price(
-							t.max_priority_fee_per_gas,
-							t.max_fee_per_gas,
-						)
-						.await?
-					}
-				};
+				let base_fee = self.included_base_fee(&block, substrate_hash).await?;
+				let effective_gas_price = effective_gas_price(&block.transactions[index], base_fee);
 
 				return Ok(Some(Receipt {
 					transaction_hash: Some(status.transaction_hash),
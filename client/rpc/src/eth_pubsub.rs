@@ -16,11 +16,20 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+	collections::HashMap,
+	marker::PhantomData,
+	sync::{Arc, Mutex},
+};
 
 use ethereum::TransactionV3 as EthereumTransaction;
 use futures::{future, FutureExt as _, StreamExt as _};
-use jsonrpsee::{core::traits::IdProvider, server::PendingSubscriptionSink};
+use jsonrpsee::{
+	core::{traits::IdProvider, RpcResult},
+	server::PendingSubscriptionSink,
+	types::SubscriptionId,
+	ConnectionId, Extensions,
+};
 // Substrate
 use sc_client_api::{
 	backend::{Backend, StorageProvider},
@@ -28,12 +37,12 @@ use sc_client_api::{
 };
 use sc_network_sync::SyncingService;
 use sc_rpc::{
-	utils::{BoundedVecDeque, PendingSubscription, Subscription},
+	utils::{BoundedVecDeque, Subscription},
 	SubscriptionTaskExecutor,
 };
 use sc_service::config::RpcSubscriptionIdProvider;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TxHash};
-use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_consensus::SyncOracle;
 use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
@@ -41,7 +50,7 @@ use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
 use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
 use fc_rpc_core::{
 	types::{
-		pubsub::{Kind, Params, PubSubResult, PubSubSyncing, SyncingStatus},
+		pubsub::{ActiveSubscription, Kind, Params, PubSubResult, PubSubSyncing, SyncingStatus},
 		FilteredParams,
 	},
 	EthPubSubApiServer,
@@ -49,6 +58,11 @@ use fc_rpc_core::{
 use fc_storage::StorageOverride;
 use fp_rpc::EthereumRuntimeRPCApi;
 
+use crate::{
+	metrics::RpcMetrics,
+	runtime_api::{self, EthereumRuntimeApiVersions},
+};
+
 #[derive(Clone, Debug)]
 pub struct EthereumSubIdProvider;
 impl IdProvider for EthereumSubIdProvider {
@@ -67,6 +81,9 @@ pub struct EthPubSub<B: BlockT, P, C, BE> {
 	storage_override: Arc<dyn StorageOverride<B>>,
 	starting_block: u64,
 	pubsub_notification_sinks: Arc<EthereumBlockNotificationSinks<EthereumBlockNotification<B>>>,
+	active_subscriptions: ActiveSubscriptions,
+	api_versions: EthereumRuntimeApiVersions<B>,
+	metrics: RpcMetrics,
 	_marker: PhantomData<BE>,
 }
 
@@ -80,6 +97,9 @@ impl<B: BlockT, P, C, BE> Clone for EthPubSub<B, P, C, BE> {
 			storage_override: self.storage_override.clone(),
 			starting_block: self.starting_block,
 			pubsub_notification_sinks: self.pubsub_notification_sinks.clone(),
+			active_subscriptions: self.active_subscriptions.clone(),
+			api_versions: self.api_versions.clone(),
+			metrics: self.metrics.clone(),
 			_marker: PhantomData::<BE>,
 		}
 	}
@@ -114,10 +134,19 @@ where
 			storage_override,
 			starting_block,
 			pubsub_notification_sinks,
+			active_subscriptions: ActiveSubscriptions::default(),
+			api_versions: EthereumRuntimeApiVersions::default(),
+			metrics: RpcMetrics::default(),
 			_marker: PhantomData,
 		}
 	}
 
+	/// Records the subscriptions served by this API in the given metrics.
+	pub fn with_metrics(mut self, metrics: RpcMetrics) -> Self {
+		self.metrics = metrics;
+		self
+	}
+
 	fn notify_header(
 		&self,
 		notification: EthereumBlockNotification<B>,
@@ -157,26 +186,14 @@ where
 
 			let api = self.client.runtime_api();
 
-			let api_version = if let Ok(Some(api_version)) =
-				api.api_version::<dyn EthereumRuntimeRPCApi<B>>(best_block)
-			{
-				api_version
-			} else {
+			let Ok(api_version) = self.api_versions.version(&*api, best_block) else {
 				return future::ready(None);
 			};
 
 			let xts = vec![xt.data().as_ref().clone()];
 
-			let txs: Option<Vec<EthereumTransaction>> = if api_version > 1 {
-				api.extrinsic_filter(best_block, xts).ok()
-			} else {
-				#[allow(deprecated)]
-				if let Ok(legacy) = api.extrinsic_filter_before_version_2(best_block, xts) {
-					Some(legacy.into_iter().map(|tx| tx.into()).collect())
-				} else {
-					None
-				}
-			};
+			let txs: Option<Vec<EthereumTransaction>> =
+				runtime_api::extrinsic_filter(&*api, best_block, api_version, xts).ok();
 
 			match txs {
 				Some(txs) => {
@@ -240,12 +257,34 @@ where
 			sc_utils::mpsc::tracing_unbounded("pubsub_notification_stream", 100_000);
 		self.pubsub_notification_sinks.lock().push(inner_sink);
 
+		let subscription_guard = self.metrics.track_subscription(match kind {
+			Kind::NewHeads => "newHeads",
+			Kind::Logs => "logs",
+			Kind::NewPendingTransactions => "newPendingTransactions",
+			Kind::Syncing => "syncing",
+		});
+
 		let fut = async move {
+			// Active until the subscription ends.
+			let _subscription_guard = subscription_guard;
+			let connection_id = pending.connection_id();
+			let Ok(sink) = pending.accept().await else {
+				return;
+			};
+			// Listed until the subscription ends.
+			let _active_subscription_guard = pubsub.active_subscriptions.insert(
+				connection_id,
+				ActiveSubscription {
+					id: subscription_id_to_string(sink.subscription_id()),
+					kind: kind.clone(),
+				},
+			);
+			let subscription = Subscription::from(sink);
 			match kind {
 				Kind::NewHeads => {
 					let stream = block_notification_stream
 						.filter_map(move |notification| pubsub.notify_header(notification));
-					PendingSubscription::from(pending)
+					subscription
 						.pipe_from_stream(stream, BoundedVecDeque::new(16))
 						.await
 				}
@@ -255,7 +294,7 @@ where
 							pubsub.notify_logs(notification, &filtered_params)
 						})
 						.flat_map(futures::stream::iter);
-					PendingSubscription::from(pending)
+					subscription
 						.pipe_from_stream(stream, BoundedVecDeque::new(16))
 						.await
 				}
@@ -264,19 +303,15 @@ where
 					let stream = pool
 						.import_notification_stream()
 						.filter_map(move |hash| pubsub.pending_transactions(&hash));
-					PendingSubscription::from(pending)
+					subscription
 						.pipe_from_stream(stream, BoundedVecDeque::new(16))
 						.await;
 				}
 				Kind::Syncing => {
-					let Ok(sink) = pending.accept().await else {
-						return;
-					};
 					// On connection subscriber expects a value.
 					// Because import notifications are only emitted when the node is synced or
 					// in case of reorg, the first event is emitted right away.
 					let syncing_status = pubsub.syncing_status().await;
-					let subscription = Subscription::from(sink);
 					let _ = subscription
 						.send(&PubSubResult::SyncingStatus(syncing_status))
 						.await;
@@ -285,18 +320,22 @@ where
 					// normally to import notifications.
 					//
 					// Only send new notifications down the pipe when the syncing status changed.
-					let mut stream = pubsub.client.import_notification_stream();
-					let mut last_syncing_status = pubsub.sync.is_major_syncing();
-					while (stream.next().await).is_some() {
-						let syncing_status = pubsub.sync.is_major_syncing();
-						if syncing_status != last_syncing_status {
-							let syncing_status = pubsub.syncing_status().await;
-							let _ = subscription
-								.send(&PubSubResult::SyncingStatus(syncing_status))
-								.await;
+					let status_updates = async {
+						let mut stream = pubsub.client.import_notification_stream();
+						let mut last_syncing_status = pubsub.sync.is_major_syncing();
+						while (stream.next().await).is_some() {
+							let syncing_status = pubsub.sync.is_major_syncing();
+							if syncing_status != last_syncing_status {
+								let syncing_status = pubsub.syncing_status().await;
+								let _ = subscription
+									.send(&PubSubResult::SyncingStatus(syncing_status))
+									.await;
+							}
+							last_syncing_status = syncing_status;
 						}
-						last_syncing_status = syncing_status;
-					}
+					};
+					// Import notifications never end, stop as soon as the client unsubscribes.
+					future::select(Box::pin(status_updates), Box::pin(subscription.closed())).await;
 				}
 			}
 		}
@@ -305,4 +344,128 @@ where
 		self.executor
 			.spawn("frontier-rpc-subscription", Some("rpc"), fut);
 	}
+
+	fn active_subscriptions(&self, ext: &Extensions) -> RpcResult<Vec<ActiveSubscription>> {
+		Ok(ext
+			.get::<ConnectionId>()
+			.map(|connection_id| self.active_subscriptions.list(*connection_id))
+			.unwrap_or_default())
+	}
+}
+
+fn subscription_id_to_string(id: SubscriptionId<'_>) -> String {
+	match id {
+		SubscriptionId::Num(id) => id.to_string(),
+		SubscriptionId::Str(id) => id.into_owned(),
+	}
+}
+
+/// The active subscriptions of every connection.
+#[derive(Clone, Default)]
+struct ActiveSubscriptions(Arc<Mutex<HashMap<ConnectionId, Vec<ActiveSubscription>>>>);
+
+impl ActiveSubscriptions {
+	/// Lists the subscription of the connection until the returned guard is dropped.
+	fn insert(
+		&self,
+		connection_id: ConnectionId,
+		subscription: ActiveSubscription,
+	) -> ActiveSubscriptionGuard {
+		let id = subscription.id.clone();
+		if let Ok(connections) = &mut self.0.lock() {
+			connections
+				.entry(connection_id)
+				.or_default()
+				.push(subscription);
+		}
+		ActiveSubscriptionGuard {
+			subscriptions: self.clone(),
+			connection_id,
+			id,
+		}
+	}
+
+	fn list(&self, connection_id: ConnectionId) -> Vec<ActiveSubscription> {
+		match &self.0.lock() {
+			Ok(connections) => connections.get(&connection_id).cloned().unwrap_or_default(),
+			Err(_) => Vec::new(),
+		}
+	}
+
+	fn remove(&self, connection_id: ConnectionId, id: &str) {
+		if let Ok(connections) = &mut self.0.lock() {
+			if let Some(subscriptions) = connections.get_mut(&connection_id) {
+				subscriptions.retain(|subscription| subscription.id != id);
+				if subscriptions.is_empty() {
+					connections.remove(&connection_id);
+				}
+			}
+		}
+	}
+}
+
+/// Removes a subscription from the active ones when dropped.
+struct ActiveSubscriptionGuard {
+	subscriptions: ActiveSubscriptions,
+	connection_id: ConnectionId,
+	id: String,
+}
+
+impl Drop for ActiveSubscriptionGuard {
+	fn drop(&mut self) {
+		self.subscriptions.remove(self.connection_id, &self.id);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn subscription(id: &str, kind: Kind) -> ActiveSubscription {
+		ActiveSubscription {
+			id: id.into(),
+			kind,
+		}
+	}
+
+	#[test]
+	fn active_subscriptions_are_listed_per_connection() {
+		let active_subscriptions = ActiveSubscriptions::default();
+		let (first, second) = (ConnectionId(1), ConnectionId(2));
+
+		let heads = active_subscriptions.insert(first, subscription("0x01", Kind::NewHeads));
+		let logs = active_subscriptions.insert(first, subscription("0x02", Kind::Logs));
+		let _syncing = active_subscriptions.insert(second, subscription("0x03", Kind::Syncing));
+
+		assert_eq!(
+			active_subscriptions.list(first),
+			vec![
+				subscription("0x01", Kind::NewHeads),
+				subscription("0x02", Kind::Logs)
+			]
+		);
+		assert_eq!(
+			active_subscriptions.list(second),
+			vec![subscription("0x03", Kind::Syncing)]
+		);
+
+		// Unsubscribing removes the subscription from the list.
+		drop(heads);
+		assert_eq!(
+			active_subscriptions.list(first),
+			vec![subscription("0x02", Kind::Logs)]
+		);
+		drop(logs);
+		assert!(active_subscriptions.list(first).is_empty());
+		assert_eq!(active_subscriptions.list(second).len(), 1);
+	}
+
+	#[test]
+	fn subscription_ids_are_listed_as_strings() {
+		assert_eq!(subscription_id_to_string(SubscriptionId::Num(42)), "42");
+		assert_eq!(
+			subscription_id_to_string(SubscriptionId::Str("0xabcd".into())),
+			"0xabcd"
+		);
+	}
 }
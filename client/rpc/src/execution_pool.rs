@@ -0,0 +1,118 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{future::Future, sync::Arc};
+
+use tokio::{
+	runtime::{Handle, RuntimeFlavor},
+	sync::Semaphore,
+};
+
+/// Runs the EVM executions of the RPC methods, e.g. `eth_call`, on the blocking threads of the
+/// runtime rather than on its async workers, so that a long execution doesn't hold up the other
+/// requests served by the same worker. At most `size` executions run at once, the others wait for
+/// their turn without using a thread.
+///
+/// A single instance can be shared by several RPC handlers to bound their executions together.
+#[derive(Clone)]
+pub struct ExecutionPool(Arc<Semaphore>);
+
+impl ExecutionPool {
+	pub fn new(size: usize) -> Self {
+		Self(Arc::new(Semaphore::new(size.max(1))))
+	}
+
+	/// Runs the `execution` to completion once a slot of the pool is free.
+	///
+	/// Outside of a multi-threaded runtime, the execution runs in place.
+	pub(crate) async fn run<F: Future>(&self, execution: F) -> F::Output {
+		let _permit = self
+			.0
+			.acquire()
+			.await
+			.expect("the semaphore is never closed; qed");
+		match Handle::try_current() {
+			Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+				tokio::task::block_in_place(|| futures::executor::block_on(execution))
+			}
+			_ => execution.await,
+		}
+	}
+}
+
+impl Default for ExecutionPool {
+	/// A pool running as many executions as there are CPUs.
+	fn default() -> Self {
+		Self::new(
+			std::thread::available_parallelism()
+				.map(|parallelism| parallelism.get())
+				.unwrap_or(1),
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::{sync::mpsc, time::Duration};
+
+	use futures::FutureExt;
+
+	use super::*;
+
+	#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+	async fn slow_executions_dont_block_other_requests() {
+		let pool = ExecutionPool::new(1);
+		let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+		let (release_tx, release_rx) = mpsc::channel::<()>();
+
+		// Blocks the only worker thread until released.
+		let slow = tokio::spawn({
+			let pool = pool.clone();
+			async move {
+				pool.run(async move {
+					started_tx.send(()).unwrap();
+					release_rx.recv().unwrap();
+					"slow"
+				})
+				.await
+			}
+		});
+		started_rx.await.unwrap();
+
+		let fast = tokio::spawn(async { "fast" });
+		assert_eq!(
+			tokio::time::timeout(Duration::from_secs(5), fast)
+				.await
+				.expect("fast requests aren't blocked")
+				.unwrap(),
+			"fast"
+		);
+
+		// The pool is full until the slow execution completes.
+		assert!(pool.run(async {}).now_or_never().is_none());
+
+		release_tx.send(()).unwrap();
+		assert_eq!(slow.await.unwrap(), "slow");
+		assert_eq!(pool.run(async { 1 }).await, 1);
+	}
+
+	#[tokio::test]
+	async fn executions_run_in_place_on_a_current_thread_runtime() {
+		assert_eq!(ExecutionPool::new(1).run(async { 1 }).await, 1);
+	}
+}
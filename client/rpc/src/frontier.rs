@@ -0,0 +1,318 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{future::Future, sync::Arc};
+
+use ethereum_types::U256;
+use futures::{FutureExt as _, StreamExt as _};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	server::PendingSubscriptionSink,
+};
+// Substrate
+use sc_client_api::client::BlockchainEvents;
+use sc_rpc::{utils::Subscription, SubscriptionTaskExecutor};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, NumberFor, Saturating, UniqueSaturatedInto};
+// Frontier
+use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
+use fc_rpc_core::{
+	types::{
+		pubsub::{FrontierKind, PubSubResult},
+		IndexedBlock,
+	},
+	FrontierApiServer,
+};
+use fc_storage::StorageOverride;
+
+use crate::internal_err;
+
+/// Frontier API implementation.
+pub struct Frontier<B: BlockT, C> {
+	client: Arc<C>,
+	backend: Arc<dyn fc_api::Backend<B>>,
+	storage_override: Arc<dyn StorageOverride<B>>,
+	executor: SubscriptionTaskExecutor,
+	pubsub_notification_sinks: Arc<EthereumBlockNotificationSinks<EthereumBlockNotification<B>>>,
+	/// When set, blocks this deep below the best block are considered final. Used on chains
+	/// without a finality gadget, where the client finalized head never moves.
+	pseudo_finality_depth: Option<u32>,
+}
+
+impl<B: BlockT, C> Clone for Frontier<B, C> {
+	fn clone(&self) -> Self {
+		Self {
+			client: self.client.clone(),
+			backend: self.backend.clone(),
+			storage_override: self.storage_override.clone(),
+			executor: self.executor.clone(),
+			pubsub_notification_sinks: self.pubsub_notification_sinks.clone(),
+			pseudo_finality_depth: self.pseudo_finality_depth,
+		}
+	}
+}
+
+impl<B, C> Frontier<B, C>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + 'static,
+{
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<dyn fc_api::Backend<B>>,
+		storage_override: Arc<dyn StorageOverride<B>>,
+		executor: SubscriptionTaskExecutor,
+		pubsub_notification_sinks: Arc<
+			EthereumBlockNotificationSinks<EthereumBlockNotification<B>>,
+		>,
+		pseudo_finality_depth: Option<u32>,
+	) -> Self {
+		Self {
+			client,
+			backend,
+			storage_override,
+			executor,
+			pubsub_notification_sinks,
+			pseudo_finality_depth,
+		}
+	}
+
+	/// The number of the latest final block, either as finalized by the client or, with
+	/// pseudo-finality enabled, as the block `pseudo_finality_depth` blocks below the best one.
+	fn finalized_number(&self) -> NumberFor<B> {
+		let info = self.client.info();
+		match self.pseudo_finality_depth {
+			Some(depth) => info.best_number.saturating_sub(depth.into()),
+			None => info.finalized_number,
+		}
+	}
+
+	/// Load the Ethereum header of the canonical block at the given final height.
+	async fn finalized_header(&self, number: u64) -> Availability<PubSubResult> {
+		let number: NumberFor<B> = number.unique_saturated_into();
+		let substrate_hash = match self.client.hash(number) {
+			Ok(Some(hash)) => hash,
+			_ => return Availability::Pending,
+		};
+		let Some(block) = self.storage_override.current_block(substrate_hash) else {
+			// No Ethereum block at this height (e.g. before the pallet was added).
+			return Availability::Skip;
+		};
+
+		// Only notify blocks that the mapping-sync worker has already indexed, so subscribers
+		// can immediately query them by hash.
+		let is_indexed = self
+			.backend
+			.block_hash(&block.header.hash())
+			.await
+			.ok()
+			.flatten()
+			.is_some_and(|hashes| hashes.contains(&substrate_hash));
+		if is_indexed {
+			Availability::Ready(PubSubResult::header(block))
+		} else {
+			Availability::Pending
+		}
+	}
+}
+
+#[async_trait]
+impl<B, C> FrontierApiServer for Frontier<B, C>
+where
+	B: BlockT,
+	C: HeaderBackend<B> + BlockchainEvents<B> + 'static,
+{
+	fn finalized_block_number(&self) -> RpcResult<U256> {
+		let number = self.finalized_number();
+		let substrate_hash = self
+			.client
+			.hash(number)
+			.map_err(|err| internal_err(format!("{:?}", err)))?
+			.ok_or_else(|| internal_err("finalized block not found"))?;
+		let block = self
+			.storage_override
+			.current_block(substrate_hash)
+			.ok_or_else(|| internal_err("finalized Ethereum block not found"))?;
+		Ok(block.header.number)
+	}
+
+	async fn latest_indexed_block(&self) -> RpcResult<Option<IndexedBlock>> {
+		let Some((_, substrate_hash)) =
+			self.backend.latest_indexed_block().await.map_err(|err| {
+				internal_err(format!("fetch latest indexed block failed: {:?}", err))
+			})?
+		else {
+			return Ok(None);
+		};
+		Ok(self
+			.storage_override
+			.current_block(substrate_hash)
+			.map(|block| IndexedBlock {
+				number: block.header.number,
+				hash: block.header.hash(),
+			}))
+	}
+
+	fn subscribe(&self, pending: PendingSubscriptionSink, kind: FrontierKind) {
+		let frontier = self.clone();
+		// The mapping-sync worker notifies through this channel every time it indexes a block.
+		let (inner_sink, block_notification_stream) =
+			sc_utils::mpsc::tracing_unbounded("frontier_notification_stream", 100_000);
+		self.pubsub_notification_sinks.lock().push(inner_sink);
+
+		let fut = async move {
+			match kind {
+				FrontierKind::FinalizedHeads => {
+					let Ok(sink) = pending.accept().await else {
+						return;
+					};
+					let subscription = Subscription::from(sink);
+
+					let finalized: u64 = frontier.finalized_number().unique_saturated_into();
+					let mut cursor = FinalizedHeadsCursor::new(finalized);
+
+					// Re-check on both finality and indexing progress: a block finalized before
+					// it is indexed is only emitted once the mapping catches up.
+					let finality_stream =
+						frontier.client.finality_notification_stream().map(|_| ());
+					let mut stream = futures::stream::select(
+						block_notification_stream.map(|_| ()),
+						finality_stream,
+					);
+					while stream.next().await.is_some() {
+						let finalized: u64 = frontier.finalized_number().unique_saturated_into();
+						let headers = cursor
+							.advance(finalized, |number| frontier.finalized_header(number))
+							.await;
+						for header in headers {
+							if subscription.send(&header).await.is_err() {
+								return;
+							}
+						}
+					}
+				}
+			}
+		}
+		.boxed();
+
+		self.executor
+			.spawn("frontier-rpc-subscription", Some("rpc"), fut);
+	}
+}
+
+/// Whether the notification for a final block can be emitted.
+enum Availability<T> {
+	/// The notification is ready to be emitted.
+	Ready(T),
+	/// The block has nothing to notify, move on to the next one.
+	Skip,
+	/// The block is not available yet, retry later.
+	Pending,
+}
+
+/// Tracks the next final block to notify, so that a finality jump over several blocks still
+/// emits every block, in order.
+struct FinalizedHeadsCursor {
+	next: u64,
+}
+
+impl FinalizedHeadsCursor {
+	/// Start notifying from the block following `finalized`.
+	fn new(finalized: u64) -> Self {
+		Self {
+			next: finalized.saturating_add(1),
+		}
+	}
+
+	/// Load the notifications for all blocks up to and including `finalized`, stopping before
+	/// the first block that is still pending.
+	async fn advance<T, F, Fut>(&mut self, finalized: u64, mut load: F) -> Vec<T>
+	where
+		F: FnMut(u64) -> Fut,
+		Fut: Future<Output = Availability<T>>,
+	{
+		let mut items = Vec::new();
+		while self.next <= finalized {
+			match load(self.next).await {
+				Availability::Ready(item) => items.push(item),
+				Availability::Skip => {}
+				Availability::Pending => break,
+			}
+			self.next += 1;
+		}
+		items
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::{executor, future};
+
+	#[test]
+	fn finalized_heads_cursor_emits_batch_in_order() {
+		let mut cursor = FinalizedHeadsCursor::new(5);
+
+		// Nothing new is final.
+		let items = executor::block_on(
+			cursor.advance(5, |number| future::ready(Availability::Ready(number))),
+		);
+		assert!(items.is_empty());
+
+		// Finality jumps several blocks at once.
+		let items = executor::block_on(
+			cursor.advance(9, |number| future::ready(Availability::Ready(number))),
+		);
+		assert_eq!(items, vec![6, 7, 8, 9]);
+	}
+
+	#[test]
+	fn finalized_heads_cursor_waits_for_indexing() {
+		let mut cursor = FinalizedHeadsCursor::new(0);
+
+		// Block 3 is final but not indexed yet.
+		let items = executor::block_on(cursor.advance(5, |number| {
+			future::ready(if number < 3 {
+				Availability::Ready(number)
+			} else {
+				Availability::Pending
+			})
+		}));
+		assert_eq!(items, vec![1, 2]);
+
+		// Once indexed, the remaining blocks are emitted in order.
+		let items = executor::block_on(
+			cursor.advance(5, |number| future::ready(Availability::Ready(number))),
+		);
+		assert_eq!(items, vec![3, 4, 5]);
+	}
+
+	#[test]
+	fn finalized_heads_cursor_skips_blocks_without_ethereum_block() {
+		let mut cursor = FinalizedHeadsCursor::new(0);
+
+		let items = executor::block_on(cursor.advance(3, |number| {
+			future::ready(if number == 2 {
+				Availability::Skip
+			} else {
+				Availability::Ready(number)
+			})
+		}));
+		assert_eq!(items, vec![1, 3]);
+	}
+}
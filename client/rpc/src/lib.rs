@@ -30,7 +30,12 @@ mod cache;
 mod debug;
 mod eth;
 mod eth_pubsub;
+mod execution_pool;
+mod frontier;
+mod metrics;
 mod net;
+mod pool_limit;
+mod runtime_api;
 mod signer;
 #[cfg(feature = "txpool")]
 mod txpool;
@@ -41,9 +46,16 @@ pub use self::txpool::TxPool;
 pub use self::{
 	cache::{EthBlockDataCacheTask, EthTask},
 	debug::Debug,
-	eth::{format, pending, EstimateGasAdapter, Eth, EthConfig, EthFilter},
+	eth::{
+		format, pending, EstimateGasAdapter, Eth, EthConfig, EthFilter,
+		DEFAULT_MAX_BLOCK_TRANSACTIONS_PAGE,
+	},
 	eth_pubsub::{EthPubSub, EthereumSubIdProvider},
+	execution_pool::ExecutionPool,
+	frontier::Frontier,
+	metrics::RpcMetrics,
 	net::Net,
+	pool_limit::DEFAULT_MAX_POOL_TOTAL_MEMORY,
 	signer::{EthDevSigner, EthSigner},
 	web3::Web3,
 };
@@ -51,13 +63,13 @@ pub use ethereum::TransactionV3 as EthereumTransaction;
 #[cfg(feature = "txpool")]
 pub use fc_rpc_core::TxPoolApiServer;
 pub use fc_rpc_core::{
-	DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, NetApiServer,
-	Web3ApiServer,
+	DebugApiServer, EthApiServer, EthFilterApiServer, EthPubSubApiServer, FrontierApiServer,
+	NetApiServer, Web3ApiServer,
 };
 pub use fc_storage::{overrides::*, StorageOverrideHandler};
 
 pub mod frontier_backend_client {
-	use super::internal_err;
+	use super::{err, internal_err};
 
 	use ethereum_types::{H160, H256, U256};
 	use jsonrpsee::core::RpcResult;
@@ -71,7 +83,7 @@ pub mod frontier_backend_client {
 	use sp_io::hashing::{blake2_128, twox_128};
 	use sp_runtime::{
 		generic::BlockId,
-		traits::{Block as BlockT, HashingFor, UniqueSaturatedInto},
+		traits::{Block as BlockT, HashingFor, NumberFor, UniqueSaturatedInto},
 	};
 	use sp_state_machine::OverlayedChanges;
 	// Frontier
@@ -219,6 +231,44 @@ pub mod frontier_backend_client {
 		})
 	}
 
+	/// The error code returned when the requested data exists on chain but isn't indexed by the
+	/// frontier backend yet.
+	pub const NOT_YET_INDEXED_CODE: i32 = -32002;
+
+	/// Ensure the frontier backend has indexed the block with the given number.
+	///
+	/// Blocks beyond the best block of the chain don't exist, and are left to the caller to
+	/// report, usually as `null`. Blocks of the chain the backend has not indexed yet are
+	/// reported with an explicit error instead, so they aren't mistaken for missing data.
+	pub async fn ensure_indexed<B, C>(
+		client: &C,
+		backend: &dyn fc_api::Backend<B>,
+		number: NumberFor<B>,
+	) -> RpcResult<()>
+	where
+		B: BlockT,
+		C: HeaderBackend<B> + 'static,
+	{
+		if number > client.info().best_number {
+			return Ok(());
+		}
+		let best_indexed = backend
+			.best_indexed_block()
+			.await
+			.map_err(|err| internal_err(format!("fetch best indexed block failed: {:?}", err)))?;
+		if number > best_indexed {
+			return Err(err(
+				NOT_YET_INDEXED_CODE,
+				format!(
+					"historical data not yet indexed, node is syncing EVM mappings, best indexed: {}",
+					best_indexed
+				),
+				None,
+			));
+		}
+		Ok(())
+	}
+
 	pub async fn load_hash<B, C>(
 		client: &C,
 		backend: &dyn fc_api::Backend<B>,
@@ -256,11 +306,12 @@ pub mod frontier_backend_client {
 		false
 	}
 
+	/// Load the Ethereum block hash and index of a transaction, from the metadata entry of the
+	/// canonical block that includes it. Entries of non-canonical blocks are ignored.
 	pub async fn load_transactions<B, C>(
 		client: &C,
 		backend: &dyn fc_api::Backend<B>,
 		transaction_hash: H256,
-		only_canonical: bool,
 	) -> RpcResult<Option<(H256, u32)>>
 	where
 		B: BlockT,
@@ -271,22 +322,10 @@ pub mod frontier_backend_client {
 			.await
 			.map_err(|err| internal_err(format!("fetch aux store failed: {:?}", err)))?;
 
-		transaction_metadata
+		Ok(transaction_metadata
 			.iter()
 			.find(|meta| is_canon::<B, C>(client, meta.substrate_block_hash))
-			.map_or_else(
-				|| {
-					if !only_canonical && transaction_metadata.len() > 0 {
-						Ok(Some((
-							transaction_metadata[0].ethereum_block_hash,
-							transaction_metadata[0].ethereum_index,
-						)))
-					} else {
-						Ok(None)
-					}
-				},
-				|meta| Ok(Some((meta.ethereum_block_hash, meta.ethereum_index))),
-			)
+			.map(|meta| (meta.ethereum_block_hash, meta.ethereum_index)))
 	}
 }
 
@@ -320,6 +359,64 @@ pub fn internal_err_with_data<T: ToString>(
 	)
 }
 
+/// The error code returned when a response is larger than the configured limit.
+pub const RESPONSE_TOO_LARGE_CODE: i32 = -32005;
+
+/// Bytes of the response size limit kept for the JSON-RPC envelope around the result, that is
+/// `{"jsonrpc":"2.0","id":..,"result":..}` with a long request id.
+pub const RESPONSE_ENVELOPE_SIZE: usize = 128;
+
+/// Ensure the JSON encoding of `response`, in its envelope, fits in `max_response_size` bytes,
+/// when there is a limit.
+///
+/// The server rejects oversized responses with an opaque error once they are serialized, while
+/// this one tells the client to narrow its query. The encoding is only counted as it is
+/// produced and stops at the limit, so it is never buffered.
+pub fn ensure_response_size<T: serde::Serialize>(
+	response: &T,
+	max_response_size: Option<usize>,
+) -> jsonrpsee::core::RpcResult<()> {
+	let Some(max_response_size) = max_response_size else {
+		return Ok(());
+	};
+	let mut counter = ResponseSizeCounter {
+		size: RESPONSE_ENVELOPE_SIZE,
+		limit: max_response_size,
+	};
+	match serde_json::to_writer(&mut counter, response) {
+		Ok(()) if counter.size <= max_response_size => Ok(()),
+		Err(e) if !e.is_io() => Err(internal_err(format!("fail to serialize response: {}", e))),
+		_ => Err(err(
+			RESPONSE_TOO_LARGE_CODE,
+			format!(
+				"response too large, exceeds the limit of {} bytes, narrow your query",
+				max_response_size
+			),
+			None,
+		)),
+	}
+}
+
+/// Counts the bytes written to it, failing once they are over `limit`.
+struct ResponseSizeCounter {
+	size: usize,
+	limit: usize,
+}
+
+impl std::io::Write for ResponseSizeCounter {
+	fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+		self.size = self.size.saturating_add(buf.len());
+		if self.size > self.limit {
+			return Err(std::io::Error::other("response too large"));
+		}
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> std::io::Result<()> {
+		Ok(())
+	}
+}
+
 pub fn public_key(transaction: &EthereumTransaction) -> Result<[u8; 64], sp_io::EcdsaVerifyError> {
 	let mut sig = [0u8; 65];
 	let mut msg = [0u8; 32];
@@ -387,6 +484,41 @@ mod tests {
 		)?))
 	}
 
+	fn log(index: u64) -> fc_rpc_core::types::Log {
+		fc_rpc_core::types::Log {
+			address: ethereum_types::H160::repeat_byte(0x11),
+			topics: vec![ethereum_types::H256::repeat_byte(0x22)],
+			data: vec![0u8; 64].into(),
+			block_hash: Some(ethereum_types::H256::repeat_byte(0x33)),
+			block_number: Some(1.into()),
+			transaction_hash: Some(ethereum_types::H256::repeat_byte(0x44)),
+			transaction_index: Some(0.into()),
+			log_index: Some(index.into()),
+			transaction_log_index: Some(index.into()),
+			removed: false,
+		}
+	}
+
+	#[test]
+	fn large_log_queries_are_too_large() {
+		let logs: Vec<_> = (0..1_000).map(log).collect();
+		let size = jsonrpsee::core::to_json_raw_value(&logs)
+			.unwrap()
+			.get()
+			.len() + super::RESPONSE_ENVELOPE_SIZE;
+
+		// No limit.
+		assert!(super::ensure_response_size(&logs, None).is_ok());
+		// The response fits, with room for the envelope.
+		assert!(super::ensure_response_size(&logs, Some(size)).is_ok());
+		// The response is one byte too large.
+		let error = super::ensure_response_size(&logs, Some(size - 1)).unwrap_err();
+		assert_eq!(error.code(), super::RESPONSE_TOO_LARGE_CODE);
+		assert!(error.message().contains("narrow your query"));
+		// Smaller queries still fit.
+		assert!(super::ensure_response_size(&logs[..10].to_vec(), Some(size - 1)).is_ok());
+	}
+
 	#[test]
 	fn substrate_block_hash_one_to_many_works() {
 		let tmp = tempdir().expect("create a temporary directory");
@@ -503,4 +635,273 @@ mod tests {
 			b2_hash,
 		);
 	}
+
+	#[test]
+	fn transaction_lookup_follows_canonical_fork() {
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+
+		let client = Arc::new(client);
+
+		// Create a temporary frontier secondary DB.
+		let backend = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.into_path())
+			.expect("a temporary db was created");
+
+		// A transaction included in both forks, and one only included in the B1 fork.
+		let shared_transaction_hash = sp_core::H256::random();
+		let b1_transaction_hash = sp_core::H256::random();
+		let b1_ethereum_block_hash = sp_core::H256::random();
+		let b2_ethereum_block_hash = sp_core::H256::random();
+
+		let load_transactions = |transaction_hash| {
+			futures::executor::block_on(super::frontier_backend_client::load_transactions(
+				client.as_ref(),
+				backend.as_ref(),
+				transaction_hash,
+			))
+			.unwrap()
+		};
+
+		// G -> B1.
+		let chain = client.chain_info();
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(chain.best_hash)
+			.with_parent_block_number(chain.best_number)
+			.build()
+			.unwrap();
+		builder.push_storage_change(vec![1], None).unwrap();
+		let b1 = builder.build().unwrap().block;
+		let b1_hash = b1.header.hash();
+		executor::block_on(client.import(BlockOrigin::Own, b1)).unwrap();
+
+		let b1_commitment = fc_db::kv::MappingCommitment::<OpaqueBlock> {
+			block_hash: b1_hash,
+			ethereum_block_hash: b1_ethereum_block_hash,
+			ethereum_transaction_hashes: vec![shared_transaction_hash, b1_transaction_hash],
+		};
+		backend
+			.mapping()
+			.write_hashes(b1_commitment.clone())
+			.unwrap();
+
+		// G -> B2, including the shared transaction at another index.
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(chain.best_hash)
+			.with_parent_block_number(chain.best_number)
+			.build()
+			.unwrap();
+		builder.push_storage_change(vec![2], None).unwrap();
+		let b2 = builder.build().unwrap().block;
+		let b2_hash = b2.header.hash();
+		executor::block_on(client.import(BlockOrigin::Own, b2)).unwrap();
+
+		let b2_commitment = fc_db::kv::MappingCommitment::<OpaqueBlock> {
+			block_hash: b2_hash,
+			ethereum_block_hash: b2_ethereum_block_hash,
+			ethereum_transaction_hashes: vec![sp_core::H256::random(), shared_transaction_hash],
+		};
+		backend.mapping().write_hashes(b2_commitment).unwrap();
+
+		// B1 is canon.
+		assert_eq!(
+			load_transactions(shared_transaction_hash),
+			Some((b1_ethereum_block_hash, 0))
+		);
+		assert_eq!(
+			load_transactions(b1_transaction_hash),
+			Some((b1_ethereum_block_hash, 1))
+		);
+
+		// B2 -> C2. B2 branch is now canon.
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(b2_hash)
+			.fetch_parent_block_number(&*client)
+			.unwrap()
+			.build()
+			.unwrap();
+		builder.push_storage_change(vec![1], None).unwrap();
+		let c2 = builder.build().unwrap().block;
+		executor::block_on(client.import(BlockOrigin::Own, c2)).unwrap();
+
+		// The canonical entry is preferred, and entries of the dead fork are ignored.
+		assert_eq!(
+			load_transactions(shared_transaction_hash),
+			Some((b2_ethereum_block_hash, 1))
+		);
+		assert_eq!(load_transactions(b1_transaction_hash), None);
+
+		// Pruning the retracted block keeps the canonical entry.
+		backend
+			.mapping()
+			.prune_transaction_metadata(b1_commitment)
+			.unwrap();
+		assert_eq!(
+			load_transactions(shared_transaction_hash),
+			Some((b2_ethereum_block_hash, 1))
+		);
+		assert_eq!(
+			backend
+				.mapping()
+				.transaction_metadata(&shared_transaction_hash)
+				.unwrap()
+				.len(),
+			1
+		);
+		assert!(backend
+			.mapping()
+			.transaction_metadata(&b1_transaction_hash)
+			.unwrap()
+			.is_empty());
+	}
+
+	#[test]
+	fn safe_and_finalized_tags_resolve_to_the_finalized_block() {
+		use fc_rpc_core::types::BlockNumberOrHash;
+		use sc_client_api::Finalizer;
+		use sp_runtime::generic::BlockId;
+
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+
+		let client = Arc::new(client);
+
+		// Create a temporary frontier secondary DB.
+		let backend = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.into_path())
+			.expect("a temporary db was created");
+
+		for _ in 0..3 {
+			let chain = client.chain_info();
+			let block = BlockBuilderBuilder::new(&*client)
+				.on_parent_block(chain.best_hash)
+				.with_parent_block_number(chain.best_number)
+				.build()
+				.unwrap()
+				.build()
+				.unwrap()
+				.block;
+			executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		}
+		let finalized_hash = client.hash(2).unwrap().unwrap();
+		client.finalize_block(finalized_hash, None, true).unwrap();
+
+		let native_block_id = |number| {
+			executor::block_on(super::frontier_backend_client::native_block_id::<
+				OpaqueBlock,
+				_,
+			>(client.as_ref(), backend.as_ref(), Some(number)))
+			.unwrap()
+		};
+
+		for tag in [BlockNumberOrHash::Safe, BlockNumberOrHash::Finalized] {
+			assert_eq!(native_block_id(tag), Some(BlockId::Hash(finalized_hash)));
+		}
+		assert_eq!(
+			native_block_id(BlockNumberOrHash::Latest),
+			Some(BlockId::Hash(client.chain_info().best_hash))
+		);
+	}
+
+	#[test]
+	fn ensure_indexed_reports_blocks_beyond_the_indexed_range() {
+		use fc_api::Backend as _;
+
+		let tmp = tempdir().expect("create a temporary directory");
+		let (client, _) = TestClientBuilder::new()
+			.build_with_native_executor::<substrate_test_runtime_client::runtime::RuntimeApi, _>(
+			None,
+		);
+
+		let client = Arc::new(client);
+
+		// Create a temporary frontier secondary DB.
+		let backend = open_frontier_backend::<OpaqueBlock, _>(client.clone(), tmp.into_path())
+			.expect("a temporary db was created");
+
+		let import_blocks = |count: u64| {
+			for _ in 0..count {
+				let chain = client.chain_info();
+				let block = BlockBuilderBuilder::new(&*client)
+					.on_parent_block(chain.best_hash)
+					.with_parent_block_number(chain.best_number)
+					.build()
+					.unwrap()
+					.build()
+					.unwrap()
+					.block;
+				executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+			}
+		};
+		// Map the blocks in the order of the mapping-sync worker, which starts from the best
+		// block and walks back its ancestors, and extends the indexed head after each round.
+		let map_block = |number: u64| {
+			let hash = client.hash(number).unwrap().unwrap();
+			backend.mapping().write_none(hash).unwrap();
+			backend.extend_indexed_head(0, usize::MAX).unwrap();
+		};
+
+		// Import 10 blocks, and map them all: nothing is indexed until genesis is mapped.
+		import_blocks(10);
+		for number in (1..=10).rev() {
+			map_block(number);
+			assert_eq!(executor::block_on(backend.latest_indexed_block()), Ok(None));
+		}
+		map_block(0);
+		assert_eq!(executor::block_on(backend.best_indexed_block()), Ok(10));
+
+		// Import 10 more blocks, and map them down to block 12: the backend lags 10 blocks
+		// behind the chain head until the mapped blocks connect with the indexed ones.
+		import_blocks(10);
+		for number in (12..=20).rev() {
+			map_block(number);
+		}
+		assert_eq!(client.chain_info().best_number, 20);
+		assert_eq!(executor::block_on(backend.best_indexed_block()), Ok(10));
+		assert_eq!(
+			executor::block_on(backend.latest_indexed_block()),
+			Ok(Some((10, client.hash(10).unwrap().unwrap())))
+		);
+
+		let ensure_indexed = |number| {
+			executor::block_on(super::frontier_backend_client::ensure_indexed(
+				client.as_ref(),
+				backend.as_ref(),
+				number,
+			))
+		};
+
+		// Indexed blocks are available.
+		assert!(ensure_indexed(0).is_ok());
+		assert!(ensure_indexed(10).is_ok());
+
+		// Blocks of the chain above the indexed head are reported, mapped or not.
+		for number in [11, 20] {
+			let err = ensure_indexed(number).unwrap_err();
+			assert_eq!(
+				err.code(),
+				super::frontier_backend_client::NOT_YET_INDEXED_CODE
+			);
+			assert_eq!(
+				err.message(),
+				"historical data not yet indexed, node is syncing EVM mappings, best indexed: 10"
+			);
+		}
+
+		// Blocks beyond the chain head don't exist, and are left to the caller.
+		assert!(ensure_indexed(21).is_ok());
+
+		// Mapping the remaining block catches the backend up.
+		map_block(11);
+		assert_eq!(executor::block_on(backend.best_indexed_block()), Ok(20));
+		assert_eq!(
+			executor::block_on(backend.latest_indexed_block()),
+			Ok(Some((20, client.chain_info().best_hash)))
+		);
+		assert!(ensure_indexed(20).is_ok());
+	}
 }
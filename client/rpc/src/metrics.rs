@@ -0,0 +1,171 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use futures::{stream, StreamExt};
+use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64};
+// Substrate
+use sc_client_api::client::BlockchainEvents;
+use sc_transaction_pool_api::TransactionPool;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, UniqueSaturatedInto};
+
+/// Prometheus metrics of the Ethereum RPC. Does nothing when built without a registry.
+///
+/// A single instance is shared by the RPC handlers, since metrics can only be registered once.
+/// The requests themselves are recorded by the `substrate_rpc_calls_*` metrics of the RPC
+/// server started by `sc-service`, whose middleware can't be extended by the node.
+#[derive(Clone, Default)]
+pub struct RpcMetrics(Option<Arc<Metrics>>);
+
+struct Metrics {
+	subscriptions: GaugeVec<U64>,
+	pending_transactions: Gauge<U64>,
+	block_height: Gauge<U64>,
+}
+
+impl RpcMetrics {
+	pub fn new(prometheus_registry: Option<Registry>) -> Self {
+		let metrics = match prometheus_registry {
+			Some(registry) => match Metrics::register(&registry) {
+				Ok(metrics) => Some(Arc::new(metrics)),
+				Err(e) => {
+					log::error!(target: "rpc", "Failed to register metrics: {:?}", e);
+					None
+				}
+			},
+			None => None,
+		};
+		Self(metrics)
+	}
+
+	/// Counts a subscription of the given type as active until the returned guard is dropped.
+	pub(crate) fn track_subscription(&self, kind: &'static str) -> SubscriptionGuard {
+		if let Some(metrics) = &self.0 {
+			metrics.subscriptions.with_label_values(&[kind]).inc();
+		}
+		SubscriptionGuard {
+			metrics: self.clone(),
+			kind,
+		}
+	}
+
+	/// Keeps the block height and pending transactions metrics up to date, on every block import
+	/// and pool change.
+	pub async fn chain_metrics_task<B, C, P>(self, client: Arc<C>, pool: Arc<P>)
+	where
+		B: BlockT,
+		C: BlockchainEvents<B> + HeaderBackend<B> + 'static,
+		P: TransactionPool<Block = B, Hash = B::Hash> + 'static,
+	{
+		let Some(metrics) = self.0 else {
+			return;
+		};
+
+		let mut notification_st = stream::select(
+			client.import_notification_stream().map(|_| ()),
+			pool.import_notification_stream().map(|_| ()),
+		);
+
+		while notification_st.next().await.is_some() {
+			let best_number: u64 = client.info().best_number.unique_saturated_into();
+			metrics.block_height.set(best_number);
+			let status = pool.status();
+			metrics
+				.pending_transactions
+				.set((status.ready + status.future) as u64);
+		}
+	}
+}
+
+/// Marks the end of a subscription when dropped.
+pub(crate) struct SubscriptionGuard {
+	metrics: RpcMetrics,
+	kind: &'static str,
+}
+
+impl Drop for SubscriptionGuard {
+	fn drop(&mut self) {
+		if let Some(metrics) = &self.metrics.0 {
+			metrics.subscriptions.with_label_values(&[self.kind]).dec();
+		}
+	}
+}
+
+impl Metrics {
+	fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			subscriptions: register(
+				GaugeVec::new(
+					Opts::new(
+						"eth_active_subscriptions",
+						"Active Ethereum RPC subscriptions.",
+					),
+					&["type"],
+				)?,
+				registry,
+			)?,
+			pending_transactions: register(
+				Gauge::new(
+					"eth_pending_transactions",
+					"Transactions in the pool, ready or not.",
+				)?,
+				registry,
+			)?,
+			block_height: register(
+				Gauge::new("eth_block_height", "Number of the best block.")?,
+				registry,
+			)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn subscriptions_are_active_until_dropped() {
+		let metrics = RpcMetrics::new(Some(Registry::new()));
+		let gauge = || {
+			metrics
+				.0
+				.as_ref()
+				.unwrap()
+				.subscriptions
+				.with_label_values(&["newHeads"])
+				.get()
+		};
+
+		let first = metrics.track_subscription("newHeads");
+		let second = metrics.track_subscription("newHeads");
+		assert_eq!(gauge(), 2);
+		drop(first);
+		assert_eq!(gauge(), 1);
+		drop(second);
+		assert_eq!(gauge(), 0);
+	}
+
+	#[test]
+	fn disabled_without_registry() {
+		let metrics = RpcMetrics::new(None);
+		assert!(metrics.0.is_none());
+		drop(metrics.track_subscription("logs"));
+	}
+}
@@ -0,0 +1,342 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use futures::{stream, StreamExt};
+use scale_codec::Encode;
+// Substrate
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	client::BlockchainEvents,
+};
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{traits::Block as BlockT, transaction_validity::TransactionPriority};
+// Frontier
+use fp_rpc::EthereumRuntimeRPCApi;
+
+use crate::cache::EthTask;
+
+/// Default bound of the memory used by the transactions of the pool, in bytes.
+pub const DEFAULT_MAX_POOL_TOTAL_MEMORY: usize = 256 * 1024 * 1024;
+
+impl<B, C, BE> EthTask<B, C, BE>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EthereumRuntimeRPCApi<B>,
+	C: BlockchainEvents<B> + 'static,
+	C: HeaderBackend<B> + StorageProvider<B, BE>,
+	BE: Backend<B> + 'static,
+{
+	/// Keeps the encoded size of the pooled transactions under `max_total_memory` bytes, by
+	/// evicting the lowest priority ones whenever the pool or the chain changes.
+	///
+	/// The transactions of the future queue are evicted before the ready ones of the same
+	/// priority. Evicted transactions are reported as invalid, so the pool removes them along with
+	/// the ones depending on them, and bans them for its ban time (`--tx-ban-seconds`) so that
+	/// they are not submitted again right away.
+	pub async fn pool_memory_limit_task<P>(
+		client: Arc<C>,
+		pool: Arc<P>,
+		max_total_memory: usize,
+		prometheus_registry: Option<prometheus_endpoint::Registry>,
+	) where
+		P: TransactionPool<Block = B, Hash = B::Hash> + 'static,
+	{
+		let metrics = match prometheus_registry {
+			Some(registry) => match PoolMemoryMetrics::register(&registry) {
+				Ok(metrics) => Some(metrics),
+				Err(e) => {
+					log::error!(target: "txpool", "Failed to register metrics: {:?}", e);
+					None
+				}
+			},
+			None => None,
+		};
+
+		let mut notification_st = stream::select(
+			client.import_notification_stream().map(|_| None),
+			pool.import_notification_stream().map(Some),
+		);
+		// The pool is only scanned when it goes over the limit, and the transactions imported
+		// afterwards are added to the queue as they come.
+		let mut queue = None;
+
+		while let Some(imported) = notification_st.next().await {
+			let mut used = pool_memory(&*pool);
+			if used <= max_total_memory {
+				queue = None;
+				if let Some(metrics) = &metrics {
+					metrics.memory.set(used as u64);
+				}
+				continue;
+			}
+
+			let scanned = queue.is_none();
+			let queue = queue.get_or_insert_with(|| EvictionQueue::scan(&*pool));
+			if !scanned {
+				if let Some(tx) = imported.and_then(|hash| pool.ready_transaction(&hash)) {
+					queue.push(
+						*tx.hash(),
+						*tx.priority(),
+						tx.data().as_ref().encoded_size(),
+					);
+				}
+			}
+
+			let (evicted, used) = evict(&*pool, queue, used, max_total_memory).await;
+			log::debug!(
+				target: "txpool",
+				"Evicted {} transactions, pool uses {} bytes out of {}",
+				evicted,
+				used,
+				max_total_memory,
+			);
+
+			if let Some(metrics) = &metrics {
+				metrics.memory.set(used as u64);
+				metrics.evictions.inc_by(evicted as u64);
+			}
+		}
+	}
+}
+
+/// Evict the transactions of `queue` until `pool`, using `used` bytes, uses at most
+/// `max_total_memory` bytes. Returns the number of evicted transactions and the bytes then used.
+async fn evict<P: TransactionPool>(
+	pool: &P,
+	queue: &mut EvictionQueue<P::Hash>,
+	mut used: usize,
+	max_total_memory: usize,
+) -> (usize, usize) {
+	let mut evicted = 0;
+	while used > max_total_memory && !queue.is_empty() {
+		let evictions = queue
+			.pop_evictions(used, max_total_memory)
+			.into_iter()
+			.map(|hash| (hash, None))
+			.collect();
+		evicted += pool.report_invalid(None, evictions).await.len();
+		used = pool_memory(pool);
+	}
+	(evicted, used)
+}
+
+/// The encoded size of the pooled transactions, in bytes.
+fn pool_memory<P: TransactionPool>(pool: &P) -> usize {
+	let status = pool.status();
+	status.ready_bytes + status.future_bytes
+}
+
+/// The pooled transactions to evict, lowest priority first. Transactions of the same priority are
+/// evicted in the order they were added.
+struct EvictionQueue<Hash> {
+	transactions: BTreeMap<(TransactionPriority, u64), (Hash, usize)>,
+	next_index: u64,
+}
+
+impl<Hash> EvictionQueue<Hash> {
+	fn new() -> Self {
+		Self {
+			transactions: BTreeMap::new(),
+			next_index: 0,
+		}
+	}
+
+	/// A queue of the transactions of `pool`, future ones first.
+	fn scan<P>(pool: &P) -> Self
+	where
+		P: TransactionPool<Hash = Hash>,
+		Hash: Clone,
+	{
+		let mut queue = Self::new();
+		for tx in pool.futures() {
+			queue.push(
+				tx.hash().clone(),
+				*tx.priority(),
+				tx.data().as_ref().encoded_size(),
+			);
+		}
+		for tx in pool.ready() {
+			queue.push(
+				tx.hash().clone(),
+				*tx.priority(),
+				tx.data().as_ref().encoded_size(),
+			);
+		}
+		queue
+	}
+
+	fn push(&mut self, hash: Hash, priority: TransactionPriority, size: usize) {
+		self.transactions
+			.insert((priority, self.next_index), (hash, size));
+		self.next_index += 1;
+	}
+
+	fn is_empty(&self) -> bool {
+		self.transactions.is_empty()
+	}
+
+	/// Pop the transactions to evict so that a pool using `used` bytes uses at most `limit`.
+	fn pop_evictions(&mut self, mut used: usize, limit: usize) -> Vec<Hash> {
+		let mut evictions = Vec::new();
+		while used > limit {
+			let Some((_, (hash, size))) = self.transactions.pop_first() else {
+				break;
+			};
+			used = used.saturating_sub(size);
+			evictions.push(hash);
+		}
+		evictions
+	}
+}
+
+struct PoolMemoryMetrics {
+	memory: prometheus_endpoint::Gauge<prometheus_endpoint::U64>,
+	evictions: prometheus::IntCounter,
+}
+
+impl PoolMemoryMetrics {
+	fn register(
+		registry: &prometheus_endpoint::Registry,
+	) -> Result<Self, prometheus_endpoint::PrometheusError> {
+		Ok(Self {
+			memory: prometheus_endpoint::register(
+				prometheus_endpoint::Gauge::new(
+					"frontier_pool_memory_bytes",
+					"Encoded size of the transactions in the pool.",
+				)?,
+				registry,
+			)?,
+			evictions: prometheus_endpoint::register(
+				prometheus::IntCounter::new(
+					"frontier_pool_evictions_total",
+					"Transactions evicted from the pool to bound its memory usage.",
+				)?,
+				registry,
+			)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use sc_transaction_pool_api::{error::IntoPoolError, TransactionSource};
+	use substrate_test_runtime_client::{
+		runtime::{Extrinsic, Transfer},
+		DefaultTestClientBuilderExt, Sr25519Keyring, TestClientBuilder, TestClientBuilderExt,
+	};
+
+	use super::*;
+
+	fn queue(transactions: &[(u32, TransactionPriority, usize)]) -> EvictionQueue<u32> {
+		let mut queue = EvictionQueue::new();
+		for (hash, priority, size) in transactions {
+			queue.push(*hash, *priority, *size);
+		}
+		queue
+	}
+
+	#[test]
+	fn nothing_is_evicted_under_the_limit() {
+		let mut queue = queue(&[(1, 10, 100), (2, 20, 100)]);
+		assert!(queue.pop_evictions(200, 200).is_empty());
+		assert!(!queue.is_empty());
+	}
+
+	#[test]
+	fn lowest_priority_transactions_are_evicted_first() {
+		let mut queue = queue(&[(1, 30, 100), (2, 10, 100), (3, 20, 100), (4, 40, 100)]);
+		assert_eq!(queue.pop_evictions(400, 250), vec![2, 3]);
+		assert_eq!(queue.pop_evictions(200, 0), vec![1, 4]);
+		assert!(queue.is_empty());
+	}
+
+	#[test]
+	fn ties_are_evicted_in_order() {
+		// Future transactions come first.
+		let mut queue = queue(&[(1, 10, 100), (2, 10, 100), (3, 10, 100)]);
+		assert_eq!(queue.pop_evictions(300, 200), vec![1]);
+	}
+
+	#[test]
+	fn transactions_added_later_are_evicted_by_priority() {
+		let mut queue = queue(&[(1, 20, 100), (2, 30, 100)]);
+		queue.push(3, 10, 100);
+		queue.push(4, 20, 100);
+		assert_eq!(queue.pop_evictions(400, 100), vec![3, 1, 4]);
+	}
+
+	fn transfer(from: Sr25519Keyring) -> Extrinsic {
+		Transfer {
+			from: from.into(),
+			to: Sr25519Keyring::Charlie.into(),
+			amount: 1,
+			nonce: 0,
+		}
+		.into_unchecked_extrinsic()
+	}
+
+	#[tokio::test]
+	async fn evicted_transactions_are_banned_by_the_pool() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let pool = sc_transaction_pool::Builder::new(
+			sp_core::testing::TaskExecutor::new(),
+			client.clone(),
+			true.into(),
+		)
+		.build();
+		let best_hash = client.info().best_hash;
+
+		let (first, second) = (
+			transfer(Sr25519Keyring::Alice),
+			transfer(Sr25519Keyring::Bob),
+		);
+		for xt in [first.clone(), second.clone()] {
+			pool.submit_one(best_hash, TransactionSource::External, xt)
+				.await
+				.expect("the transaction is valid");
+		}
+		let used = pool_memory(&pool);
+
+		// Only one transaction needs to go to get under the limit.
+		let mut queue = EvictionQueue::scan(&pool);
+		let (evicted, remaining) = evict(&pool, &mut queue, used, used - 1).await;
+		assert_eq!(evicted, 1);
+		assert_eq!(pool.status().ready, 1);
+		let kept = pool.ready().next().expect("a transaction is kept");
+		let evicted_xt = if kept.data().as_ref() == &first {
+			second
+		} else {
+			first
+		};
+		assert_eq!(remaining, used - evicted_xt.encoded_size());
+
+		let err = pool
+			.submit_one(best_hash, TransactionSource::External, evicted_xt)
+			.await
+			.expect_err("the evicted transaction is banned");
+		assert!(matches!(
+			err.into_pool_error(),
+			Ok(sc_transaction_pool_api::error::Error::TemporarilyBanned)
+		));
+	}
+}
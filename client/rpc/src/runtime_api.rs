@@ -0,0 +1,259 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Resolution of the `EthereumRuntimeRPCApi` version exposed at a given block.
+//!
+//! While a chain goes through a runtime upgrade, blocks before and after the upgrade expose
+//! different versions of the runtime API, and every runtime call must use the parameter layout of
+//! the version exposed at the block it is executed on. The `Eth` and `EthPubSub` handlers resolve
+//! that version with `EthereumRuntimeApiVersions`, which caches it for each block, and read
+//! blocks and filter extrinsics through the shims of this module, which convert the legacy
+//! layouts. The calls of `eth_call` and `eth_estimateGas` are dispatched by version in
+//! `eth::execute`, where their parameters are encoded.
+
+use std::sync::{Arc, Mutex};
+
+use ethereum::{BlockV3 as EthereumBlock, TransactionV3 as EthereumTransaction};
+use jsonrpsee::core::RpcResult;
+use schnellru::{ByLength, LruMap};
+// Substrate
+use sp_api::{ApiError, ApiExt};
+use sp_runtime::traits::Block as BlockT;
+// Frontier
+use fp_rpc::EthereumRuntimeRPCApi;
+
+use crate::internal_err;
+
+/// The latest `EthereumRuntimeRPCApi` version this client knows how to call.
+pub const MAX_SUPPORTED_ETHEREUM_RUNTIME_API_VERSION: u32 = 6;
+
+/// The number of blocks whose `EthereumRuntimeRPCApi` version is kept.
+const VERSION_CACHE_SIZE: u32 = 1024;
+
+/// The `EthereumRuntimeRPCApi` versions exposed by the runtime at the recently queried blocks.
+pub struct EthereumRuntimeApiVersions<B: BlockT> {
+	versions: Arc<Mutex<LruMap<B::Hash, u32>>>,
+}
+
+impl<B: BlockT> Clone for EthereumRuntimeApiVersions<B> {
+	fn clone(&self) -> Self {
+		Self {
+			versions: self.versions.clone(),
+		}
+	}
+}
+
+impl<B: BlockT> Default for EthereumRuntimeApiVersions<B> {
+	fn default() -> Self {
+		Self {
+			versions: Arc::new(Mutex::new(LruMap::new(ByLength::new(VERSION_CACHE_SIZE)))),
+		}
+	}
+}
+
+impl<B: BlockT> EthereumRuntimeApiVersions<B> {
+	/// Get the `EthereumRuntimeRPCApi` version exposed by the runtime at the given block.
+	///
+	/// Fails with a clear error if the runtime does not expose the API, or exposes a version newer
+	/// than the ones supported by this client.
+	pub fn version<Api>(&self, api: &Api, hash: B::Hash) -> RpcResult<u32>
+	where
+		Api: ApiExt<B>,
+	{
+		self.version_with(hash, || {
+			api.api_version::<dyn EthereumRuntimeRPCApi<B>>(hash)
+		})
+	}
+
+	fn version_with(
+		&self,
+		hash: B::Hash,
+		query: impl FnOnce() -> Result<Option<u32>, ApiError>,
+	) -> RpcResult<u32> {
+		if let Ok(versions) = &mut self.versions.lock() {
+			if let Some(version) = versions.get(&hash) {
+				return Ok(*version);
+			}
+		}
+		let version = query().map_err(|err| {
+			internal_err(format!("failed to retrieve Runtime Api version: {err}"))
+		})?;
+		let version = check_ethereum_runtime_api_version(version)?;
+		if let Ok(versions) = &mut self.versions.lock() {
+			versions.insert(hash, version);
+		}
+		Ok(version)
+	}
+}
+
+fn check_ethereum_runtime_api_version(version: Option<u32>) -> RpcResult<u32> {
+	match version {
+		None => Err(internal_err(
+			"EthereumRuntimeRPCApi is not available in the runtime",
+		)),
+		Some(version) if version > MAX_SUPPORTED_ETHEREUM_RUNTIME_API_VERSION => {
+			Err(internal_err(format!(
+				"runtime too new for this client: EthereumRuntimeRPCApi version {version} is not \
+				 supported, the latest supported version is \
+				 {MAX_SUPPORTED_ETHEREUM_RUNTIME_API_VERSION}"
+			)))
+		}
+		Some(version) => Ok(version),
+	}
+}
+
+/// `EthereumRuntimeRPCApi::current_block` for the given API version.
+pub fn current_block<B, Api>(
+	api: &Api,
+	hash: B::Hash,
+	version: u32,
+) -> Result<Option<EthereumBlock>, ApiError>
+where
+	B: BlockT,
+	Api: EthereumRuntimeRPCApi<B>,
+{
+	if version > 1 {
+		api.current_block(hash)
+	} else {
+		#[allow(deprecated)]
+		let legacy_block = api.current_block_before_version_2(hash)?;
+		Ok(legacy_block.map(|block| block.into()))
+	}
+}
+
+/// `EthereumRuntimeRPCApi::extrinsic_filter` for the given API version.
+pub fn extrinsic_filter<B, Api>(
+	api: &Api,
+	hash: B::Hash,
+	version: u32,
+	xts: Vec<B::Extrinsic>,
+) -> Result<Vec<EthereumTransaction>, ApiError>
+where
+	B: BlockT,
+	Api: EthereumRuntimeRPCApi<B>,
+{
+	if version > 1 {
+		api.extrinsic_filter(hash, xts)
+	} else {
+		#[allow(deprecated)]
+		let legacy = api.extrinsic_filter_before_version_2(hash, xts)?;
+		Ok(legacy.into_iter().map(|tx| tx.into()).collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use std::{cell::RefCell, collections::BTreeMap};
+	// Substrate
+	use sp_core::H256;
+	use sp_runtime::{
+		generic::{Block, Header},
+		traits::BlakeTwo256,
+		OpaqueExtrinsic,
+	};
+
+	type OpaqueBlock = Block<Header<u64, BlakeTwo256>, OpaqueExtrinsic>;
+
+	/// A runtime exposing a version of the API at each block, counting the version queries.
+	struct MockRuntime {
+		versions: BTreeMap<H256, Option<u32>>,
+		queries: RefCell<Vec<H256>>,
+	}
+
+	impl MockRuntime {
+		fn new(versions: impl IntoIterator<Item = (H256, Option<u32>)>) -> Self {
+			Self {
+				versions: versions.into_iter().collect(),
+				queries: RefCell::new(Vec::new()),
+			}
+		}
+
+		fn version(
+			&self,
+			versions: &EthereumRuntimeApiVersions<OpaqueBlock>,
+			hash: H256,
+		) -> RpcResult<u32> {
+			versions.version_with(hash, || {
+				self.queries.borrow_mut().push(hash);
+				Ok(self.versions.get(&hash).copied().flatten())
+			})
+		}
+	}
+
+	#[test]
+	fn versions_are_resolved_per_block() {
+		let before_upgrade = H256::repeat_byte(0x01);
+		let after_upgrade = H256::repeat_byte(0x02);
+		let runtime = MockRuntime::new([(before_upgrade, Some(5)), (after_upgrade, Some(6))]);
+		let versions = EthereumRuntimeApiVersions::default();
+
+		assert_eq!(runtime.version(&versions, before_upgrade).ok(), Some(5));
+		assert_eq!(runtime.version(&versions, after_upgrade).ok(), Some(6));
+		assert_eq!(runtime.version(&versions, before_upgrade).ok(), Some(5));
+		assert_eq!(runtime.version(&versions, after_upgrade).ok(), Some(6));
+		// Each block is only queried once.
+		assert_eq!(
+			*runtime.queries.borrow(),
+			vec![before_upgrade, after_upgrade]
+		);
+	}
+
+	#[test]
+	fn unsupported_versions_are_not_cached() {
+		let hash = H256::repeat_byte(0x01);
+		let runtime =
+			MockRuntime::new([(hash, Some(MAX_SUPPORTED_ETHEREUM_RUNTIME_API_VERSION + 1))]);
+		let versions = EthereumRuntimeApiVersions::default();
+
+		for _ in 0..2 {
+			let err = runtime.version(&versions, hash).unwrap_err();
+			assert!(err.message().starts_with("runtime too new for this client"));
+		}
+		assert_eq!(runtime.queries.borrow().len(), 2);
+	}
+
+	#[test]
+	fn supported_versions_are_accepted() {
+		for version in 1..=MAX_SUPPORTED_ETHEREUM_RUNTIME_API_VERSION {
+			assert_eq!(
+				check_ethereum_runtime_api_version(Some(version)).ok(),
+				Some(version)
+			);
+		}
+	}
+
+	#[test]
+	fn missing_api_is_rejected() {
+		let err = check_ethereum_runtime_api_version(None).unwrap_err();
+		assert_eq!(
+			err.message(),
+			"EthereumRuntimeRPCApi is not available in the runtime"
+		);
+	}
+
+	#[test]
+	fn newer_versions_are_rejected() {
+		let err = check_ethereum_runtime_api_version(Some(
+			MAX_SUPPORTED_ETHEREUM_RUNTIME_API_VERSION + 1,
+		))
+		.unwrap_err();
+		assert!(err.message().starts_with("runtime too new for this client"));
+	}
+}
@@ -36,8 +36,14 @@ pub trait EthSigner: Send + Sync {
 		message: TransactionMessage,
 		address: &H160,
 	) -> Result<EthereumTransaction, ErrorObjectOwned>;
+	/// Sign an EIP-191 personal message using the given account, returning the signature as
+	/// `r || s || v`.
+	fn sign_message(&self, _message: &[u8], _address: &H160) -> Result<[u8; 65], ErrorObjectOwned> {
+		Err(internal_err("signer cannot sign messages"))
+	}
 }
 
+/// A signer holding its secret keys in memory. For development and testing only.
 pub struct EthDevSigner {
 	keys: Vec<libsecp256k1::SecretKey>,
 }
@@ -53,6 +59,25 @@ impl EthDevSigner {
 			.expect("Test key is valid; qed")],
 		}
 	}
+
+	/// A signer of the accounts of the given secret keys.
+	pub fn with_keys(secrets: &[H256]) -> Result<Self, String> {
+		let keys = secrets
+			.iter()
+			.map(|secret| {
+				libsecp256k1::SecretKey::parse(&secret.0)
+					.map_err(|_| format!("invalid dev signer key {:?}", secret))
+			})
+			.collect::<Result<_, _>>()?;
+		Ok(Self { keys })
+	}
+}
+
+/// The hash signed by `eth_sign`: the message prefixed as an EIP-191 personal message.
+pub fn personal_message_hash(message: &[u8]) -> H256 {
+	let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	prefixed.extend_from_slice(message);
+	H256::from(keccak_256(&prefixed))
 }
 
 fn secret_key_address(secret: &libsecp256k1::SecretKey) -> H160 {
@@ -192,4 +217,95 @@ impl EthSigner for EthDevSigner {
 
 		transaction.ok_or_else(|| internal_err("signer not available"))
 	}
+
+	fn sign_message(&self, message: &[u8], address: &H160) -> Result<[u8; 65], ErrorObjectOwned> {
+		let secret = self
+			.keys
+			.iter()
+			.find(|secret| &secret_key_address(secret) == address)
+			.ok_or_else(|| internal_err("signer not available"))?;
+
+		let signing_message = libsecp256k1::Message::parse(&personal_message_hash(message).0);
+		let (signature, recid) = libsecp256k1::sign(&signing_message, secret);
+		let mut res = [0u8; 65];
+		res[0..64].copy_from_slice(&signature.serialize());
+		res[64] = 27 + recid.serialize();
+		Ok(res)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::{EnvelopedDecodable, EnvelopedEncodable, TransactionAction};
+	use ethereum_types::U256;
+
+	fn recover(signature: &[u8; 65], hash: H256) -> H160 {
+		let public =
+			sp_io::crypto::secp256k1_ecdsa_recover(signature, &hash.0).expect("signature is valid");
+		H160::from(H256::from(keccak_256(&public)))
+	}
+
+	#[test]
+	fn personal_message_hash_follows_eip191() {
+		assert_eq!(
+			personal_message_hash(b"Hello World"),
+			"0xa1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+				.parse::<H256>()
+				.unwrap()
+		);
+	}
+
+	#[test]
+	fn signed_message_recovers_to_the_account() {
+		let signer = EthDevSigner::with_keys(&[
+			"0x99b3c12287537e38c90a9219d4cb074a89a16e9cdb20bf85728ebd97c343e342"
+				.parse::<H256>()
+				.unwrap(),
+		])
+		.unwrap();
+		let address = "0x6be02d1d3665660d22ff9624b7be0551ee1ac91b"
+			.parse::<H160>()
+			.unwrap();
+		assert_eq!(signer.accounts(), vec![address]);
+
+		let signature = signer.sign_message(b"Hello World", &address).unwrap();
+		assert!(signature[64] == 27 || signature[64] == 28);
+		assert_eq!(
+			recover(&signature, personal_message_hash(b"Hello World")),
+			address
+		);
+
+		assert!(signer
+			.sign_message(b"Hello World", &H160::repeat_byte(0x01))
+			.is_err());
+	}
+
+	#[test]
+	fn signed_transaction_recovers_to_the_account() {
+		let signer = EthDevSigner::new();
+		let address = signer.accounts()[0];
+		let message = TransactionMessage::Legacy(ethereum::LegacyTransactionMessage {
+			nonce: U256::zero(),
+			gas_price: U256::from(1_000_000_000u64),
+			gas_limit: U256::from(21_000u64),
+			action: TransactionAction::Call(H160::repeat_byte(0x02)),
+			value: U256::from(1u64),
+			input: vec![],
+			chain_id: Some(42),
+		});
+
+		let transaction = signer.sign(message, &address).unwrap();
+		let public = crate::public_key(&transaction).unwrap();
+		assert_eq!(H160::from(H256::from(keccak_256(&public))), address);
+
+		// The encoding is the one accepted by `eth_sendRawTransaction`.
+		let encoded = transaction.encode();
+		assert_eq!(EthereumTransaction::decode(&encoded).unwrap(), transaction);
+	}
+
+	#[test]
+	fn invalid_keys_are_refused() {
+		assert!(EthDevSigner::with_keys(&[H256::zero()]).is_err());
+	}
 }
@@ -0,0 +1,294 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the `transact` extrinsic.
+//!
+//! The transactions aren't signed: `transact` trusts the sender given by its origin, which is
+//! only checked by the self-contained extrinsic validation.
+
+use alloc::{vec, vec::Vec};
+use ethereum::{
+	eip2930::TransactionSignature as EIP2930TransactionSignature,
+	legacy::TransactionSignature as LegacyTransactionSignature,
+};
+// Substrate
+use frame_benchmarking::v2::*;
+use frame_support::traits::Currency;
+// Frontier
+use pallet_evm::AddressMapping;
+
+use super::*;
+
+/// The gas limit of the benchmarked transactions.
+const GAS_LIMIT: u64 = 1_000_000;
+
+/// Creation code of a contract whose runtime code always reverts.
+const REVERTER_INIT_CODE: [u8; 17] = hex_literal::hex!("6005600c60003960056000f360006000fd");
+
+/// Runtime code which always reverts.
+const REVERTER_CODE: [u8; 5] = hex_literal::hex!("60006000fd");
+
+/// Input of the `ECRecover` precompile, with a valid signature.
+const ECRECOVER_INPUT: [u8; 128] = hex_literal::hex!(
+	"38d18acb67d25c8bb9942764b62f18e17054f66a817bd4295423adf9ed98873e"
+	"000000000000000000000000000000000000000000000000000000000000001b"
+	"38d18acb67d25c8bb9942764b62f18e17054f66a817bd4295423adf9ed98873e"
+	"789d1dd423d25f0772d2748d60f7e4b81bb14d086eba8e8e8efb6dcff8a4ae02"
+);
+
+/// Runtime specific parameters of the benchmarks.
+pub trait BenchmarkHelper {
+	/// The sender of the benchmarked transactions. It must be allowed to deploy contracts.
+	fn source() -> H160 {
+		H160::repeat_byte(0x42)
+	}
+
+	/// The address of a precompile of the runtime, and an input to call it with. Defaults to the
+	/// `ECRecover` precompile at address `0x01`.
+	fn precompile_call() -> (H160, Vec<u8>) {
+		(H160::from_low_u64_be(1), ECRECOVER_INPUT.to_vec())
+	}
+}
+
+impl BenchmarkHelper for () {}
+
+/// The benchmark sender, funded to pay for the transactions.
+fn funded_source<T: Config>() -> H160 {
+	let source = T::BenchmarkHelper::source();
+	let account_id = <T as pallet_evm::Config>::AddressMapping::into_account_id(source);
+	<T as pallet_evm::Config>::Currency::make_free_balance_be(
+		&account_id,
+		1_000_000_000_000_000_000_000_000u128.saturated_into(),
+	);
+	source
+}
+
+fn nonce<T: Config>(source: H160) -> U256 {
+	pallet_evm::Pallet::<T>::account_basic(&source).0.nonce
+}
+
+fn gas_price<T: Config>() -> U256 {
+	let (min_gas_price, _) = <T as pallet_evm::Config>::FeeCalculator::min_gas_price();
+	min_gas_price
+}
+
+fn chain_id<T: Config>() -> u64 {
+	<T as pallet_evm::Config>::ChainId::get()
+}
+
+fn legacy_transaction<T: Config>(
+	source: H160,
+	action: TransactionAction,
+	value: U256,
+	input: Vec<u8>,
+) -> Transaction {
+	Transaction::Legacy(ethereum::LegacyTransaction {
+		nonce: nonce::<T>(source),
+		gas_price: gas_price::<T>(),
+		gas_limit: GAS_LIMIT.into(),
+		action,
+		value,
+		input,
+		signature: LegacyTransactionSignature::new(
+			chain_id::<T>() * 2 + 35,
+			H256::repeat_byte(1),
+			H256::repeat_byte(1),
+		)
+		.expect("the signature is well-formed"),
+	})
+}
+
+fn eip2930_transaction<T: Config>(
+	source: H160,
+	action: TransactionAction,
+	value: U256,
+	input: Vec<u8>,
+	access_list: Vec<AccessListItem>,
+) -> Transaction {
+	Transaction::EIP2930(ethereum::EIP2930Transaction {
+		chain_id: chain_id::<T>(),
+		nonce: nonce::<T>(source),
+		gas_price: gas_price::<T>(),
+		gas_limit: GAS_LIMIT.into(),
+		action,
+		value,
+		input,
+		access_list,
+		signature: EIP2930TransactionSignature::new(
+			false,
+			H256::repeat_byte(1),
+			H256::repeat_byte(1),
+		)
+		.expect("the signature is well-formed"),
+	})
+}
+
+fn eip1559_transaction<T: Config>(
+	source: H160,
+	action: TransactionAction,
+	value: U256,
+	input: Vec<u8>,
+) -> Transaction {
+	let gas_price = gas_price::<T>();
+	Transaction::EIP1559(ethereum::EIP1559Transaction {
+		chain_id: chain_id::<T>(),
+		nonce: nonce::<T>(source),
+		max_priority_fee_per_gas: U256::zero(),
+		max_fee_per_gas: gas_price,
+		gas_limit: GAS_LIMIT.into(),
+		action,
+		value,
+		input,
+		access_list: vec![],
+		signature: EIP2930TransactionSignature::new(
+			false,
+			H256::repeat_byte(1),
+			H256::repeat_byte(1),
+		)
+		.expect("the signature is well-formed"),
+	})
+}
+
+/// The status and receipt status code of the last executed transaction.
+fn last_execution<T: Config>() -> (TransactionStatus, u8) {
+	let transaction_index = Pending::<T>::count().saturating_sub(1);
+	let (_, status, receipt) =
+		Pending::<T>::get(transaction_index).expect("the transaction was executed");
+	let status_code = match receipt {
+		Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) | Receipt::EIP7702(d) => {
+			d.status_code
+		}
+	};
+	(status, status_code)
+}
+
+#[benchmarks(
+	where
+		OriginFor<T>: Into<Result<RawOrigin, OriginFor<T>>> + From<RawOrigin>,
+)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn transact_legacy() {
+		let source = funded_source::<T>();
+		let transaction = legacy_transaction::<T>(
+			source,
+			TransactionAction::Call(H160::repeat_byte(0xde)),
+			U256::one(),
+			vec![],
+		);
+
+		#[extrinsic_call]
+		transact(RawOrigin::EthereumTransaction(source), transaction);
+
+		assert_eq!(last_execution::<T>().1, 1);
+	}
+
+	#[benchmark]
+	fn transact_eip2930() {
+		let source = funded_source::<T>();
+		let target = H160::repeat_byte(0xde);
+		let transaction = eip2930_transaction::<T>(
+			source,
+			TransactionAction::Call(target),
+			U256::one(),
+			vec![],
+			vec![AccessListItem {
+				address: target,
+				storage_keys: vec![H256::zero()],
+			}],
+		);
+
+		#[extrinsic_call]
+		transact(RawOrigin::EthereumTransaction(source), transaction);
+
+		assert_eq!(last_execution::<T>().1, 1);
+	}
+
+	#[benchmark]
+	fn transact_eip1559() {
+		let source = funded_source::<T>();
+		let transaction = eip1559_transaction::<T>(
+			source,
+			TransactionAction::Call(H160::repeat_byte(0xde)),
+			U256::one(),
+			vec![],
+		);
+
+		#[extrinsic_call]
+		transact(RawOrigin::EthereumTransaction(source), transaction);
+
+		assert_eq!(last_execution::<T>().1, 1);
+	}
+
+	#[benchmark]
+	fn transact_create() {
+		let source = funded_source::<T>();
+		let transaction = eip1559_transaction::<T>(
+			source,
+			TransactionAction::Create,
+			U256::zero(),
+			REVERTER_INIT_CODE.to_vec(),
+		);
+
+		#[extrinsic_call]
+		transact(RawOrigin::EthereumTransaction(source), transaction);
+
+		let (status, status_code) = last_execution::<T>();
+		assert_eq!(status_code, 1);
+		assert!(status.contract_address.is_some());
+	}
+
+	#[benchmark]
+	fn transact_precompile() {
+		let source = funded_source::<T>();
+		let (precompile, input) = T::BenchmarkHelper::precompile_call();
+		let transaction = eip1559_transaction::<T>(
+			source,
+			TransactionAction::Call(precompile),
+			U256::zero(),
+			input,
+		);
+
+		#[extrinsic_call]
+		transact(RawOrigin::EthereumTransaction(source), transaction);
+
+		assert_eq!(last_execution::<T>().1, 1);
+	}
+
+	#[benchmark]
+	fn transact_revert() {
+		let source = funded_source::<T>();
+		let reverter = H160::repeat_byte(0xdf);
+		pallet_evm::Pallet::<T>::create_account(reverter, REVERTER_CODE.to_vec(), None)
+			.expect("the reverter contract is deployed");
+		let transaction = eip1559_transaction::<T>(
+			source,
+			TransactionAction::Call(reverter),
+			U256::zero(),
+			vec![],
+		);
+
+		#[extrinsic_call]
+		transact(RawOrigin::EthereumTransaction(source), transaction);
+
+		assert_eq!(last_execution::<T>().1, 0);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(1).1, crate::mock::Test);
+}
@@ -27,18 +27,22 @@
 
 extern crate alloc;
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
 #[cfg(all(feature = "std", test))]
 mod mock;
 #[cfg(all(feature = "std", test))]
 mod tests;
 
+pub mod offchain;
+
 use alloc::{vec, vec::Vec};
 use core::marker::PhantomData;
 pub use ethereum::{
 	AccessListItem, BlockV3 as Block, LegacyTransactionMessage, Log, ReceiptV4 as Receipt,
 	TransactionAction, TransactionV3 as Transaction,
 };
-use ethereum_types::{Bloom, BloomInput, H160, H256, H64, U256};
+use ethereum_types::{H160, H256, H64, U256};
 use evm::ExitReason;
 use scale_codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -161,6 +165,11 @@ where
 		len: usize,
 	) -> Option<TransactionValidity> {
 		if let Call::transact { transaction } = self {
+			// Only bounds the pool: larger transactions are still valid in blocks.
+			if len > T::MaxPoolTransactionSize::get() as usize {
+				return Some(Err(InvalidTransaction::ExhaustsResources.into()));
+			}
+
 			if let Err(e) = CheckWeight::<T>::do_validate(dispatch_info, len) {
 				return Some(Err(e));
 			}
@@ -208,6 +217,16 @@ pub mod pallet {
 		type PostLogContent: Get<PostLogContent>;
 		/// The maximum length of the extra data in the Executed event.
 		type ExtraDataLength: Get<u32>;
+		/// The maximum encoded size in bytes of a transaction accepted in the pool.
+		type MaxPoolTransactionSize: Get<u32>;
+		/// The maximum number of `Log` events deposited for a transaction, one per EVM log, for
+		/// the indexers which only read Substrate events. Zero disables them.
+		type MaxLogEvents: Get<u32>;
+		/// The maximum length of the log data kept in a `Log` event.
+		type MaxLogEventDataLength: Get<u32>;
+		/// Runtime specific parameters of the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: crate::benchmarking::BenchmarkHelper;
 	}
 
 	pub mod config_preludes {
@@ -224,6 +243,8 @@ pub mod pallet {
 
 		parameter_types! {
 			pub const PostBlockAndTxnHashes: PostLogContent = PostLogContent::BlockAndTxnHashes;
+			// Same as geth.
+			pub const DefaultMaxPoolTransactionSize: u32 = 128 * 1024;
 		}
 
 		#[register_default_impl(TestDefaultConfig)]
@@ -233,6 +254,11 @@ pub mod pallet {
 			type StateRoot = IntermediateStateRoot<Self::Version>;
 			type PostLogContent = PostBlockAndTxnHashes;
 			type ExtraDataLength = ConstU32<30>;
+			type MaxPoolTransactionSize = DefaultMaxPoolTransactionSize;
+			type MaxLogEvents = ConstU32<0>;
+			type MaxLogEventDataLength = ConstU32<256>;
+			#[cfg(feature = "runtime-benchmarks")]
+			type BenchmarkHelper = ();
 		}
 	}
 
@@ -291,6 +317,10 @@ pub mod pallet {
 			weight.saturating_add(T::DbWeight::get().reads_writes(2, 2))
 		}
 
+		fn offchain_worker(_: BlockNumberFor<T>) {
+			offchain::index_logs::<T>();
+		}
+
 		fn on_runtime_upgrade() -> Weight {
 			frame_support::storage::unhashed::put::<EthereumStorageSchema>(
 				PALLET_ETHEREUM_SCHEMA,
@@ -308,13 +338,7 @@ pub mod pallet {
 	{
 		/// Transact an Ethereum transaction.
 		#[pallet::call_index(0)]
-		#[pallet::weight({
-			let without_base_extrinsic_weight = true;
-			<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight({
-				let transaction_data: TransactionData = transaction.into();
-				transaction_data.gas_limit.unique_saturated_into()
-			}, without_base_extrinsic_weight)
-		})]
+		#[pallet::weight(Pallet::<T>::transact_weight(transaction))]
 		pub fn transact(
 			origin: OriginFor<T>,
 			transaction: Transaction,
@@ -341,6 +365,14 @@ pub mod pallet {
 			exit_reason: ExitReason,
 			extra_data: Vec<u8>,
 		},
+		/// A log emitted by an ethereum transaction, deposited before its `Executed` event. Only
+		/// deposited when `MaxLogEvents` is set, for the first logs of the transaction. The data
+		/// is truncated to `MaxLogEventDataLength` bytes.
+		Log {
+			address: H160,
+			topics: Vec<H256>,
+			data: Vec<u8>,
+		},
 	}
 
 	#[pallet::error]
@@ -392,6 +424,40 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+	/// The weight of a `transact` call: the weight of its gas limit, and of the events deposited
+	/// by the EVM runner.
+	pub fn transact_weight(transaction: &Transaction) -> Weight {
+		let without_base_extrinsic_weight = true;
+		let transaction_data: TransactionData = transaction.into();
+		<T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
+			transaction_data.gas_limit.unique_saturated_into(),
+			without_base_extrinsic_weight,
+		)
+		.saturating_add(Self::events_weight())
+	}
+
+	/// The weight of the events deposited for a transaction, beyond the `Executed` one.
+	fn events_weight() -> Weight {
+		pallet_evm::Pallet::<T>::execution_event_weight().saturating_add(Self::log_events_weight())
+	}
+
+	/// The weight of depositing the `Log` events of a transaction: the write of `MaxLogEvents`
+	/// events with four topics and the longest data.
+	fn log_events_weight() -> Weight {
+		let max_log_events = u64::from(T::MaxLogEvents::get());
+		if max_log_events == 0 {
+			return Weight::zero();
+		}
+		// `address`, the topics and the data with their length prefixes.
+		let max_event_size = 20 + 1 + 4 * 32 + 5 + u64::from(T::MaxLogEventDataLength::get());
+		T::DbWeight::get()
+			.writes(max_log_events)
+			.saturating_add(Weight::from_parts(
+				0,
+				max_event_size.saturating_mul(max_log_events),
+			))
+	}
+
 	pub fn transaction_weight(transaction_data: &TransactionData) -> (Option<Weight>, Option<u64>) {
 		match <T as pallet_evm::Config>::GasWeightMapping::gas_to_weight(
 			transaction_data.gas_limit.unique_saturated_into(),
@@ -451,24 +517,21 @@ impl<T: Config> Pallet<T> {
 		let mut transactions = Vec::with_capacity(transactions_count as usize);
 		let mut statuses = Vec::with_capacity(transactions_count as usize);
 		let mut receipts = Vec::with_capacity(transactions_count as usize);
-		let mut logs_bloom = Bloom::default();
 		let mut cumulative_gas_used = U256::zero();
 		for transaction_index in 0..transactions_count {
 			if let Some((transaction, status, receipt)) = Pending::<T>::take(transaction_index) {
 				transactions.push(transaction);
 				statuses.push(status);
-				receipts.push(receipt.clone());
-				let (logs, used_gas) = match receipt {
-					Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => {
-						(d.logs.clone(), d.used_gas)
-					}
-					Receipt::EIP7702(d) => (d.logs.clone(), d.used_gas),
+				cumulative_gas_used = match &receipt {
+					Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => d.used_gas,
+					Receipt::EIP7702(d) => d.used_gas,
 				};
-				cumulative_gas_used = used_gas;
-				Self::logs_bloom(logs, &mut logs_bloom);
+				receipts.push(receipt);
 			}
 		}
 
+		let logs_bloom = fp_ethereum::compute_logs_bloom(&receipts);
+
 		let ommers = Vec::<ethereum::Header>::new();
 		let receipts_root = ethereum::util::ordered_trie_root(
 			receipts.iter().map(ethereum::EnvelopedEncodable::encode),
@@ -518,15 +581,6 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn logs_bloom(logs: Vec<Log>, bloom: &mut Bloom) {
-		for log in logs {
-			bloom.accrue(BloomInput::Raw(&log.address[..]));
-			for topic in log.topics {
-				bloom.accrue(BloomInput::Raw(&topic[..]));
-			}
-		}
-	}
-
 	// Controls that must be performed by the pool.
 	// The controls common with the State Transition Function (STF) are in
 	// the function `validate_transaction_common`.
@@ -629,11 +683,7 @@ impl<T: Config> Pallet<T> {
 					to,
 					contract_address: None,
 					logs: info.logs.clone(),
-					logs_bloom: {
-						let mut bloom: Bloom = Bloom::default();
-						Self::logs_bloom(info.logs, &mut bloom);
-						bloom
-					},
+					logs_bloom: fp_ethereum::logs_bloom(&info.logs),
 				},
 				info.weight_info,
 				info.used_gas,
@@ -674,11 +724,7 @@ impl<T: Config> Pallet<T> {
 					to,
 					contract_address: Some(info.value),
 					logs: info.logs.clone(),
-					logs_bloom: {
-						let mut bloom: Bloom = Bloom::default();
-						Self::logs_bloom(info.logs, &mut bloom);
-						bloom
-					},
+					logs_bloom: fp_ethereum::logs_bloom(&info.logs),
 				},
 				info.weight_info,
 				info.used_gas,
@@ -734,6 +780,15 @@ impl<T: Config> Pallet<T> {
 			}
 		};
 
+		for log in status.logs.iter().take(T::MaxLogEvents::get() as usize) {
+			let data_length = log.data.len().min(T::MaxLogEventDataLength::get() as usize);
+			Self::deposit_event(Event::Log {
+				address: log.address,
+				topics: log.topics.clone(),
+				data: log.data[..data_length].to_vec(),
+			});
+		}
+
 		Pending::<T>::insert(transaction_index, (transaction, status, receipt));
 
 		Self::deposit_event(Event::Executed {
@@ -759,7 +814,7 @@ impl<T: Config> Pallet<T> {
 							*gas_to_weight.proof_size_mut() = proof_size_usage;
 						}
 					}
-					Some(gas_to_weight)
+					Some(gas_to_weight.saturating_add(Self::events_weight()))
 				},
 				pays_fee: Pays::No,
 			},
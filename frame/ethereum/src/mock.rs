@@ -93,6 +93,8 @@ parameter_types! {
 	// Alice is allowed to create contracts via CREATE and CALL(CREATE)
 	pub AllowedAddressesCreate: Vec<H160> = vec![H160::from_str("0x1a642f0e3c3af545e7acbd38b07251b3990914f1").expect("alice address")];
 	pub AllowedAddressesCreateInner: Vec<H160> = vec![H160::from_str("0x1a642f0e3c3af545e7acbd38b07251b3990914f1").expect("alice address")];
+	pub static MaxLogEvents: u32 = 0;
+	pub static MaxLogEventDataLength: u32 = 256;
 }
 
 #[derive_impl(pallet_evm::config_preludes::TestDefaultConfig)]
@@ -110,8 +112,23 @@ impl pallet_evm::Config for Test {
 	type Timestamp = Timestamp;
 }
 
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::benchmarking::BenchmarkHelper for BenchmarkHelper {
+	fn source() -> H160 {
+		// Alice is allowed to create contracts.
+		H160::from_str("0x1a642f0e3c3af545e7acbd38b07251b3990914f1").expect("alice address")
+	}
+}
+
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
-impl Config for Test {}
+impl Config for Test {
+	type MaxLogEvents = MaxLogEvents;
+	type MaxLogEventDataLength = MaxLogEventDataLength;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BenchmarkHelper;
+}
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	type SignedInfo = H160;
@@ -0,0 +1,189 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Off-chain indexing of the EVM logs.
+//!
+//! When enabled on a node, the off-chain worker stores the logs of every block it runs on in
+//! the persistent off-chain storage, where block explorers can read them without decoding the
+//! receipts. Indexing is disabled by default, and enabled on a node by setting
+//! [`LOG_INDEXING_ENABLED_KEY`] to `true` in its persistent off-chain storage, e.g. with the
+//! `offchain_localStorageSet` RPC.
+//!
+//! The logs of a block are stored under [`indexed_logs_key`] of its Ethereum block hash. The
+//! off-chain worker runs on imported blocks, not only finalized ones, so the blocks of every
+//! fork are indexed, and readers look up the blocks of the canonical chain by their hashes. The
+//! hashes of the indexed blocks are kept by number under [`indexed_blocks_key`], and once a block
+//! is built on, the logs of the blocks it retracted at the heights below are deleted.
+
+use alloc::vec::Vec;
+use ethereum_types::{H160, H256, U256};
+use scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+// Substrate
+use sp_runtime::{offchain::storage::StorageValueRef, RuntimeDebug};
+
+use crate::{BlockHash, Config, CurrentBlock, CurrentTransactionStatuses};
+
+/// The persistent off-chain storage key enabling the log indexing on a node.
+pub const LOG_INDEXING_ENABLED_KEY: &[u8] = b"frontier::log-indexing::enabled";
+
+/// The prefix of the persistent off-chain storage keys of the indexed logs.
+pub const INDEXED_LOGS_PREFIX: &[u8] = b"frontier::log-indexing::logs";
+
+/// The prefix of the persistent off-chain storage keys of the hashes of the indexed blocks.
+pub const INDEXED_BLOCKS_PREFIX: &[u8] = b"frontier::log-indexing::blocks";
+
+/// The maximum encoded size of the indexed logs of a block. The logs of larger blocks are not
+/// indexed.
+pub const MAX_INDEXED_LOGS_SIZE: usize = 4 * 1024 * 1024;
+
+/// An EVM log, as indexed in the off-chain storage.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct IndexedLog {
+	/// The hash of the Ethereum block including the log.
+	pub block_hash: H256,
+	/// The number of the Ethereum block including the log.
+	pub block_number: u64,
+	/// The hash of the transaction emitting the log.
+	pub tx_hash: H256,
+	/// The index of the log in its block.
+	pub log_index: u32,
+	/// The address of the contract emitting the log.
+	pub address: H160,
+	/// The topics of the log.
+	pub topics: Vec<H256>,
+	/// The data of the log.
+	pub data: Vec<u8>,
+}
+
+/// The persistent off-chain storage key of the indexed logs of a block.
+pub fn indexed_logs_key(block_hash: H256) -> Vec<u8> {
+	(INDEXED_LOGS_PREFIX, block_hash).encode()
+}
+
+/// The persistent off-chain storage key of the hashes of the indexed blocks at a height.
+pub fn indexed_blocks_key(block_number: u64) -> Vec<u8> {
+	(INDEXED_BLOCKS_PREFIX, block_number).encode()
+}
+
+/// Whether the log indexing is enabled on this node.
+pub fn is_log_indexing_enabled() -> bool {
+	StorageValueRef::persistent(LOG_INDEXING_ENABLED_KEY)
+		.get::<bool>()
+		.ok()
+		.flatten()
+		.unwrap_or(false)
+}
+
+/// The indexed logs of a block, if any.
+pub fn indexed_logs(block_hash: H256) -> Option<Vec<IndexedLog>> {
+	StorageValueRef::persistent(&indexed_logs_key(block_hash))
+		.get::<Vec<IndexedLog>>()
+		.ok()
+		.flatten()
+}
+
+/// Index the logs of the current block, if enabled on this node.
+pub(crate) fn index_logs<T: Config>() {
+	if !is_log_indexing_enabled() {
+		return;
+	}
+	let (Some(block), Some(statuses)) = (
+		CurrentBlock::<T>::get(),
+		CurrentTransactionStatuses::<T>::get(),
+	) else {
+		return;
+	};
+	let block_hash = block.header.hash();
+	let block_number = block.header.number.low_u64();
+
+	prune_retracted_logs::<T>(block_number);
+
+	let logs = statuses
+		.into_iter()
+		.flat_map(|status| {
+			let tx_hash = status.transaction_hash;
+			status.logs.into_iter().map(move |log| (tx_hash, log))
+		})
+		.enumerate()
+		.map(|(log_index, (tx_hash, log))| IndexedLog {
+			block_hash,
+			block_number,
+			tx_hash,
+			log_index: log_index as u32,
+			address: log.address,
+			topics: log.topics,
+			data: log.data,
+		})
+		.collect::<Vec<_>>();
+
+	let size = logs.encoded_size();
+	if size > MAX_INDEXED_LOGS_SIZE {
+		log::warn!(
+			target: "ethereum",
+			"Skipping the off-chain indexing of block {}: {} bytes of logs exceed the {} bytes limit",
+			block_number,
+			size,
+			MAX_INDEXED_LOGS_SIZE,
+		);
+		return;
+	}
+
+	StorageValueRef::persistent(&indexed_logs_key(block_hash)).set(&logs);
+
+	let indexed_blocks = StorageValueRef::persistent(&indexed_blocks_key(block_number));
+	let mut hashes = indexed_blocks
+		.get::<Vec<H256>>()
+		.ok()
+		.flatten()
+		.unwrap_or_default();
+	if !hashes.contains(&block_hash) {
+		hashes.push(block_hash);
+		indexed_blocks.set(&hashes);
+	}
+}
+
+/// Delete the indexed logs of the blocks below `block_number` retracted by the current chain,
+/// walking down until a height indexing no block or only its canonical one, or out of the known
+/// block hashes.
+fn prune_retracted_logs<T: Config>(block_number: u64) {
+	for number in (0..block_number).rev() {
+		let canonical = BlockHash::<T>::get(U256::from(number));
+		if canonical.is_zero() {
+			return;
+		}
+
+		let mut indexed_blocks = StorageValueRef::persistent(&indexed_blocks_key(number));
+		let hashes = indexed_blocks
+			.get::<Vec<H256>>()
+			.ok()
+			.flatten()
+			.unwrap_or_default();
+		if hashes.is_empty() || hashes == [canonical] {
+			return;
+		}
+
+		for hash in hashes.iter().filter(|hash| **hash != canonical) {
+			StorageValueRef::persistent(&indexed_logs_key(*hash)).clear();
+		}
+		if hashes.contains(&canonical) {
+			indexed_blocks.set(&alloc::vec![canonical]);
+		} else {
+			indexed_blocks.clear();
+		}
+	}
+}
@@ -21,7 +21,8 @@ use super::*;
 use evm::{ExitReason, ExitRevert, ExitSucceed};
 use fp_ethereum::{TransactionData, ValidatedTransaction};
 use frame_support::{
-	dispatch::{DispatchClass, GetDispatchInfo, Pays, PostDispatchInfo},
+	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo},
+	traits::Get,
 	weights::Weight,
 };
 use pallet_evm::AddressMapping;
@@ -158,6 +159,109 @@ fn transaction_with_to_low_nonce_should_not_work() {
 	});
 }
 
+/// The self-contained call of an ERC20 creation by `account`, with its source and dispatch info.
+fn legacy_erc20_creation_self_contained(
+	account: &AccountInfo,
+) -> (crate::Call<Test>, H160, DispatchInfo) {
+	let signed = legacy_erc20_creation_transaction(account);
+	let call = crate::Call::<Test>::transact {
+		transaction: signed,
+	};
+	let source = call.check_self_contained().unwrap().unwrap();
+	let extrinsic = CheckedExtrinsic::<u64, _, SignedExtra, H160> {
+		signed: fp_self_contained::CheckedSignature::SelfContained(source),
+		function: RuntimeCall::Ethereum(call.clone()),
+	};
+	let dispatch_info = extrinsic.get_dispatch_info();
+	(call, source, dispatch_info)
+}
+
+#[test]
+fn transaction_larger_than_pool_limit_should_not_be_pooled() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let (call, source, dispatch_info) = legacy_erc20_creation_self_contained(alice);
+		let limit = <Test as crate::Config>::MaxPoolTransactionSize::get() as usize;
+
+		assert_ok!(call
+			.validate_self_contained(&source, &dispatch_info, limit)
+			.unwrap());
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, limit + 1)
+				.unwrap(),
+			InvalidTransaction::ExhaustsResources
+		);
+	});
+}
+
+#[test]
+fn transaction_from_account_with_code_should_not_be_pooled() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		// EIP-3607: the code of a contract stays at its address, even after a `SELFDESTRUCT`
+		// outside of its creation transaction.
+		assert_ok!(pallet_evm::Pallet::<Test>::create_account(
+			alice.address,
+			vec![0x00],
+			None
+		));
+
+		let (call, source, dispatch_info) = legacy_erc20_creation_self_contained(alice);
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			InvalidTransaction::BadSigner
+		);
+	});
+}
+
+#[test]
+fn transaction_from_account_with_self_destructed_code_should_not_be_pooled() {
+	let (pairs, mut ext) = new_test_ext(2);
+	let alice = &pairs[0];
+	let bob = &pairs[1];
+
+	ext.execute_with(|| {
+		// CALLER SELFDESTRUCT
+		assert_ok!(pallet_evm::Pallet::<Test>::create_account(
+			alice.address,
+			vec![0x33, 0xff],
+			None
+		));
+
+		let t = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: TransactionAction::Call(alice.address),
+			value: U256::zero(),
+			input: vec![],
+		}
+		.sign(&bob.private_key);
+		let (_, _, info) = Ethereum::execute(bob.address, &t, None).unwrap();
+		match info {
+			CallOrCreateInfo::Call(info) => {
+				assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+			}
+			CallOrCreateInfo::Create(_) => panic!("expected call info"),
+		}
+
+		// EIP-6780: the code was not created in the same transaction, so it is kept.
+		assert!(!pallet_evm::AccountCodes::<Test>::get(alice.address).is_empty());
+
+		let (call, source, dispatch_info) = legacy_erc20_creation_self_contained(alice);
+		assert_err!(
+			call.validate_self_contained(&source, &dispatch_info, 0)
+				.unwrap(),
+			InvalidTransaction::BadSigner
+		);
+	});
+}
+
 #[test]
 fn transaction_with_to_hight_nonce_should_fail_in_block() {
 	let (pairs, mut ext) = new_test_ext(1);
@@ -269,6 +373,329 @@ fn contract_should_be_created_at_given_address() {
 	});
 }
 
+#[test]
+fn block_logs_bloom_should_include_receipt_logs() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	let erc20_address = contract_address(alice.address, 0);
+	// keccak256("Transfer(address,address,uint256)")
+	let transfer_topic =
+		H256::from_str("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").unwrap();
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			legacy_erc20_creation_transaction(alice),
+		));
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+
+		let block = crate::CurrentBlock::<Test>::get().expect("block is stored");
+		let receipts = crate::CurrentReceipts::<Test>::get().expect("receipts are stored");
+		let logs_bloom = block.header.logs_bloom;
+
+		assert_eq!(logs_bloom, fp_ethereum::compute_logs_bloom(&receipts));
+		// The mint emits a `Transfer` event from the new contract.
+		assert!(logs_bloom.contains_input(ethereum_types::BloomInput::Raw(&erc20_address[..])));
+		assert!(logs_bloom.contains_input(ethereum_types::BloomInput::Raw(&transfer_topic[..])));
+	});
+}
+
+fn register_offchain_ext(
+	ext: &mut sp_io::TestExternalities,
+	offchain: sp_core::offchain::testing::TestOffchainExt,
+) {
+	use sp_core::offchain::{OffchainDbExt, OffchainWorkerExt};
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+}
+
+fn enable_log_indexing() {
+	sp_runtime::offchain::storage::StorageValueRef::persistent(
+		crate::offchain::LOG_INDEXING_ENABLED_KEY,
+	)
+	.set(&true);
+}
+
+fn current_block_hash() -> H256 {
+	crate::CurrentBlock::<Test>::get()
+		.expect("a block is stored")
+		.header
+		.hash()
+}
+
+#[test]
+fn offchain_worker_indexes_block_logs_when_enabled() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+	register_offchain_ext(&mut ext, Default::default());
+
+	let erc20_address = contract_address(alice.address, 0);
+	let transaction = legacy_erc20_creation_transaction(alice);
+	let tx_hash = transaction.hash();
+
+	ext.execute_with(|| {
+		enable_log_indexing();
+
+		System::set_block_number(1);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			transaction,
+		));
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+		<Ethereum as frame_support::traits::Hooks<_>>::offchain_worker(1);
+
+		let block_hash = current_block_hash();
+		let logs = crate::offchain::indexed_logs(block_hash).expect("logs are indexed");
+		// The mint emits a single `Transfer` event from the new contract.
+		assert_eq!(logs.len(), 1);
+		assert_eq!(logs[0].block_hash, block_hash);
+		assert_eq!(logs[0].block_number, 1);
+		assert_eq!(logs[0].tx_hash, tx_hash);
+		assert_eq!(logs[0].log_index, 0);
+		assert_eq!(logs[0].address, erc20_address);
+		let status = &crate::CurrentTransactionStatuses::<Test>::get().unwrap()[0];
+		assert_eq!(logs[0].topics, status.logs[0].topics);
+		assert_eq!(logs[0].data, status.logs[0].data);
+	});
+}
+
+#[test]
+fn offchain_worker_does_not_index_logs_by_default() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+	register_offchain_ext(&mut ext, Default::default());
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			legacy_erc20_creation_transaction(alice),
+		));
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+		<Ethereum as frame_support::traits::Hooks<_>>::offchain_worker(1);
+
+		assert_eq!(crate::offchain::indexed_logs(current_block_hash()), None);
+	});
+}
+
+#[test]
+fn offchain_worker_prunes_the_logs_of_retracted_forks() {
+	// Both forks share the off-chain storage of the node.
+	let offchain = sp_core::offchain::testing::TestOffchainExt::default();
+	let (pairs, mut fork_a) = new_test_ext(1);
+	let alice = &pairs[0];
+	register_offchain_ext(&mut fork_a, offchain.clone());
+	let (_, mut fork_b) = new_test_ext(1);
+	register_offchain_ext(&mut fork_b, offchain);
+
+	// Fork A includes a transaction emitting a log at block 1, fork B an empty block.
+	let block_a = fork_a.execute_with(|| {
+		enable_log_indexing();
+		System::set_block_number(1);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			legacy_erc20_creation_transaction(alice),
+		));
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+		<Ethereum as frame_support::traits::Hooks<_>>::offchain_worker(1);
+		current_block_hash()
+	});
+	let block_b = fork_b.execute_with(|| {
+		System::set_block_number(1);
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(1);
+		<Ethereum as frame_support::traits::Hooks<_>>::offchain_worker(1);
+		current_block_hash()
+	});
+	assert_ne!(block_a, block_b);
+
+	// Until one of them is built on, the blocks of both forks are indexed.
+	fork_b.execute_with(|| {
+		assert_eq!(
+			crate::offchain::indexed_logs(block_a).map(|logs| logs.len()),
+			Some(1)
+		);
+		assert_eq!(crate::offchain::indexed_logs(block_b), Some(Vec::new()));
+	});
+
+	// Building on fork B retracts the block of fork A.
+	fork_b.execute_with(|| {
+		System::set_block_number(2);
+		<Ethereum as frame_support::traits::Hooks<_>>::on_finalize(2);
+		<Ethereum as frame_support::traits::Hooks<_>>::offchain_worker(2);
+
+		assert_eq!(crate::offchain::indexed_logs(block_a), None);
+		assert_eq!(crate::offchain::indexed_logs(block_b), Some(Vec::new()));
+		assert_eq!(
+			crate::offchain::indexed_logs(current_block_hash()),
+			Some(Vec::new())
+		);
+		assert_eq!(
+			sp_runtime::offchain::storage::StorageValueRef::persistent(
+				&crate::offchain::indexed_blocks_key(1)
+			)
+			.get::<Vec<H256>>()
+			.unwrap(),
+			Some(vec![block_b])
+		);
+	});
+}
+
+/// The `Log` events deposited by the pallet, in order.
+fn log_events() -> Vec<(H160, Vec<H256>, Vec<u8>)> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Ethereum(Event::Log {
+				address,
+				topics,
+				data,
+			}) => Some((address, topics, data)),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn logs_are_deposited_as_events_when_enabled() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+	MaxLogEvents::set(16);
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			legacy_erc20_creation_transaction(alice),
+		));
+
+		let status = &crate::Pending::<Test>::get(0).unwrap().1;
+		// The mint emits a single `Transfer` event from the new contract.
+		assert_eq!(status.logs.len(), 1);
+		assert_eq!(
+			log_events(),
+			vec![(
+				contract_address(alice.address, 0),
+				status.logs[0].topics.clone(),
+				status.logs[0].data.clone(),
+			)]
+		);
+		assert!(matches!(
+			System::events().last().unwrap().event,
+			RuntimeEvent::Ethereum(Event::Executed { .. })
+		));
+	});
+}
+
+#[test]
+fn logs_are_not_deposited_as_events_by_default() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			legacy_erc20_creation_transaction(alice),
+		));
+
+		assert_eq!(crate::Pending::<Test>::get(0).unwrap().1.logs.len(), 1);
+		assert!(log_events().is_empty());
+	});
+}
+
+#[test]
+fn log_events_are_bounded() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+	MaxLogEvents::set(2);
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		// Creation code emitting three empty `LOG0`s.
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: ethereum::TransactionAction::Create,
+			value: U256::zero(),
+			input: hex::decode("60006000a060006000a060006000a000").unwrap(),
+		}
+		.sign(&alice.private_key);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			transaction,
+		));
+
+		assert_eq!(crate::Pending::<Test>::get(0).unwrap().1.logs.len(), 3);
+		let address = contract_address(alice.address, 0);
+		assert_eq!(
+			log_events(),
+			vec![(address, vec![], vec![]), (address, vec![], vec![])]
+		);
+	});
+}
+
+#[test]
+fn log_event_data_is_truncated() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+	MaxLogEvents::set(1);
+	MaxLogEventDataLength::set(8);
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		// Creation code emitting a `LOG0` of 64 bytes starting with 0xff.
+		let transaction = LegacyUnsignedTransaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(1),
+			gas_limit: U256::from(0x100000),
+			action: ethereum::TransactionAction::Create,
+			value: U256::zero(),
+			input: hex::decode("60ff60005360406000a000").unwrap(),
+		}
+		.sign(&alice.private_key);
+		assert_ok!(Ethereum::transact(
+			RawOrigin::EthereumTransaction(alice.address).into(),
+			transaction,
+		));
+
+		let status = &crate::Pending::<Test>::get(0).unwrap().1;
+		assert_eq!(status.logs[0].data.len(), 64);
+		assert_eq!(
+			log_events(),
+			vec![(
+				contract_address(alice.address, 0),
+				vec![],
+				status.logs[0].data[..8].to_vec(),
+			)]
+		);
+	});
+}
+
+#[test]
+fn log_events_are_charged() {
+	let (pairs, mut ext) = new_test_ext(1);
+	let alice = &pairs[0];
+
+	ext.execute_with(|| {
+		let transaction = legacy_erc20_creation_transaction(alice);
+		let weight = Ethereum::transact_weight(&transaction);
+
+		MaxLogEvents::set(2);
+		MaxLogEventDataLength::set(100);
+		// Two events with an address, four topics and 100 bytes of data, with length prefixes.
+		let log_events_weight = <Test as frame_system::Config>::DbWeight::get()
+			.writes(2)
+			.saturating_add(Weight::from_parts(0, 2 * (20 + 1 + 4 * 32 + 5 + 100)));
+		assert_eq!(
+			Ethereum::transact_weight(&transaction),
+			weight.saturating_add(log_events_weight)
+		);
+	});
+}
+
 #[test]
 fn transaction_should_generate_correct_gas_used() {
 	let (pairs, mut ext) = new_test_ext(1);
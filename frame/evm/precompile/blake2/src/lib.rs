@@ -18,8 +18,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(unused_crate_dependencies)]
 
-mod eip_152;
+pub mod eip_152;
 
+use core::marker::PhantomData;
+// Substrate
+use sp_core::Get;
+// Frontier
 use fp_evm::{
 	ExitError, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
 	PrecompileResult,
@@ -31,6 +35,29 @@ impl Blake2F {
 	const GAS_COST_PER_ROUND: u64 = 1; // https://eips.ethereum.org/EIPS/eip-152#gas-costs-and-benchmarks
 }
 
+/// [`Blake2F`] recording `RefTimePerRound` ref time as external cost for each round of the
+/// compression function.
+///
+/// The value should come from the runtime's own run of the `precompile_blake2f` benchmark of
+/// `pallet-evm`.
+pub struct MeteredBlake2F<RefTimePerRound>(PhantomData<RefTimePerRound>);
+
+impl<RefTimePerRound: Get<u64>> Precompile for MeteredBlake2F<RefTimePerRound> {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Malformed inputs are left to `Blake2F`, which rejects them.
+		if let Some(rounds_buf) = handle.input().get(0..4) {
+			let rounds = u32::from_be_bytes(rounds_buf.try_into().expect("4 bytes; qed")) as u64;
+			handle.record_external_cost(
+				Some(rounds.saturating_mul(RefTimePerRound::get())),
+				None,
+				None,
+			)?;
+		}
+
+		Blake2F::execute(handle)
+	}
+}
+
 impl Precompile for Blake2F {
 	/// Format of `input`:
 	/// [4 bytes for rounds][64 bytes for h][128 bytes for m][8 bytes for t_0][8 bytes for t_1][1 byte for f]
@@ -115,7 +142,28 @@ impl Precompile for Blake2F {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+	use fp_evm::Context;
+	use pallet_evm_test_vector_support::{test_precompile_test_vectors, MockHandle};
+	use sp_core::ConstU64;
+
+	#[test]
+	fn records_ref_time_per_round() {
+		let mut input = vec![0u8; 213];
+		input[0..4].copy_from_slice(&12u32.to_be_bytes());
+		let context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+		let mut handle = MockHandle::new(input, None, context);
+
+		assert!(MeteredBlake2F::<ConstU64<100_000>>::execute(&mut handle).is_ok());
+		assert_eq!(handle.gas_used, 12);
+		assert_eq!(
+			handle.external_costs,
+			vec![(Some(12 * 100_000), None, None)]
+		);
+	}
 
 	#[test]
 	fn process_consensus_tests() -> Result<(), String> {
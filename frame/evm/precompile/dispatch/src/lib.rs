@@ -26,15 +26,16 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-use alloc::format;
+use alloc::{format, vec, vec::Vec};
 use core::marker::PhantomData;
 
 use scale_codec::{Decode, DecodeLimit};
 // Substrate
 use frame_support::{
-	dispatch::{DispatchClass, GetDispatchInfo, Pays, PostDispatchInfo},
-	traits::{ConstU32, Get},
+	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo},
+	traits::{ConstBool, ConstU32, Get},
 };
+use sp_core::{H160, H256};
 use sp_runtime::traits::Dispatchable;
 // Frontier
 use fp_evm::{
@@ -43,20 +44,41 @@ use fp_evm::{
 };
 use pallet_evm::{AddressMapping, GasWeightMapping};
 
+/// Signature of the log emitted after each dispatch, its keccak256 hash is the log first topic.
+pub const DISPATCHED_EVENT_SIGNATURE: &[u8] = b"Dispatched(address,uint8,uint8,bool)";
+
+// Gas costs of a LOG1 emitting the 4 words of the `Dispatched` log.
+const LOG_GAS: u64 = 375;
+const LOG_TOPIC_GAS: u64 = 375;
+const LOG_DATA_GAS: u64 = 8;
+const DISPATCHED_LOG_GAS: u64 = LOG_GAS + LOG_TOPIC_GAS + LOG_DATA_GAS * 4 * 32;
+
 // `DecodeLimit` specifies the max depth a call can use when decoding, as unbounded depth
 // can be used to overflow the stack.
 // Default value is 8, which is the same as in XCM call decoding.
-pub struct Dispatch<T, DispatchValidator = (), DecodeLimit = ConstU32<8>> {
-	_marker: PhantomData<(T, DispatchValidator, DecodeLimit)>,
+//
+// `EmitLog` opts in to a `Dispatched` log after each successful dispatch, so that Ethereum-side
+// indexers can follow the dispatched calls. A failed dispatch then logs an unsuccessful outcome
+// and returns, keeping the weight of the call charged, rather than failing the execution. The log
+// costs gas, so it is disabled by default.
+pub struct Dispatch<
+	T,
+	DispatchValidator = (),
+	DecodeLimit = ConstU32<8>,
+	EmitLog = ConstBool<false>,
+> {
+	_marker: PhantomData<(T, DispatchValidator, DecodeLimit, EmitLog)>,
 }
 
-impl<T, DispatchValidator, DecodeLimit> Precompile for Dispatch<T, DispatchValidator, DecodeLimit>
+impl<T, DispatchValidator, DecodeLimit, EmitLog> Precompile
+	for Dispatch<T, DispatchValidator, DecodeLimit, EmitLog>
 where
 	T: pallet_evm::Config,
 	T::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
 	<T::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<pallet_evm::AccountIdOf<T>>>,
 	DispatchValidator: DispatchValidateT<pallet_evm::AccountIdOf<T>, T::RuntimeCall>,
 	DecodeLimit: Get<u32>,
+	EmitLog: Get<bool>,
 {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		let input = handle.input();
@@ -68,6 +90,9 @@ where
 				exit_status: ExitError::Other("decode failed".into()),
 			})?;
 		let info = call.get_dispatch_info();
+		// The encoded call starts with the call indices.
+		let (pallet_index, call_index) = (input[0], input[1]);
+		let caller = context.caller;
 
 		if let Some(gas) = target_gas {
 			let valid_weight = info.total_weight().ref_time()
@@ -79,7 +104,7 @@ where
 			}
 		}
 
-		let origin = T::AddressMapping::into_account_id(context.caller);
+		let origin = T::AddressMapping::into_account_id(caller);
 
 		if let Some(err) = DispatchValidator::validate_before_dispatch(&origin, &call) {
 			return Err(err);
@@ -91,25 +116,16 @@ where
 			None,
 		)?;
 
+		if EmitLog::get() {
+			handle.record_cost(DISPATCHED_LOG_GAS)?;
+		}
+
 		match call.dispatch(Some(origin).into()) {
 			Ok(post_info) => {
-				if post_info.pays_fee(&info) == Pays::Yes {
-					let actual_weight = post_info.actual_weight.unwrap_or(info.total_weight());
-					let cost = T::GasWeightMapping::weight_to_gas(actual_weight);
-					handle.record_cost(cost)?;
-
-					handle.refund_external_cost(
-						Some(
-							info.total_weight()
-								.ref_time()
-								.saturating_sub(actual_weight.ref_time()),
-						),
-						Some(
-							info.total_weight()
-								.proof_size()
-								.saturating_sub(actual_weight.proof_size()),
-						),
-					);
+				charge_actual_weight::<T>(handle, &info, &post_info)?;
+
+				if EmitLog::get() {
+					log_dispatched(handle, caller, pallet_index, call_index, true)?;
 				}
 
 				Ok(PrecompileOutput {
@@ -117,15 +133,85 @@ where
 					output: Default::default(),
 				})
 			}
-			Err(e) => Err(PrecompileFailure::Error {
-				exit_status: ExitError::Other(
-					format!("dispatch execution failed: {}", <&'static str>::from(e)).into(),
-				),
-			}),
+			Err(e) => {
+				if EmitLog::get() {
+					// The weight of the failed call stays charged.
+					charge_actual_weight::<T>(handle, &info, &e.post_info)?;
+					log_dispatched(handle, caller, pallet_index, call_index, false)?;
+
+					return Ok(PrecompileOutput {
+						exit_status: ExitSucceed::Stopped,
+						output: Default::default(),
+					});
+				}
+
+				Err(PrecompileFailure::Error {
+					exit_status: ExitError::Other(
+						format!("dispatch execution failed: {}", <&'static str>::from(e)).into(),
+					),
+				})
+			}
 		}
 	}
 }
 
+/// Charge the gas of the weight actually used by the dispatch, refunding the rest of the
+/// recorded weight.
+fn charge_actual_weight<T: pallet_evm::Config>(
+	handle: &mut impl PrecompileHandle,
+	info: &DispatchInfo,
+	post_info: &PostDispatchInfo,
+) -> Result<(), PrecompileFailure> {
+	if post_info.pays_fee(info) == Pays::Yes {
+		let actual_weight = post_info.actual_weight.unwrap_or(info.total_weight());
+		let cost = T::GasWeightMapping::weight_to_gas(actual_weight);
+		handle.record_cost(cost)?;
+
+		handle.refund_external_cost(
+			Some(
+				info.total_weight()
+					.ref_time()
+					.saturating_sub(actual_weight.ref_time()),
+			),
+			Some(
+				info.total_weight()
+					.proof_size()
+					.saturating_sub(actual_weight.proof_size()),
+			),
+		);
+	}
+	Ok(())
+}
+
+/// The ABI encoded data of the `Dispatched(address,uint8,uint8,bool)` log: the caller, the
+/// pallet and call indices and the dispatch outcome.
+fn dispatched_data(caller: H160, pallet_index: u8, call_index: u8, success: bool) -> Vec<u8> {
+	let mut data = vec![0u8; 4 * 32];
+	data[12..32].copy_from_slice(caller.as_bytes());
+	data[63] = pallet_index;
+	data[95] = call_index;
+	data[127] = success as u8;
+	data
+}
+
+/// Emit the `Dispatched` log from the precompile address.
+fn log_dispatched(
+	handle: &mut impl PrecompileHandle,
+	caller: H160,
+	pallet_index: u8,
+	call_index: u8,
+	success: bool,
+) -> Result<(), PrecompileFailure> {
+	let topics: Vec<H256> = vec![sp_io::hashing::keccak_256(DISPATCHED_EVENT_SIGNATURE).into()];
+	let address = handle.code_address();
+	handle.log(
+		address,
+		topics,
+		dispatched_data(caller, pallet_index, call_index, success),
+	)?;
+	Ok(())
+}
+
 /// Dispatch validation trait.
 pub trait DispatchValidateT<AccountId, RuntimeCall> {
 	fn validate_before_dispatch(
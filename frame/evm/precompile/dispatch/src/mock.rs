@@ -20,7 +20,7 @@
 use core::str::FromStr;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, FindAuthor},
+	traits::{ConstBool, ConstU32, FindAuthor, Nothing},
 	weights::Weight,
 	ConsensusEngineId,
 };
@@ -151,6 +151,7 @@ impl pallet_evm::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type PrecompilesType = ();
 	type PrecompilesValue = ();
+	type ReservedAddresses = Nothing;
 	type ChainId = ();
 	type BlockGasLimit = BlockGasLimit;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
@@ -161,6 +162,8 @@ impl pallet_evm::Config for Test {
 	type GasLimitStorageGrowthRatio = ();
 	type Timestamp = Timestamp;
 	type CreateInnerOriginFilter = ();
+	type ExecutionEvents = ConstBool<false>;
+	type ExecutionEventReasonLength = ConstU32<256>;
 	type CreateOriginFilter = ();
 	type WeightInfo = ();
 }
@@ -168,6 +171,9 @@ impl pallet_evm::Config for Test {
 pub(crate) struct MockHandle {
 	pub input: Vec<u8>,
 	pub context: Context,
+	pub code_address: H160,
+	pub logs: Vec<(H160, Vec<H256>, Vec<u8>)>,
+	pub gas_used: u64,
 }
 
 impl PrecompileHandle for MockHandle {
@@ -183,7 +189,8 @@ impl PrecompileHandle for MockHandle {
 		unimplemented!()
 	}
 
-	fn record_cost(&mut self, _: u64) -> Result<(), ExitError> {
+	fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+		self.gas_used += cost;
 		Ok(())
 	}
 
@@ -202,12 +209,13 @@ impl PrecompileHandle for MockHandle {
 		unimplemented!()
 	}
 
-	fn log(&mut self, _: H160, _: Vec<H256>, _: Vec<u8>) -> Result<(), ExitError> {
-		unimplemented!()
+	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
+		self.logs.push((address, topics, data));
+		Ok(())
 	}
 
 	fn code_address(&self) -> H160 {
-		unimplemented!()
+		self.code_address
 	}
 
 	fn input(&self) -> &[u8] {
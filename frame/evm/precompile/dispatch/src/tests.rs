@@ -20,7 +20,7 @@ use crate::mock::*;
 use fp_evm::Context;
 use frame_support::{assert_err, assert_ok};
 use scale_codec::Encode;
-use sp_core::{H160, U256};
+use sp_core::{H160, H256, U256};
 use sp_runtime::BuildStorage;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -51,6 +51,9 @@ fn decode_limit_too_high() {
 				caller: H160::default(),
 				apparent_value: U256::default(),
 			},
+			code_address: H160::default(),
+			logs: Vec::new(),
+			gas_used: 0,
 		};
 
 		assert_eq!(
@@ -82,6 +85,9 @@ fn decode_limit_ok() {
 				caller: H160::default(),
 				apparent_value: U256::default(),
 			},
+			code_address: H160::default(),
+			logs: Vec::new(),
+			gas_used: 0,
 		};
 
 		assert_ok!(Dispatch::<Test>::execute(&mut handle));
@@ -99,6 +105,9 @@ fn dispatch_validator_works_well() {
 				caller: H160::default(),
 				apparent_value: U256::default(),
 			},
+			code_address: H160::default(),
+			logs: Vec::new(),
+			gas_used: 0,
 		};
 		assert_ok!(Dispatch::<Test>::execute(&mut handle));
 
@@ -126,3 +135,111 @@ fn dispatch_validator_works_well() {
 		);
 	});
 }
+
+/// The dispatch precompile with the `Dispatched` log enabled.
+type LoggingDispatch = Dispatch<Test, (), ConstU32<8>, ConstBool<true>>;
+
+fn dispatch_handle(call: RuntimeCall) -> MockHandle {
+	MockHandle {
+		input: call.encode(),
+		context: Context {
+			address: H160::from_low_u64_be(1025),
+			caller: H160::repeat_byte(0x11),
+			apparent_value: U256::default(),
+		},
+		code_address: H160::from_low_u64_be(1025),
+		logs: Vec::new(),
+		gas_used: 0,
+	}
+}
+
+fn expected_log_data(caller: H160, call: &RuntimeCall, success: bool) -> Vec<u8> {
+	let encoded = call.encode();
+	let mut data = vec![0u8; 128];
+	data[12..32].copy_from_slice(caller.as_bytes());
+	data[63] = encoded[0];
+	data[95] = encoded[1];
+	data[127] = success as u8;
+	data
+}
+
+#[test]
+fn dispatch_emits_dispatched_log() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() });
+		let mut handle = dispatch_handle(call.clone());
+
+		assert_ok!(LoggingDispatch::execute(&mut handle));
+
+		assert_eq!(handle.logs.len(), 1);
+		let (address, topics, data) = &handle.logs[0];
+		assert_eq!(*address, H160::from_low_u64_be(1025));
+		assert_eq!(
+			*topics,
+			vec![H256::from(sp_io::hashing::keccak_256(
+				b"Dispatched(address,uint8,uint8,bool)"
+			))]
+		);
+		assert_eq!(
+			*data,
+			expected_log_data(H160::repeat_byte(0x11), &call, true)
+		);
+	});
+}
+
+#[test]
+fn failed_dispatch_logs_the_unsuccessful_outcome() {
+	new_test_ext().execute_with(|| {
+		// The caller has no balance to transfer.
+		let call = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: H160::repeat_byte(0x22),
+			value: 1,
+		});
+		let mut handle = dispatch_handle(call.clone());
+
+		assert_ok!(LoggingDispatch::execute(&mut handle));
+
+		assert_eq!(handle.logs.len(), 1);
+		let (address, topics, data) = &handle.logs[0];
+		assert_eq!(*address, H160::from_low_u64_be(1025));
+		assert_eq!(
+			*topics,
+			vec![H256::from(sp_io::hashing::keccak_256(
+				b"Dispatched(address,uint8,uint8,bool)"
+			))]
+		);
+		assert_eq!(
+			*data,
+			expected_log_data(H160::repeat_byte(0x11), &call, false)
+		);
+
+		// The weight of the failed call stays charged, on top of the log.
+		let weight = call.get_dispatch_info().total_weight();
+		assert_eq!(
+			handle.gas_used,
+			<Test as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+				+ DISPATCHED_LOG_GAS
+		);
+	});
+}
+
+#[test]
+fn dispatch_log_is_disabled_by_default() {
+	new_test_ext().execute_with(|| {
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() });
+		let mut handle = dispatch_handle(call);
+		assert_ok!(Dispatch::<Test>::execute(&mut handle));
+		assert!(handle.logs.is_empty());
+
+		let call = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: H160::repeat_byte(0x22),
+			value: 1,
+		});
+		let mut handle = dispatch_handle(call);
+		assert!(matches!(
+			Dispatch::<Test>::execute(&mut handle),
+			Err(PrecompileFailure::Error { .. })
+		));
+		assert!(handle.logs.is_empty());
+	});
+}
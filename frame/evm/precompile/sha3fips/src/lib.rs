@@ -59,6 +59,26 @@ impl LinearCostPrecompile for Sha3FIPS512 {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use fp_evm::{Context, RefTimePerInputWord};
+	use pallet_evm_test_vector_support::MockHandle;
+	use sp_core::ConstU64;
+
+	#[test]
+	fn records_ref_time_per_input_word() {
+		type Metered = RefTimePerInputWord<Sha3FIPS256, ConstU64<500_000>>;
+
+		let context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+		let mut handle = MockHandle::new(vec![0u8; 33], None, context);
+
+		assert!(<Metered as fp_evm::Precompile>::execute(&mut handle).is_ok());
+		// The gas cost is unchanged.
+		assert_eq!(handle.gas_used, 60 + 2 * 12);
+		assert_eq!(handle.external_costs, vec![(Some(2 * 500_000), None, None)]);
+	}
 
 	#[test]
 	fn test_empty_input() -> Result<(), PrecompileFailure> {
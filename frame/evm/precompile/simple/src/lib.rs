@@ -137,7 +137,18 @@ impl LinearCostPrecompile for ECRecoverPublicKey {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use pallet_evm_test_vector_support::test_precompile_test_vectors;
+	use fp_evm::{BoundedInputPrecompile, Context, ExitRevert, Precompile, RefTimePerInputWord};
+	use pallet_evm_test_vector_support::{test_precompile_test_vectors, MockHandle};
+	use sp_core::{ConstU32, ConstU64};
+
+	fn new_handle(input: Vec<u8>) -> MockHandle {
+		let context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: From::from(0),
+		};
+		MockHandle::new(input, None, context)
+	}
 
 	// TODO: this fails on the test "InvalidHighV-bits-1" where it is expected to return ""
 	#[test]
@@ -157,4 +168,53 @@ mod tests {
 		test_precompile_test_vectors::<Ripemd160>("../testdata/common_ripemd.json")?;
 		Ok(())
 	}
+
+	#[test]
+	fn ref_time_per_input_word_is_recorded_on_top_of_gas() {
+		type MeteredIdentity = RefTimePerInputWord<Identity, ConstU64<2_000>>;
+		type MeteredRipemd160 = RefTimePerInputWord<Ripemd160, ConstU64<300_000>>;
+
+		let mut handle = new_handle(vec![0u8; 64]);
+		assert!(<MeteredIdentity as Precompile>::execute(&mut handle).is_ok());
+		assert_eq!(handle.gas_used, 15 + 2 * 3);
+		assert_eq!(handle.external_costs, vec![(Some(2 * 2_000), None, None)]);
+
+		let mut handle = new_handle(vec![0u8; 1]);
+		assert!(<MeteredRipemd160 as Precompile>::execute(&mut handle).is_ok());
+		assert_eq!(handle.gas_used, 600 + 120);
+		assert_eq!(handle.external_costs, vec![(Some(300_000), None, None)]);
+
+		// Unwrapped precompiles don't record any ref time.
+		let mut handle = new_handle(vec![0u8; 64]);
+		assert!(<Identity as Precompile>::execute(&mut handle).is_ok());
+		assert!(handle.external_costs.is_empty());
+	}
+
+	#[test]
+	fn bounded_input_reverts_before_hashing() {
+		type BoundedRipemd160 = BoundedInputPrecompile<Ripemd160, ConstU32<64>>;
+
+		let mut handle = new_handle(vec![0u8; 65]);
+		match <BoundedRipemd160 as Precompile>::execute(&mut handle) {
+			Err(PrecompileFailure::Revert { exit_status, .. }) => {
+				assert_eq!(exit_status, ExitRevert::Reverted)
+			}
+			_ => panic!("expected a revert"),
+		}
+		// Nothing was charged for the oversized input.
+		assert_eq!(handle.gas_used, 0);
+		assert!(handle.external_costs.is_empty());
+
+		let mut handle = new_handle(vec![0u8; 64]);
+		let output =
+			<BoundedRipemd160 as Precompile>::execute(&mut handle).expect("input within the limit");
+		let mut expected = new_handle(vec![0u8; 64]);
+		assert_eq!(
+			output.output,
+			<Ripemd160 as Precompile>::execute(&mut expected)
+				.unwrap()
+				.output
+		);
+		assert_eq!(handle.gas_used, 600 + 2 * 120);
+	}
 }
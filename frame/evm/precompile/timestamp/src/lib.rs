@@ -0,0 +1,73 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+// Substrate
+use frame_support::traits::{Get, Time};
+use sp_runtime::traits::UniqueSaturatedInto;
+// Frontier
+use fp_evm::{ExitSucceed, Precompile, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use pallet_evm::GasWeightMapping;
+
+/// Returns the current block timestamp with millisecond precision, along with the current block
+/// number, ABI encoded as `(uint256 timestampMillis, uint256 blockNumber)`.
+///
+/// The timestamp is read from `pallet_evm::Config::Timestamp`, usually `pallet-timestamp`. The
+/// `TIMESTAMP` opcode is left untouched and keeps returning seconds.
+pub struct Timestamp<T> {
+	_marker: PhantomData<T>,
+}
+
+impl<T: pallet_evm::Config> Precompile for Timestamp<T> {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Reads the timestamp and the block number.
+		let weight = T::DbWeight::get().reads(2);
+		handle.record_cost(T::GasWeightMapping::weight_to_gas(weight))?;
+
+		let now: u128 = T::Timestamp::now().unique_saturated_into();
+		let number: u128 = frame_system::Pallet::<T>::block_number().unique_saturated_into();
+
+		let mut output = Vec::with_capacity(64);
+		output.extend_from_slice(&encode_word(now));
+		output.extend_from_slice(&encode_word(number));
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			output,
+		})
+	}
+}
+
+/// Encode a value as a big-endian 32 bytes word.
+fn encode_word(value: u128) -> [u8; 32] {
+	let mut word = [0u8; 32];
+	word[16..].copy_from_slice(&value.to_be_bytes());
+	word
+}
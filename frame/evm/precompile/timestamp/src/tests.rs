@@ -0,0 +1,94 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::mock::*;
+
+use fp_evm::Context;
+use frame_support::weights::constants::RocksDbWeight;
+use sp_core::{H160, U256};
+use sp_runtime::BuildStorage;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::<Test>::default()
+		.build_storage()
+		.unwrap()
+		.into()
+}
+
+fn handle() -> MockHandle {
+	MockHandle {
+		input: Vec::new(),
+		context: Context {
+			address: H160::default(),
+			caller: H160::default(),
+			apparent_value: U256::default(),
+		},
+		gas_used: 0,
+	}
+}
+
+#[test]
+fn returns_timestamp_in_milliseconds_and_block_number() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(42);
+		pallet_timestamp::Now::<Test>::put(1_700_000_000_123u64);
+
+		let output = Timestamp::<Test>::execute(&mut handle()).expect("precompile succeeds");
+
+		assert_eq!(output.exit_status, ExitSucceed::Returned);
+		assert_eq!(output.output.len(), 64);
+		assert_eq!(
+			U256::from_big_endian(&output.output[0..32]),
+			U256::from(1_700_000_000_123u64)
+		);
+		assert_eq!(
+			U256::from_big_endian(&output.output[32..64]),
+			U256::from(42)
+		);
+	});
+}
+
+#[test]
+fn timestamp_keeps_sub_second_precision() {
+	new_test_ext().execute_with(|| {
+		pallet_timestamp::Now::<Test>::put(1_999u64);
+
+		let output = Timestamp::<Test>::execute(&mut handle()).expect("precompile succeeds");
+
+		// The `TIMESTAMP` opcode would return 1 here.
+		assert_eq!(
+			U256::from_big_endian(&output.output[0..32]),
+			U256::from(1_999)
+		);
+	});
+}
+
+#[test]
+fn charges_the_reads_of_the_timestamp_and_the_block_number() {
+	new_test_ext().execute_with(|| {
+		let mut handle = handle();
+
+		Timestamp::<Test>::execute(&mut handle).expect("precompile succeeds");
+
+		let expected = <Test as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+			RocksDbWeight::get().reads(2),
+		);
+		assert!(expected > 0);
+		assert_eq!(handle.gas_used, expected);
+	});
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: Apache-2.0
+pragma solidity >=0.8.3;
+
+/// @title WETH9-compatible wrapped native currency
+/// @dev The interface of the `pallet-evm-precompile-weth` precompile, wired at address
+/// 0x0000000000000000000000000000000000000800 in the template runtime.
+interface WrappedNative {
+    /// @dev Returns the name of the token.
+    /// @custom:selector 06fdde03
+    function name() external view returns (string memory);
+
+    /// @dev Returns the symbol of the token.
+    /// @custom:selector 95d89b41
+    function symbol() external view returns (string memory);
+
+    /// @dev Returns the decimals places of the token.
+    /// @custom:selector 313ce567
+    function decimals() external view returns (uint8);
+
+    /// @dev Returns the amount of wrapped native currency.
+    /// @custom:selector 18160ddd
+    function totalSupply() external view returns (uint256);
+
+    /// @dev Returns the wrapped balance of an account.
+    /// @custom:selector 70a08231
+    function balanceOf(address owner) external view returns (uint256);
+
+    /// @dev Returns the amount a spender is allowed to transfer on behalf of an owner.
+    /// @custom:selector dd62ed3e
+    function allowance(address owner, address spender)
+        external
+        view
+        returns (uint256);
+
+    /// @dev Wraps the sent native currency. Also called by plain transfers and unknown selectors.
+    /// @custom:selector d0e30db0
+    function deposit() external payable;
+
+    /// @dev Unwraps an amount of the caller's balance and sends it the native currency, with a
+    /// 2300 gas stipend.
+    /// @custom:selector 2e1a7d4d
+    function withdraw(uint256 wad) external;
+
+    /// @dev Allows a spender to transfer an amount on behalf of the caller.
+    /// @custom:selector 095ea7b3
+    function approve(address guy, uint256 wad) external returns (bool);
+
+    /// @dev Transfers an amount of the caller's balance.
+    /// @custom:selector a9059cbb
+    function transfer(address dst, uint256 wad) external returns (bool);
+
+    /// @dev Transfers an amount from an account, using the caller's allowance unless it is the
+    /// account itself or the allowance is the maximum `uint256`.
+    /// @custom:selector 23b872dd
+    function transferFrom(
+        address src,
+        address dst,
+        uint256 wad
+    ) external returns (bool);
+
+    /// @dev Emitted by `deposit`.
+    event Deposit(address indexed dst, uint256 wad);
+
+    /// @dev Emitted by `withdraw`.
+    event Withdrawal(address indexed src, uint256 wad);
+
+    /// @dev Emitted by `transfer` and `transferFrom`.
+    event Transfer(address indexed src, address indexed dst, uint256 wad);
+
+    /// @dev Emitted by `approve`.
+    event Approval(address indexed src, address indexed guy, uint256 wad);
+}
@@ -0,0 +1,396 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! WETH9-compatible wrapped native currency precompile.
+//!
+//! The precompile implements `deposit()`, `withdraw(uint256)` and the ERC-20 interface of the
+//! canonical WETH9 contract, and emits the same events, so contracts written against WETH9 can
+//! use it unchanged. The Solidity interface is in `WrappedNative.sol`.
+//!
+//! The wrapped balances are kept in the runtime storage. The wrapped native currency is held by
+//! the account of the precompile address: the EVM moves the value of a `deposit()` call to it,
+//! and `withdraw(uint256)` sends it back with a value transfer from it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_crate_dependencies)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+// Substrate
+use frame_support::{
+	storage::types::{StorageDoubleMap, StorageMap, StorageValue, ValueQuery},
+	traits::StorageInstance,
+	Blake2_128Concat,
+};
+use sp_core::{H160, U256};
+// Frontier
+use fp_evm::{Context, ExitReason, ExitRevert, PrecompileFailure, Transfer};
+use precompile_utils::prelude::*;
+
+/// `Deposit(address,uint256)`
+pub const SELECTOR_LOG_DEPOSIT: [u8; 32] = keccak256!("Deposit(address,uint256)");
+
+/// `Withdrawal(address,uint256)`
+pub const SELECTOR_LOG_WITHDRAWAL: [u8; 32] = keccak256!("Withdrawal(address,uint256)");
+
+/// `Transfer(address,address,uint256)`
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+
+/// `Approval(address,address,uint256)`
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+/// The gas forwarded to the recipient of a withdrawal, like Solidity's `transfer`: the EVM adds
+/// the 2300 gas stipend to calls transferring value, zero value calls get it explicitly.
+const WITHDRAWAL_STIPEND: u64 = 2300;
+
+/// 16 (hash) + 20 (key) + 32 (value)
+const BALANCE_PROOF_SIZE: usize = 68;
+/// 16 (hash1) + 20 (key1) + 16 (hash2) + 20 (key2) + 32 (value)
+const ALLOWANCE_PROOF_SIZE: usize = 104;
+/// 32 (value)
+const TOTAL_SUPPLY_PROOF_SIZE: usize = 32;
+
+/// The metadata of the wrapped native currency, configured by the runtime.
+pub trait WrappedNativeMetadata {
+	/// The name of the token, e.g. `Wrapped Ether`.
+	fn name() -> &'static str;
+	/// The symbol of the token, e.g. `WETH`.
+	fn symbol() -> &'static str;
+	/// The decimals of the token, usually the 18 decimals of the EVM native currency.
+	fn decimals() -> u8 {
+		18
+	}
+}
+
+/// The prefix of [`Balances`].
+pub struct BalancesPrefix;
+
+impl StorageInstance for BalancesPrefix {
+	const STORAGE_PREFIX: &'static str = "Balances";
+
+	fn pallet_prefix() -> &'static str {
+		"WrappedNative"
+	}
+}
+
+/// The prefix of [`Allowances`].
+pub struct AllowancesPrefix;
+
+impl StorageInstance for AllowancesPrefix {
+	const STORAGE_PREFIX: &'static str = "Allowances";
+
+	fn pallet_prefix() -> &'static str {
+		"WrappedNative"
+	}
+}
+
+/// The prefix of [`TotalSupply`].
+pub struct TotalSupplyPrefix;
+
+impl StorageInstance for TotalSupplyPrefix {
+	const STORAGE_PREFIX: &'static str = "TotalSupply";
+
+	fn pallet_prefix() -> &'static str {
+		"WrappedNative"
+	}
+}
+
+/// The wrapped balance of each address.
+pub type Balances = StorageMap<BalancesPrefix, Blake2_128Concat, H160, U256, ValueQuery>;
+
+/// The amount an owner allows a spender to transfer on its behalf.
+pub type Allowances = StorageDoubleMap<
+	AllowancesPrefix,
+	Blake2_128Concat,
+	H160,
+	Blake2_128Concat,
+	H160,
+	U256,
+	ValueQuery,
+>;
+
+/// The sum of the wrapped balances, which is also the native balance held for them.
+pub type TotalSupply = StorageValue<TotalSupplyPrefix, U256, ValueQuery>;
+
+/// WETH9-compatible wrapped native currency.
+///
+/// Unlike WETH9, `totalSupply()` only counts the deposits: native currency sent to the
+/// precompile address in other ways, e.g. by `SELFDESTRUCT`, is not wrapped.
+pub struct WrappedNative<Runtime, Metadata>(PhantomData<(Runtime, Metadata)>);
+
+#[precompile_utils::precompile]
+impl<Runtime, Metadata> WrappedNative<Runtime, Metadata>
+where
+	Runtime: pallet_evm::Config,
+	Metadata: WrappedNativeMetadata,
+{
+	#[precompile::pre_check]
+	fn pre_check(handle: &mut impl PrecompileHandle) -> EvmResult {
+		// The wrapped balances must only move with the native currency of the precompile.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("Cannot be called with DELEGATECALL or CALLCODE"));
+		}
+		Ok(())
+	}
+
+	#[precompile::public("name()")]
+	#[precompile::view]
+	fn name(_handle: &mut impl PrecompileHandle) -> EvmResult<UnboundedString> {
+		Ok(Metadata::name().as_bytes().into())
+	}
+
+	#[precompile::public("symbol()")]
+	#[precompile::view]
+	fn symbol(_handle: &mut impl PrecompileHandle) -> EvmResult<UnboundedString> {
+		Ok(Metadata::symbol().as_bytes().into())
+	}
+
+	#[precompile::public("decimals()")]
+	#[precompile::view]
+	fn decimals(_handle: &mut impl PrecompileHandle) -> EvmResult<u8> {
+		Ok(Metadata::decimals())
+	}
+
+	#[precompile::public("totalSupply()")]
+	#[precompile::view]
+	fn total_supply(handle: &mut impl PrecompileHandle) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(TOTAL_SUPPLY_PROOF_SIZE)?;
+		Ok(TotalSupply::get())
+	}
+
+	#[precompile::public("balanceOf(address)")]
+	#[precompile::view]
+	fn balance_of(handle: &mut impl PrecompileHandle, owner: Address) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(BALANCE_PROOF_SIZE)?;
+		Ok(Balances::get(H160::from(owner)))
+	}
+
+	#[precompile::public("allowance(address,address)")]
+	#[precompile::view]
+	fn allowance(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		spender: Address,
+	) -> EvmResult<U256> {
+		handle.record_db_read::<Runtime>(ALLOWANCE_PROOF_SIZE)?;
+		Ok(Allowances::get(H160::from(owner), H160::from(spender)))
+	}
+
+	#[precompile::public("deposit()")]
+	#[precompile::fallback]
+	#[precompile::payable]
+	fn deposit(handle: &mut impl PrecompileHandle) -> EvmResult {
+		handle.record_log_costs_manual(2, 32)?;
+
+		// The EVM already moved the value to the precompile address.
+		let dst = handle.context().caller;
+		let wad = handle.context().apparent_value;
+
+		Self::mint(handle, dst, wad)?;
+
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_DEPOSIT,
+			dst,
+			solidity::encode_event_data(wad),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("withdraw(uint256)")]
+	fn withdraw(handle: &mut impl PrecompileHandle, wad: U256) -> EvmResult {
+		handle.record_log_costs_manual(2, 32)?;
+
+		let this = handle.context().address;
+		let src = handle.context().caller;
+
+		// The balance is debited before sending the native currency, so the recipient can't
+		// withdraw it again if it calls back into the precompile.
+		Self::burn(handle, src, wad)?;
+
+		let gas = if wad.is_zero() { WITHDRAWAL_STIPEND } else { 0 };
+		let (reason, output) = handle.call(
+			src,
+			Some(Transfer {
+				source: this,
+				target: src,
+				value: wad,
+			}),
+			Vec::new(),
+			Some(gas),
+			false,
+			&Context {
+				address: src,
+				caller: this,
+				apparent_value: wad,
+			},
+		);
+		match reason {
+			ExitReason::Succeed(_) => (),
+			ExitReason::Fatal(exit_status) => return Err(PrecompileFailure::Fatal { exit_status }),
+			// Like Solidity, bubble up the output of the failed call. Reverting the precompile
+			// also reverts the debit.
+			ExitReason::Revert(_) | ExitReason::Error(_) => {
+				return Err(PrecompileFailure::Revert {
+					exit_status: ExitRevert::Reverted,
+					output,
+				})
+			}
+		}
+
+		log2(
+			this,
+			SELECTOR_LOG_WITHDRAWAL,
+			src,
+			solidity::encode_event_data(wad),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("approve(address,uint256)")]
+	fn approve(handle: &mut impl PrecompileHandle, guy: Address, wad: U256) -> EvmResult<bool> {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		let src = handle.context().caller;
+		let guy = H160::from(guy);
+		Allowances::insert(src, guy, wad);
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_APPROVAL,
+			src,
+			guy,
+			solidity::encode_event_data(wad),
+		)
+		.record(handle)?;
+
+		Ok(true)
+	}
+
+	#[precompile::public("transfer(address,uint256)")]
+	fn transfer(handle: &mut impl PrecompileHandle, dst: Address, wad: U256) -> EvmResult<bool> {
+		let src = handle.context().caller;
+		Self::transfer_from_to(handle, src, dst.into(), wad)?;
+		Ok(true)
+	}
+
+	#[precompile::public("transferFrom(address,address,uint256)")]
+	fn transfer_from(
+		handle: &mut impl PrecompileHandle,
+		src: Address,
+		dst: Address,
+		wad: U256,
+	) -> EvmResult<bool> {
+		let src = H160::from(src);
+		let caller = handle.context().caller;
+
+		// Like WETH9, the allowance is neither required to move one's own balance nor consumed
+		// when it is infinite.
+		if src != caller {
+			handle.record_db_read::<Runtime>(ALLOWANCE_PROOF_SIZE)?;
+			let allowance = Allowances::get(src, caller);
+			if allowance != U256::MAX {
+				let allowance = allowance
+					.checked_sub(wad)
+					.ok_or_else(|| revert("Insufficient allowance"))?;
+				handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+				Allowances::insert(src, caller, allowance);
+			}
+		}
+
+		Self::transfer_from_to(handle, src, dst.into(), wad)?;
+		Ok(true)
+	}
+}
+
+impl<Runtime, Metadata> WrappedNative<Runtime, Metadata>
+where
+	Runtime: pallet_evm::Config,
+	Metadata: WrappedNativeMetadata,
+{
+	fn mint(handle: &mut impl PrecompileHandle, dst: H160, wad: U256) -> EvmResult {
+		handle.record_db_read::<Runtime>(BALANCE_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(TOTAL_SUPPLY_PROOF_SIZE)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost() * 2)?;
+
+		let total_supply = TotalSupply::get()
+			.checked_add(wad)
+			.ok_or_else(|| revert("Total supply overflow"))?;
+		// The balance is bounded by the total supply.
+		Balances::mutate(dst, |balance| *balance = balance.saturating_add(wad));
+		TotalSupply::put(total_supply);
+		Ok(())
+	}
+
+	fn burn(handle: &mut impl PrecompileHandle, src: H160, wad: U256) -> EvmResult {
+		handle.record_db_read::<Runtime>(BALANCE_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(TOTAL_SUPPLY_PROOF_SIZE)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost() * 2)?;
+
+		let balance = Balances::get(src)
+			.checked_sub(wad)
+			.ok_or_else(|| revert("Insufficient balance"))?;
+		Balances::insert(src, balance);
+		// The total supply is at least the balance.
+		TotalSupply::mutate(|total_supply| *total_supply = total_supply.saturating_sub(wad));
+		Ok(())
+	}
+
+	fn transfer_from_to(
+		handle: &mut impl PrecompileHandle,
+		src: H160,
+		dst: H160,
+		wad: U256,
+	) -> EvmResult {
+		handle.record_db_read::<Runtime>(BALANCE_PROOF_SIZE)?;
+		handle.record_db_read::<Runtime>(BALANCE_PROOF_SIZE)?;
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost() * 2)?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		let src_balance = Balances::get(src)
+			.checked_sub(wad)
+			.ok_or_else(|| revert("Insufficient balance"))?;
+		Balances::insert(src, src_balance);
+		// The balance is bounded by the total supply.
+		Balances::mutate(dst, |balance| *balance = balance.saturating_add(wad));
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_TRANSFER,
+			src,
+			dst,
+			solidity::encode_event_data(wad),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+}
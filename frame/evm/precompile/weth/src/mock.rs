@@ -0,0 +1,200 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test mock for unit tests
+
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstBool, ConstU32, Nothing},
+	weights::Weight,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+
+use pallet_evm::{
+	EnsureAddressNever, EnsureAddressRoot, IdentityAddressMapping, IsPrecompileResult, Precompile,
+	PrecompileHandle, PrecompileResult, PrecompileSet,
+};
+use precompile_utils::testing::ExtBuilder;
+
+use crate::{WrappedNative, WrappedNativeMetadata};
+
+frame_support::construct_runtime! {
+	pub enum Test {
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage},
+		EVM: pallet_evm::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeTask = RuntimeTask;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = H160;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = frame_system::mocking::MockBlock<Self>;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 0;
+}
+impl pallet_balances::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type WeightInfo = ();
+	type Balance = u64;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type ReserveIdentifier = [u8; 8];
+	type FreezeIdentifier = RuntimeFreezeReason;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type MaxFreezes = ();
+	type DoneSlashHandler = ();
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1000;
+}
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+pub struct WrappedEther;
+impl WrappedNativeMetadata for WrappedEther {
+	fn name() -> &'static str {
+		"Wrapped Ether"
+	}
+
+	fn symbol() -> &'static str {
+		"WETH"
+	}
+}
+
+pub type PrecompileCall = crate::WrappedNativeCall<Test, WrappedEther>;
+
+/// The address of the precompile.
+pub fn weth() -> H160 {
+	H160::from_low_u64_be(2048)
+}
+
+pub struct MockPrecompiles;
+impl PrecompileSet for MockPrecompiles {
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		if handle.code_address() == weth() {
+			Some(WrappedNative::<Test, WrappedEther>::execute(handle))
+		} else {
+			None
+		}
+	}
+
+	fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
+		IsPrecompileResult::Answer {
+			is_precompile: address == weth(),
+			extra_cost: 0,
+		}
+	}
+}
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::max_value();
+	pub WeightPerGas: Weight = Weight::from_parts(20_000, 0);
+	pub MockPrecompilesValue: MockPrecompiles = MockPrecompiles;
+}
+impl pallet_evm::Config for Test {
+	type AccountProvider = pallet_evm::FrameSystemAccountProvider<Self>;
+	type FeeCalculator = ();
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+
+	type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressRoot<Self::AccountId>;
+
+	type WithdrawOrigin = EnsureAddressNever<Self::AccountId>;
+	type AddressMapping = IdentityAddressMapping;
+	type Currency = Balances;
+
+	type RuntimeEvent = RuntimeEvent;
+	type PrecompilesType = MockPrecompiles;
+	type PrecompilesValue = MockPrecompilesValue;
+	type ReservedAddresses = Nothing;
+	type ChainId = ();
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction = ();
+	type OnCreate = ();
+	type FindAuthor = ();
+	type GasLimitPovSizeRatio = ();
+	type GasLimitStorageGrowthRatio = ();
+	type Timestamp = Timestamp;
+	type CreateInnerOriginFilter = ();
+	type ExecutionEvents = ConstBool<false>;
+	type ExecutionEventReasonLength = ConstU32<256>;
+	type CreateOriginFilter = ();
+	type WeightInfo = ();
+}
+
+pub fn alice() -> H160 {
+	H160::repeat_byte(0xaa)
+}
+
+pub fn bob() -> H160 {
+	H160::repeat_byte(0xbb)
+}
+
+pub const INITIAL_BALANCE: u64 = 1_000_000;
+
+pub fn ext_builder() -> ExtBuilder<Test> {
+	ExtBuilder::default().with_balances(vec![
+		(alice(), INITIAL_BALANCE.into()),
+		(bob(), INITIAL_BALANCE.into()),
+	])
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	ext_builder().build()
+}
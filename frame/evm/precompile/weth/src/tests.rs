@@ -0,0 +1,322 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use sp_core::{H160, U256};
+
+use fp_evm::{CallInfo, ExitReason, ExitRevert, ExitSucceed, Log};
+use pallet_evm::Runner;
+use precompile_utils::{
+	prelude::{log0, log2, log3, solidity, Address, UnboundedString},
+	testing::check_precompile_implements_solidity_interfaces,
+};
+
+use crate::{
+	mock::*, Allowances, TotalSupply, SELECTOR_LOG_APPROVAL, SELECTOR_LOG_DEPOSIT,
+	SELECTOR_LOG_TRANSFER, SELECTOR_LOG_WITHDRAWAL,
+};
+
+// Forwards its call data and value to the precompile at 0x0800, reverting if the call fails.
+// When called by the precompile, i.e. on withdrawals, emits an empty `LOG0`.
+const RECEIVER_CONTRACT_CODE: [u8; 44] = hex_literal::hex!(
+	"336108001460255736600060003760006000366000346108005af160235760006000fd5b005b60006000a000"
+);
+
+// Same as the receiver contract, but reverts when called by the precompile.
+const REJECTER_CONTRACT_CODE: [u8; 44] = hex_literal::hex!(
+	"336108001460255736600060003760006000366000346108005af160235760006000fd5b005b60006000fd00"
+);
+
+fn call(source: H160, target: H160, input: impl Into<Vec<u8>>, value: u64) -> CallInfo {
+	<Test as pallet_evm::Config>::Runner::call(
+		source,
+		target,
+		input.into(),
+		value.into(),
+		1_000_000,
+		Some(U256::zero()),
+		None,
+		None,
+		Vec::new(),
+		Vec::new(),
+		true,  // transactional
+		false, // must be validated
+		None,
+		None,
+		<Test as pallet_evm::Config>::config(),
+	)
+	.expect("call succeeds")
+}
+
+fn native_balance(address: H160) -> u64 {
+	pallet_balances::Pallet::<Test>::free_balance(address)
+}
+
+fn wrapped_balance(address: H160) -> U256 {
+	crate::Balances::get(address)
+}
+
+fn deposit_log(dst: H160, wad: u64) -> Log {
+	log2(
+		weth(),
+		SELECTOR_LOG_DEPOSIT,
+		dst,
+		solidity::encode_event_data(U256::from(wad)),
+	)
+}
+
+fn withdrawal_log(src: H160, wad: u64) -> Log {
+	log2(
+		weth(),
+		SELECTOR_LOG_WITHDRAWAL,
+		src,
+		solidity::encode_event_data(U256::from(wad)),
+	)
+}
+
+fn succeeded() -> ExitReason {
+	ExitReason::Succeed(ExitSucceed::Returned)
+}
+
+#[test]
+fn implements_solidity_interface() {
+	check_precompile_implements_solidity_interfaces(
+		&["WrappedNative.sol"],
+		PrecompileCall::supports_selector,
+	)
+}
+
+#[test]
+fn metadata_is_configured_by_the_runtime() {
+	new_test_ext().execute_with(|| {
+		let info = call(alice(), weth(), PrecompileCall::name {}, 0);
+		assert_eq!(
+			info.value,
+			solidity::encode_return_value(UnboundedString::from("Wrapped Ether".as_bytes()))
+		);
+
+		let info = call(alice(), weth(), PrecompileCall::symbol {}, 0);
+		assert_eq!(
+			info.value,
+			solidity::encode_return_value(UnboundedString::from("WETH".as_bytes()))
+		);
+
+		let info = call(alice(), weth(), PrecompileCall::decimals {}, 0);
+		assert_eq!(info.value, solidity::encode_return_value(18u8));
+	});
+}
+
+#[test]
+fn deposit_transfer_withdraw_round_trip() {
+	new_test_ext().execute_with(|| {
+		let info = call(alice(), weth(), PrecompileCall::deposit {}, 1000);
+		assert_eq!(info.exit_reason, succeeded());
+		assert_eq!(info.logs, vec![deposit_log(alice(), 1000)]);
+		assert_eq!(native_balance(alice()), INITIAL_BALANCE - 1000);
+		assert_eq!(native_balance(weth()), 1000);
+		assert_eq!(wrapped_balance(alice()), U256::from(1000));
+
+		let info = call(
+			alice(),
+			weth(),
+			PrecompileCall::transfer {
+				dst: Address(bob()),
+				wad: 400.into(),
+			},
+			0,
+		);
+		assert_eq!(info.value, solidity::encode_return_value(true));
+		assert_eq!(
+			info.logs,
+			vec![log3(
+				weth(),
+				SELECTOR_LOG_TRANSFER,
+				alice(),
+				bob(),
+				solidity::encode_event_data(U256::from(400)),
+			)]
+		);
+		assert_eq!(wrapped_balance(alice()), U256::from(600));
+		assert_eq!(wrapped_balance(bob()), U256::from(400));
+
+		let info = call(
+			bob(),
+			weth(),
+			PrecompileCall::withdraw { wad: 400.into() },
+			0,
+		);
+		assert_eq!(info.exit_reason, succeeded());
+		assert_eq!(info.logs, vec![withdrawal_log(bob(), 400)]);
+		assert_eq!(native_balance(bob()), INITIAL_BALANCE + 400);
+		assert_eq!(native_balance(weth()), 600);
+		assert_eq!(wrapped_balance(bob()), U256::zero());
+
+		let info = call(bob(), weth(), PrecompileCall::total_supply {}, 0);
+		assert_eq!(info.value, solidity::encode_return_value(U256::from(600)));
+	});
+}
+
+#[test]
+fn plain_value_transfer_deposits() {
+	new_test_ext().execute_with(|| {
+		let info = call(alice(), weth(), Vec::<u8>::new(), 1000);
+		assert_eq!(info.exit_reason, succeeded());
+		assert_eq!(info.logs, vec![deposit_log(alice(), 1000)]);
+		assert_eq!(wrapped_balance(alice()), U256::from(1000));
+		assert_eq!(TotalSupply::get(), U256::from(1000));
+	});
+}
+
+#[test]
+fn withdraw_more_than_balance_reverts() {
+	new_test_ext().execute_with(|| {
+		call(alice(), weth(), PrecompileCall::deposit {}, 1000);
+
+		let info = call(
+			alice(),
+			weth(),
+			PrecompileCall::withdraw { wad: 1001.into() },
+			0,
+		);
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert!(info.logs.is_empty());
+		assert_eq!(wrapped_balance(alice()), U256::from(1000));
+		assert_eq!(native_balance(weth()), 1000);
+	});
+}
+
+#[test]
+fn withdraw_to_contract_runs_its_receive() {
+	let receiver = H160::repeat_byte(0xcc);
+	ext_builder()
+		.with_evm_account(receiver, RECEIVER_CONTRACT_CODE.to_vec(), BTreeMap::new())
+		.build()
+		.execute_with(|| {
+			let info = call(alice(), receiver, PrecompileCall::deposit {}, 1000);
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+			assert_eq!(info.logs, vec![deposit_log(receiver, 1000)]);
+			assert_eq!(wrapped_balance(receiver), U256::from(1000));
+
+			let info = call(
+				alice(),
+				receiver,
+				PrecompileCall::withdraw { wad: 1000.into() },
+				0,
+			);
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+			// The receive of the contract runs during the withdrawal.
+			assert_eq!(
+				info.logs,
+				vec![
+					log0(receiver, Vec::<u8>::new()),
+					withdrawal_log(receiver, 1000)
+				]
+			);
+			assert_eq!(native_balance(receiver), 1000);
+			assert_eq!(native_balance(weth()), 0);
+			assert_eq!(wrapped_balance(receiver), U256::zero());
+			assert_eq!(TotalSupply::get(), U256::zero());
+		});
+}
+
+#[test]
+fn rejected_withdrawal_keeps_the_wrapped_balance() {
+	let rejecter = H160::repeat_byte(0xdd);
+	ext_builder()
+		.with_evm_account(rejecter, REJECTER_CONTRACT_CODE.to_vec(), BTreeMap::new())
+		.build()
+		.execute_with(|| {
+			call(alice(), rejecter, PrecompileCall::deposit {}, 1000);
+
+			let info = call(
+				alice(),
+				rejecter,
+				PrecompileCall::withdraw { wad: 1000.into() },
+				0,
+			);
+			assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+			assert!(info.logs.is_empty());
+			assert_eq!(native_balance(rejecter), 0);
+			assert_eq!(native_balance(weth()), 1000);
+			assert_eq!(wrapped_balance(rejecter), U256::from(1000));
+			assert_eq!(TotalSupply::get(), U256::from(1000));
+		});
+}
+
+#[test]
+fn transfer_from_spends_the_allowance() {
+	new_test_ext().execute_with(|| {
+		let transfer_from = |wad: U256| PrecompileCall::transfer_from {
+			src: Address(alice()),
+			dst: Address(bob()),
+			wad,
+		};
+		call(alice(), weth(), PrecompileCall::deposit {}, 1000);
+
+		let info = call(
+			alice(),
+			weth(),
+			PrecompileCall::approve {
+				guy: Address(bob()),
+				wad: 300.into(),
+			},
+			0,
+		);
+		assert_eq!(info.value, solidity::encode_return_value(true));
+		assert_eq!(
+			info.logs,
+			vec![log3(
+				weth(),
+				SELECTOR_LOG_APPROVAL,
+				alice(),
+				bob(),
+				solidity::encode_event_data(U256::from(300)),
+			)]
+		);
+
+		let info = call(bob(), weth(), transfer_from(200.into()), 0);
+		assert_eq!(info.value, solidity::encode_return_value(true));
+		assert_eq!(Allowances::get(alice(), bob()), U256::from(100));
+		assert_eq!(wrapped_balance(bob()), U256::from(200));
+
+		let info = call(bob(), weth(), transfer_from(200.into()), 0);
+		assert_eq!(info.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+		assert_eq!(Allowances::get(alice(), bob()), U256::from(100));
+
+		// The owner doesn't need an allowance.
+		let info = call(alice(), weth(), transfer_from(100.into()), 0);
+		assert_eq!(info.value, solidity::encode_return_value(true));
+		assert_eq!(wrapped_balance(bob()), U256::from(300));
+
+		// An infinite allowance isn't consumed.
+		call(
+			alice(),
+			weth(),
+			PrecompileCall::approve {
+				guy: Address(bob()),
+				wad: U256::MAX,
+			},
+			0,
+		);
+		let info = call(bob(), weth(), transfer_from(700.into()), 0);
+		assert_eq!(info.value, solidity::encode_return_value(true));
+		assert_eq!(Allowances::get(alice(), bob()), U256::MAX);
+		assert_eq!(wrapped_balance(alice()), U256::zero());
+		assert_eq!(wrapped_balance(bob()), U256::from(1000));
+	});
+}
@@ -15,10 +15,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 use super::*;
+use alloc::vec;
 use frame_benchmarking::benchmarks;
 
 type CurrencyOf<T> = <T as Config>::Currency;
 
+/// The maximum size of deployed code, as of EIP-170.
+const MAX_CODE_SIZE: u32 = 24 * 1024;
+
+/// The largest precompile input measured, in 32 bytes words.
+const MAX_PRECOMPILE_INPUT_WORDS: u32 = 1024;
+
+/// The most Blake2 compression rounds measured.
+const MAX_BLAKE2F_ROUNDS: u32 = 10_000;
+
 benchmarks! {
 	withdraw {
 		let caller = frame_benchmarking::whitelisted_caller::<T::AccountId>();
@@ -31,6 +41,96 @@ benchmarks! {
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err(), sp_runtime::DispatchError::BadOrigin);
 	}
+
+	account_storages_read {
+		let address = H160::from_low_u64_le(1);
+		let index = H256::from_low_u64_le(1);
+		<AccountStorages<T>>::insert(address, index, H256::repeat_byte(1));
+	}: {
+		assert_eq!(<AccountStorages<T>>::get(address, index), H256::repeat_byte(1));
+	}
+
+	account_storages_write {
+		let address = H160::from_low_u64_le(1);
+		let index = H256::from_low_u64_le(1);
+	}: {
+		<AccountStorages<T>>::insert(address, index, H256::repeat_byte(1));
+	}
+	verify {
+		assert_eq!(<AccountStorages<T>>::get(address, index), H256::repeat_byte(1));
+	}
+
+	account_codes_read {
+		let b in 0 .. MAX_CODE_SIZE;
+		let address = H160::from_low_u64_le(1);
+		<AccountCodes<T>>::insert(address, vec![0u8; b as usize]);
+	}: {
+		assert_eq!(<AccountCodes<T>>::get(address).len(), b as usize);
+	}
+
+	account_codes_write {
+		let b in 0 .. MAX_CODE_SIZE;
+		let address = H160::from_low_u64_le(1);
+		let code = vec![0u8; b as usize];
+	}: {
+		Pallet::<T>::create_account(address, code, None).expect("the code is deployed");
+	}
+	verify {
+		assert_eq!(<AccountCodes<T>>::decode_len(address).unwrap_or(0), b as usize);
+	}
+
+	account_nonce_read {
+		let account_id = T::AddressMapping::into_account_id(H160::from_low_u64_le(1));
+		T::AccountProvider::inc_account_nonce(&account_id);
+	}: {
+		assert!(!T::AccountProvider::account_nonce(&account_id).is_zero());
+	}
+
+	account_nonce_inc {
+		let account_id = T::AddressMapping::into_account_id(H160::from_low_u64_le(1));
+	}: {
+		T::AccountProvider::inc_account_nonce(&account_id);
+	}
+	verify {
+		assert!(!T::AccountProvider::account_nonce(&account_id).is_zero());
+	}
+
+	// The precompile benchmarks measure the ref time for `RefTimePerInputWord` and
+	// `MeteredBlake2F`. Only the hashing is measured, the gas is charged by the precompiles.
+	precompile_identity {
+		let w in 0 .. MAX_PRECOMPILE_INPUT_WORDS;
+		let input = vec![0u8; 32 * w as usize];
+	}: {
+		assert!(<pallet_evm_precompile_simple::Identity as LinearCostPrecompile>::execute(&input, 0).is_ok());
+	}
+
+	precompile_ripemd160 {
+		let w in 0 .. MAX_PRECOMPILE_INPUT_WORDS;
+		let input = vec![0u8; 32 * w as usize];
+	}: {
+		assert!(<pallet_evm_precompile_simple::Ripemd160 as LinearCostPrecompile>::execute(&input, 0).is_ok());
+	}
+
+	precompile_sha3_fips_256 {
+		let w in 0 .. MAX_PRECOMPILE_INPUT_WORDS;
+		let input = vec![0u8; 32 * w as usize];
+	}: {
+		assert!(<pallet_evm_precompile_sha3fips::Sha3FIPS256 as LinearCostPrecompile>::execute(&input, 0).is_ok());
+	}
+
+	precompile_sha3_fips_512 {
+		let w in 0 .. MAX_PRECOMPILE_INPUT_WORDS;
+		let input = vec![0u8; 32 * w as usize];
+	}: {
+		assert!(<pallet_evm_precompile_sha3fips::Sha3FIPS512 as LinearCostPrecompile>::execute(&input, 0).is_ok());
+	}
+
+	precompile_blake2f {
+		let r in 0 .. MAX_BLAKE2F_ROUNDS;
+		let mut h = [0u64; 8];
+	}: {
+		pallet_evm_precompile_blake2::eip_152::compress(&mut h, [0u64; 16], [0u64; 2], false, r as usize);
+	}
 }
 
 // impl_benchmark_test_suite!(Pallet, crate::tests::new_test_ext(), crate::mock::Test);
@@ -60,6 +60,7 @@ extern crate alloc;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 pub mod runner;
@@ -89,24 +90,24 @@ use frame_support::{
 			imbalance::{Imbalance, OnUnbalanced, SignedImbalance},
 			ExistenceRequirement, Fortitude, Precision, Preservation, WithdrawReasons,
 		},
-		FindAuthor, Get, Time,
+		Contains, FindAuthor, Get, Time,
 	},
 	weights::Weight,
 };
 use frame_system::RawOrigin;
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
-	traits::{BadOrigin, NumberFor, Saturating, UniqueSaturatedInto, Zero},
+	traits::{BadOrigin, CheckedAdd, NumberFor, Saturating, UniqueSaturatedInto, Zero},
 	AccountId32, DispatchErrorWithPostInfo,
 };
 // Frontier
 use fp_account::AccountId20;
 use fp_evm::GenesisAccount;
 pub use fp_evm::{
-	Account, AccountProvider, CallInfo, CreateInfo, ExecutionInfoV2 as ExecutionInfo,
-	FeeCalculator, IsPrecompileResult, LinearCostPrecompile, Log, Precompile, PrecompileFailure,
-	PrecompileHandle, PrecompileOutput, PrecompileResult, PrecompileSet,
-	TransactionValidationError, Vicinity,
+	Account, AccountProvider, BoundedInputPrecompile, CallInfo, CreateInfo,
+	ExecutionInfoV2 as ExecutionInfo, FeeCalculator, IsPrecompileResult, LinearCostPrecompile, Log,
+	Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+	PrecompileSet, PrecompileSetList, RefTimePerInputWord, TransactionValidationError, Vicinity,
 };
 
 pub use self::{
@@ -121,7 +122,11 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -149,10 +154,14 @@ pub mod pallet {
 		type CallOrigin: EnsureAddressOrigin<Self::RuntimeOrigin>;
 
 		/// Allow the source address to deploy contracts directly via CREATE calls.
+		/// A rejected deployment fails with `Error::CreateOriginNotAllowed`.
 		#[pallet::no_default_bounds]
 		type CreateOriginFilter: EnsureCreateOrigin<Self>;
 
 		/// Allow the source address to deploy contracts via CALL(CREATE) calls.
+		/// Checked independently of `CreateOriginFilter`, against the transaction origin, so an
+		/// address can be allowed to deploy through a factory contract only. A rejected
+		/// deployment makes the inner CREATE fail.
 		#[pallet::no_default_bounds]
 		type CreateInnerOriginFilter: EnsureCreateOrigin<Self>;
 
@@ -176,6 +185,10 @@ pub mod pallet {
 		type PrecompilesType: PrecompileSet;
 		type PrecompilesValue: Get<Self::PrecompilesType>;
 
+		/// Addresses where no contract can be deployed, e.g. `PrecompileReservedAddresses` so that
+		/// no contract shadows a current or future precompile.
+		type ReservedAddresses: Contains<H160>;
+
 		/// Chain ID of EVM.
 		type ChainId: Get<u64>;
 		/// The block gas limit. Can be a simple constant, or an adjustment algorithm in another pallet.
@@ -208,10 +221,25 @@ pub mod pallet {
 		#[pallet::no_default]
 		type Timestamp: Time;
 
+		/// Whether the runner deposits an `ExecutionSucceeded`, `ExecutionReverted` or
+		/// `ExecutionFailed` event for each execution. They come in addition to the `Executed`,
+		/// `ExecutedFailed`, `Created` and `CreatedFailed` events of the calls of this pallet.
+		type ExecutionEvents: Get<bool>;
+
+		/// The maximum length of the revert data kept in the `ExecutionReverted` event.
+		type ExecutionEventReasonLength: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
 		/// EVM config used in the module.
+		///
+		/// The config also selects the `SELFDESTRUCT` semantics. With EIP-6780 (Cancun and later
+		/// configs, including the default one), a contract is only deleted when it was created in
+		/// the same transaction, otherwise only its balance is transferred. With earlier configs,
+		/// the contract is always deleted. A deleted contract is removed at the end of the
+		/// transaction: calls within the same transaction still run its code, while calls from
+		/// the following transactions, including in the same block, succeed with no output.
 		fn config() -> &'static EvmConfig {
 			&PECTRA_CONFIG
 		}
@@ -220,7 +248,11 @@ pub mod pallet {
 	pub mod config_preludes {
 		use super::*;
 		use core::str::FromStr;
-		use frame_support::{derive_impl, parameter_types, ConsensusEngineId};
+		use frame_support::{
+			derive_impl, parameter_types,
+			traits::{ConstBool, ConstU32, Nothing},
+			ConsensusEngineId,
+		};
 		use sp_runtime::traits::BlakeTwo256;
 
 		pub struct TestDefaultConfig;
@@ -256,6 +288,7 @@ pub mod pallet {
 			type RuntimeEvent = ();
 			type PrecompilesType = ();
 			type PrecompilesValue = ();
+			type ReservedAddresses = Nothing;
 			type ChainId = ChainId;
 			type BlockGasLimit = BlockGasLimit;
 			type OnChargeTransaction = ();
@@ -265,6 +298,8 @@ pub mod pallet {
 			type GasLimitStorageGrowthRatio = GasLimitStorageGrowthRatio;
 			type CreateOriginFilter = ();
 			type CreateInnerOriginFilter = ();
+			type ExecutionEvents = ConstBool<false>;
+			type ExecutionEventReasonLength = ConstU32<256>;
 			type WeightInfo = ();
 		}
 
@@ -320,6 +355,7 @@ pub mod pallet {
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
 			T::GasWeightMapping::gas_to_weight(*gas_limit, without_base_extrinsic_weight)
+				.saturating_add(Pallet::<T>::execution_event_weight())
 		})]
 		pub fn call(
 			origin: OriginFor<T>,
@@ -387,7 +423,7 @@ pub mod pallet {
 							*gas_to_weight.proof_size_mut() = proof_size_usage;
 						}
 					}
-					Some(gas_to_weight)
+					Some(gas_to_weight.saturating_add(Pallet::<T>::execution_event_weight()))
 				},
 				pays_fee: Pays::No,
 			})
@@ -399,6 +435,7 @@ pub mod pallet {
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
 			T::GasWeightMapping::gas_to_weight(*gas_limit, without_base_extrinsic_weight)
+				.saturating_add(Pallet::<T>::execution_event_weight())
 		})]
 		pub fn create(
 			origin: OriginFor<T>,
@@ -476,7 +513,7 @@ pub mod pallet {
 							*gas_to_weight.proof_size_mut() = proof_size_usage;
 						}
 					}
-					Some(gas_to_weight)
+					Some(gas_to_weight.saturating_add(Pallet::<T>::execution_event_weight()))
 				},
 				pays_fee: Pays::No,
 			})
@@ -487,6 +524,7 @@ pub mod pallet {
 		#[pallet::weight({
 			let without_base_extrinsic_weight = true;
 			T::GasWeightMapping::gas_to_weight(*gas_limit, without_base_extrinsic_weight)
+				.saturating_add(Pallet::<T>::execution_event_weight())
 		})]
 		pub fn create2(
 			origin: OriginFor<T>,
@@ -566,7 +604,7 @@ pub mod pallet {
 							*gas_to_weight.proof_size_mut() = proof_size_usage;
 						}
 					}
-					Some(gas_to_weight)
+					Some(gas_to_weight.saturating_add(Pallet::<T>::execution_event_weight()))
 				},
 				pays_fee: Pays::No,
 			})
@@ -586,6 +624,26 @@ pub mod pallet {
 		Executed { address: H160 },
 		/// A contract has been executed with errors. States are reverted with only gas fees applied.
 		ExecutedFailed { address: H160 },
+		/// An EVM execution succeeded. `to` is the called contract, or the created one.
+		ExecutionSucceeded {
+			from: H160,
+			to: H160,
+			gas_used: U256,
+		},
+		/// An EVM execution reverted, with its revert data truncated to
+		/// `ExecutionEventReasonLength` bytes.
+		ExecutionReverted {
+			from: H160,
+			to: H160,
+			gas_used: U256,
+			reason: Vec<u8>,
+		},
+		/// An EVM execution failed with an error.
+		ExecutionFailed {
+			from: H160,
+			to: H160,
+			error: ExitReason,
+		},
 	}
 
 	#[pallet::error]
@@ -643,6 +701,9 @@ pub mod pallet {
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T> {
+		/// EVM accounts to fund or predeploy at genesis, with their balance, nonce, code and
+		/// storage. The code of each account must fit in the contract size limit of the EVM
+		/// config.
 		pub accounts: BTreeMap<H160, GenesisAccount>,
 		#[serde(skip)]
 		pub _marker: PhantomData<T>,
@@ -656,7 +717,33 @@ pub mod pallet {
 		fn build(&self) {
 			const MAX_ACCOUNT_NONCE: usize = 100;
 
+			// The balances are converted with saturation, make sure they are minted in full.
+			let mut total_issuance = T::Currency::total_issuance();
 			for (address, account) in &self.accounts {
+				if let Some(limit) = T::config().create_contract_limit {
+					assert!(
+						account.code.len() <= limit,
+						"Genesis code of {:?} exceeds the contract size limit of {} bytes",
+						address,
+						limit,
+					);
+				}
+
+				let balance: BalanceOf<T> = account.balance.unique_saturated_into();
+				assert!(
+					U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(balance))
+						== account.balance,
+					"Genesis balance of {:?} overflows the balance type: {}",
+					address,
+					account.balance,
+				);
+				total_issuance = total_issuance.checked_add(&balance).unwrap_or_else(|| {
+					panic!(
+						"Genesis balance of {:?} overflows the total issuance of the currency",
+						address
+					)
+				});
+
 				let account_id = T::AddressMapping::into_account_id(*address);
 
 				// ASSUME: in one single EVM transaction, the nonce will not increase more than
@@ -668,10 +755,7 @@ pub mod pallet {
 					T::AccountProvider::inc_account_nonce(&account_id);
 				}
 
-				let _ = T::Currency::deposit_creating(
-					&account_id,
-					account.balance.unique_saturated_into(),
-				);
+				let _ = T::Currency::deposit_creating(&account_id, balance);
 
 				let _ = Pallet::<T>::create_account(*address, account.code.clone(), None);
 
@@ -855,6 +939,18 @@ impl<T> EnsureCreateOrigin<T> for () {
 	}
 }
 
+/// The address ranges reserved by the precompiles of the runtime.
+pub struct PrecompileReservedAddresses<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> Contains<H160> for PrecompileReservedAddresses<T>
+where
+	T::PrecompilesType: PrecompileSetList,
+{
+	fn contains(address: &H160) -> bool {
+		T::PrecompilesValue::get().is_reserved_address(*address)
+	}
+}
+
 /// Trait to be implemented for evm address mapping.
 pub trait AddressMapping<A> {
 	fn into_account_id(address: H160) -> A;
@@ -946,6 +1042,22 @@ where
 static PECTRA_CONFIG: EvmConfig = EvmConfig::pectra();
 
 impl<T: Config> Pallet<T> {
+	/// The weight of depositing the execution event of the runner: zero unless
+	/// `T::ExecutionEvents` is set, else the writes of the event of the call, with an address, and
+	/// of an execution event with the longest revert data.
+	pub fn execution_event_weight() -> Weight {
+		if !T::ExecutionEvents::get() {
+			return Weight::zero();
+		}
+		// The address of `Executed`, `ExecutedFailed`, `Created` or `CreatedFailed`.
+		let call_event_size = 20;
+		// `from`, `to`, `gas_used` and the revert data with its length prefix.
+		let max_event_size = 20 + 20 + 32 + 5 + u64::from(T::ExecutionEventReasonLength::get());
+		T::DbWeight::get()
+			.writes(2)
+			.saturating_add(Weight::from_parts(0, call_event_size + max_event_size))
+	}
+
 	/// Check whether an account is empty.
 	pub fn is_account_empty(address: &H160) -> bool {
 		let (account, _) = Self::account_basic(address);
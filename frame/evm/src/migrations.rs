@@ -0,0 +1,274 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the EVM pallet.
+
+#[cfg(feature = "try-runtime")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use scale_codec::{Decode, Encode};
+// Substrate
+use frame_support::{
+	migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+	traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::{Weight, WeightMeter},
+};
+use sp_core::H160;
+
+use crate::{AccountCodes, AccountCodesMetadata, CodeMetadata, Config, Pallet};
+
+/// Migrates the EVM pallet storage from version 0 to version 1.
+///
+/// In version 0 the code metadata (size and hash) of an account was computed lazily and only
+/// cached in `AccountCodesMetadata` on first access. Version 1 guarantees every entry in
+/// `AccountCodes` has a matching `AccountCodesMetadata` entry, so the proof size of code reads
+/// can always be known before the code itself is read.
+///
+/// The whole of `AccountCodes` is read within the upgrade block, so the returned weight grows
+/// linearly with the number of contracts: two reads per contract and one write per missing
+/// metadata entry, plus the proof size of every code. Chains that cannot fit this in a single
+/// block should run [`LazyMigrateV0ToV1`] through `pallet-migrations` instead.
+#[doc = "storage: V0 -> V1 migration"]
+pub struct MigrateV0ToV1<T>(PhantomData<T>);
+
+impl<T: Config> OnRuntimeUpgrade for MigrateV0ToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weights = T::DbWeight::get();
+
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version != 0 {
+			log::info!(
+				target: "evm",
+				"Skipping V0 -> V1 migration, on-chain storage version is {:?}",
+				on_chain_version,
+			);
+			return db_weights.reads(1);
+		}
+
+		let mut reads: u64 = 1;
+		let mut writes: u64 = 0;
+		for (address, code) in <AccountCodes<T>>::iter() {
+			reads = reads.saturating_add(2);
+			if !<AccountCodesMetadata<T>>::contains_key(address) {
+				<AccountCodesMetadata<T>>::insert(address, CodeMetadata::from_code(&code));
+				writes = writes.saturating_add(1);
+			}
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		writes = writes.saturating_add(1);
+
+		log::info!(
+			target: "evm",
+			"V0 -> V1 migration populated {} code metadata entries",
+			writes.saturating_sub(1),
+		);
+
+		db_weights.reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		let codes_count = <AccountCodes<T>>::iter_keys().count() as u64;
+		Ok(codes_count.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let codes_count: u64 = Decode::decode(&mut state.as_slice())
+			.map_err(|_| "the state parameter should be generated by pre_upgrade")?;
+
+		frame_support::ensure!(
+			Pallet::<T>::on_chain_storage_version() == 1,
+			"storage version was not updated to V1"
+		);
+
+		let mut migrated_count: u64 = 0;
+		for (address, code) in <AccountCodes<T>>::iter() {
+			frame_support::ensure!(
+				<AccountCodesMetadata<T>>::get(address) == Some(CodeMetadata::from_code(&code)),
+				"account code metadata is missing or does not match the account code"
+			);
+			migrated_count = migrated_count.saturating_add(1);
+		}
+
+		frame_support::ensure!(
+			migrated_count == codes_count,
+			"number of account codes changed during the migration"
+		);
+		Ok(())
+	}
+}
+
+/// Multi-block version of [`MigrateV0ToV1`], to be run by `pallet-migrations`.
+///
+/// Each step migrates as many contracts as fit in the given weight, resuming after the address
+/// of the last migrated contract. The storage version is only set to 1 once every contract has
+/// been migrated.
+pub struct LazyMigrateV0ToV1<T>(PhantomData<T>);
+
+impl<T: Config> LazyMigrateV0ToV1<T> {
+	/// Worst case weight of migrating a single contract.
+	///
+	/// The proof size assumes the largest code allowed by the EVM config, falling back to the
+	/// EIP-170 limit when the config has none.
+	pub fn contract_weight() -> Weight {
+		let max_code_size = T::config().create_contract_limit.unwrap_or(0x6000) as u64;
+		T::DbWeight::get()
+			.reads_writes(2, 1)
+			.saturating_add(Weight::from_parts(0, max_code_size))
+	}
+}
+
+impl<T: Config> SteppedMigration for LazyMigrateV0ToV1<T> {
+	type Cursor = H160;
+	type Identifier = MigrationId<10>;
+
+	fn id() -> Self::Identifier {
+		MigrationId {
+			pallet_id: *b"pallet-evm",
+			version_from: 0,
+			version_to: 1,
+		}
+	}
+
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+		let required = Self::contract_weight().saturating_add(T::DbWeight::get().reads(1));
+		if meter.remaining().any_lt(required) {
+			return Err(SteppedMigrationError::InsufficientWeight { required });
+		}
+
+		if cursor.is_none() {
+			meter.consume(T::DbWeight::get().reads(1));
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return Ok(None);
+			}
+		}
+
+		let mut iter = match cursor {
+			Some(last) => <AccountCodes<T>>::iter_from(<AccountCodes<T>>::hashed_key_for(last)),
+			None => <AccountCodes<T>>::iter(),
+		};
+		let mut last = cursor;
+		while meter.try_consume(Self::contract_weight()).is_ok() {
+			let Some((address, code)) = iter.next() else {
+				StorageVersion::new(1).put::<Pallet<T>>();
+				return Ok(None);
+			};
+			if !<AccountCodesMetadata<T>>::contains_key(address) {
+				<AccountCodesMetadata<T>>::insert(address, CodeMetadata::from_code(&code));
+			}
+			last = Some(address);
+		}
+		Ok(last)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{mock::Test, tests::new_test_ext};
+
+	#[test]
+	fn migrate_v0_to_v1_populates_code_metadata() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(0).put::<Pallet<Test>>();
+
+			let address = H160::repeat_byte(0xaa);
+			let code = b"Exemple".to_vec();
+			// V0 layout: code without its metadata.
+			<AccountCodes<Test>>::insert(address, code.clone());
+			assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
+
+			MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+			assert_eq!(
+				<AccountCodesMetadata<Test>>::get(address),
+				Some(CodeMetadata::from_code(&code))
+			);
+			assert_eq!(Pallet::<Test>::on_chain_storage_version(), 1);
+		});
+	}
+
+	#[test]
+	fn migrate_v0_to_v1_is_noop_on_v1() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(1).put::<Pallet<Test>>();
+
+			let address = H160::repeat_byte(0xaa);
+			<AccountCodes<Test>>::insert(address, b"Exemple".to_vec());
+
+			MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+			assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
+			assert_eq!(Pallet::<Test>::on_chain_storage_version(), 1);
+		});
+	}
+
+	#[test]
+	fn lazy_migrate_v0_to_v1_resumes_from_the_cursor() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(0).put::<Pallet<Test>>();
+
+			let addresses = [H160::repeat_byte(0xaa), H160::repeat_byte(0xbb)];
+			for address in addresses {
+				<AccountCodes<Test>>::insert(address, b"Exemple".to_vec());
+			}
+
+			// Only one contract fits in the first step.
+			let mut meter = WeightMeter::with_limit(LazyMigrateV0ToV1::<Test>::contract_weight());
+			let cursor = LazyMigrateV0ToV1::<Test>::step(None, &mut meter).unwrap();
+			assert!(cursor.is_some());
+			let migrated = addresses
+				.iter()
+				.filter(|address| <AccountCodesMetadata<Test>>::contains_key(*address))
+				.count();
+			assert_eq!(migrated, 1);
+			assert_eq!(Pallet::<Test>::on_chain_storage_version(), 0);
+
+			let mut meter = WeightMeter::new();
+			assert_eq!(
+				LazyMigrateV0ToV1::<Test>::step(cursor, &mut meter).unwrap(),
+				None
+			);
+			for address in addresses {
+				assert_eq!(
+					<AccountCodesMetadata<Test>>::get(address),
+					Some(CodeMetadata::from_code(b"Exemple"))
+				);
+			}
+			assert_eq!(Pallet::<Test>::on_chain_storage_version(), 1);
+		});
+	}
+
+	#[test]
+	fn lazy_migrate_v0_to_v1_requires_the_weight_of_a_contract() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(0).put::<Pallet<Test>>();
+
+			let mut meter = WeightMeter::with_limit(Weight::zero());
+			assert!(matches!(
+				LazyMigrateV0ToV1::<Test>::step(None, &mut meter),
+				Err(SteppedMigrationError::InsufficientWeight { .. })
+			));
+		});
+	}
+}
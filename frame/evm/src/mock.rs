@@ -17,12 +17,14 @@
 
 //! Test mock for unit tests and benchmarking
 
-use frame_support::{derive_impl, parameter_types, weights::Weight};
+use core::ops::RangeInclusive;
+use frame_support::{derive_impl, parameter_types, traits::ConstU32, weights::Weight};
 use sp_core::{H160, U256};
 
 use crate::{
 	EnsureAddressNever, EnsureAddressRoot, EnsureAllowedCreateAddress, FeeCalculator,
 	IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet,
+	PrecompileSetList,
 };
 
 frame_support::construct_runtime! {
@@ -65,6 +67,7 @@ parameter_types! {
 	pub SuicideQuickClearLimit: u32 = 0;
 	pub AllowedAddressesCreate: Vec<H160> = vec![H160::default(), H160::from([4u8;20])];
 	pub AllowedAddressesCreateInner: Vec<H160> = vec![H160::from([4u8;20]), H160::from([5u8;20])];
+	pub static ExecutionEvents: bool = false;
 }
 
 #[derive_impl(crate::config_preludes::TestDefaultConfig)]
@@ -79,8 +82,11 @@ impl crate::Config for Test {
 	type Currency = Balances;
 	type PrecompilesType = MockPrecompileSet;
 	type PrecompilesValue = MockPrecompiles;
+	type ReservedAddresses = crate::PrecompileReservedAddresses<Self>;
 	type Runner = crate::runner::stack::Runner<Self>;
 	type Timestamp = Timestamp;
+	type ExecutionEvents = ExecutionEvents;
+	type ExecutionEventReasonLength = ConstU32<4>;
 }
 
 pub struct FixedGasPrice;
@@ -94,13 +100,18 @@ impl FeeCalculator for FixedGasPrice {
 /// Example PrecompileSet with only Identity precompile.
 pub struct MockPrecompileSet;
 
+/// Addresses of the precompiles of `MockPrecompileSet`.
+const MOCK_PRECOMPILES: [H160; 1] = [H160([
+	0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+])];
+
 impl PrecompileSet for MockPrecompileSet {
 	/// Tries to execute a precompile in the precompile set.
 	/// If the provided address is not a precompile, returns None.
 	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
 		let address = handle.code_address();
 
-		if address == H160::from_low_u64_be(1) {
+		if address == MOCK_PRECOMPILES[0] {
 			return Some(pallet_evm_precompile_simple::Identity::execute(handle));
 		}
 
@@ -112,8 +123,27 @@ impl PrecompileSet for MockPrecompileSet {
 	/// `execute` already performs a check internally.
 	fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
 		IsPrecompileResult::Answer {
-			is_precompile: address == H160::from_low_u64_be(1),
+			is_precompile: MOCK_PRECOMPILES.contains(&address),
 			extra_cost: 0,
 		}
 	}
 }
+
+parameter_types! {
+	/// An address reserved by the precompile set on top of its precompiles.
+	pub static ReservedAddress: Option<H160> = None;
+}
+
+impl PrecompileSetList for MockPrecompileSet {
+	fn precompiles(&self) -> Vec<H160> {
+		MOCK_PRECOMPILES.to_vec()
+	}
+
+	fn reserved_address_ranges(&self) -> Vec<RangeInclusive<H160>> {
+		self.precompiles()
+			.into_iter()
+			.chain(ReservedAddress::get())
+			.map(|address| address..=address)
+			.collect()
+	}
+}
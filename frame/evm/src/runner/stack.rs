@@ -36,7 +36,7 @@ use cumulus_primitives_storage_weight_reclaim::get_proof_size;
 use frame_support::{
 	traits::{
 		tokens::{currency::Currency, ExistenceRequirement},
-		Get, Time,
+		Contains, Get, Time,
 	},
 	weights::Weight,
 };
@@ -159,6 +159,37 @@ where
 		res
 	}
 
+	/// Deposits the event describing the outcome of an execution from `from` to `to`, if
+	/// `T::ExecutionEvents` is set. The revert data is only kept for reverted executions, up to
+	/// `T::ExecutionEventReasonLength` bytes.
+	fn deposit_execution_event(
+		from: H160,
+		to: H160,
+		exit_reason: &ExitReason,
+		gas_used: U256,
+		mut revert_data: Vec<u8>,
+	) {
+		if !T::ExecutionEvents::get() {
+			return;
+		}
+		revert_data.truncate(T::ExecutionEventReasonLength::get() as usize);
+		let event = match exit_reason {
+			ExitReason::Succeed(_) => Event::<T>::ExecutionSucceeded { from, to, gas_used },
+			ExitReason::Revert(_) => Event::<T>::ExecutionReverted {
+				from,
+				to,
+				gas_used,
+				reason: revert_data,
+			},
+			ExitReason::Error(_) | ExitReason::Fatal(_) => Event::<T>::ExecutionFailed {
+				from,
+				to,
+				error: exit_reason.clone(),
+			},
+		};
+		Pallet::<T>::deposit_event(event);
+	}
+
 	// Execute an already validated EVM operation.
 	fn execute_inner<'config, 'precompiles, F, R>(
 		source: H160,
@@ -410,6 +441,8 @@ where
 
 		let state = executor.into_state();
 
+		// Self-destructed contracts are only removed once the transaction is executed, so they
+		// keep their code for the rest of the transaction, as per the Ethereum semantics.
 		for address in &state.substate.deletes {
 			log::debug!(
 				target: "evm",
@@ -583,6 +616,15 @@ where
 				)
 			},
 		)
+		.inspect(|info| {
+			Self::deposit_execution_event(
+				source,
+				target,
+				&info.exit_reason,
+				info.used_gas.standard,
+				info.value.clone(),
+			)
+		})
 	}
 
 	fn create(
@@ -639,6 +681,7 @@ where
 		}
 
 		let precompiles = T::PrecompilesValue::get();
+		let mut revert_data = Vec::new();
 		Self::execute(
 			source,
 			value,
@@ -654,7 +697,7 @@ where
 			|executor| {
 				let address = executor.create_address(evm::CreateScheme::Legacy { caller: source });
 				T::OnCreate::on_create(source, address);
-				let (reason, _) = executor.transact_create(
+				let (reason, data) = executor.transact_create(
 					source,
 					value,
 					init,
@@ -662,9 +705,19 @@ where
 					access_list,
 					authorization_list,
 				);
+				revert_data = data;
 				(reason, address)
 			},
 		)
+		.inspect(|info| {
+			Self::deposit_execution_event(
+				source,
+				info.value,
+				&info.exit_reason,
+				info.used_gas.standard,
+				revert_data,
+			)
+		})
 	}
 
 	fn create2(
@@ -723,6 +776,7 @@ where
 
 		let precompiles = T::PrecompilesValue::get();
 		let code_hash = H256::from(sp_io::hashing::keccak_256(&init));
+		let mut revert_data = Vec::new();
 		Self::execute(
 			source,
 			value,
@@ -742,7 +796,7 @@ where
 					salt,
 				});
 				T::OnCreate::on_create(source, address);
-				let (reason, _) = executor.transact_create2(
+				let (reason, data) = executor.transact_create2(
 					source,
 					value,
 					init,
@@ -751,9 +805,19 @@ where
 					access_list,
 					authorization_list,
 				);
+				revert_data = data;
 				(reason, address)
 			},
 		)
+		.inspect(|info| {
+			Self::deposit_execution_event(
+				source,
+				info.value,
+				&info.exit_reason,
+				info.used_gas.standard,
+				revert_data,
+			)
+		})
 	}
 }
 
@@ -1100,6 +1164,8 @@ where
 	}
 
 	fn inc_nonce(&mut self, address: H160) -> Result<(), ExitError> {
+		// Also called by the executor on newly created contracts when the config enables
+		// `create_increase_nonce`, so that they start with nonce 1 as per EIP-161.
 		let account_id = T::AddressMapping::into_account_id(address);
 		T::AccountProvider::inc_account_nonce(&account_id);
 		Ok(())
@@ -1165,6 +1231,13 @@ where
 		code: Vec<u8>,
 		caller: Option<H160>,
 	) -> Result<(), ExitError> {
+		// Contracts would shadow the precompiles at these addresses.
+		if T::ReservedAddresses::contains(&address) {
+			return Err(ExitError::Other(
+				"cannot deploy a contract to a reserved precompile address".into(),
+			));
+		}
+
 		log::debug!(
 			target: "evm",
 			"Inserting code ({} bytes) at {:?}",
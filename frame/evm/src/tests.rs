@@ -874,7 +874,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		H160::default(), // root
 		GenesisAccount {
 			nonce: U256::from(1),
-			balance: U256::max_value(),
+			balance: U256::from(u64::MAX / 8),
 			storage: Default::default(),
 			code: vec![],
 		},
@@ -883,7 +883,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		H160::from([4u8; 20]), // alith
 		GenesisAccount {
 			nonce: U256::from(1),
-			balance: U256::max_value(),
+			balance: U256::from(u64::MAX / 8),
 			storage: Default::default(),
 			code: vec![],
 		},
@@ -892,7 +892,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		H160::from([5u8; 20]), // bob
 		GenesisAccount {
 			nonce: U256::from(1),
-			balance: U256::max_value(),
+			balance: U256::from(u64::MAX / 8),
 			storage: Default::default(),
 			code: vec![],
 		},
@@ -901,7 +901,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 		H160::from([6u8; 20]), // charleth
 		GenesisAccount {
 			nonce: U256::from(1),
-			balance: U256::max_value(),
+			balance: U256::from(u64::MAX / 8),
 			storage: Default::default(),
 			code: vec![],
 		},
@@ -1074,6 +1074,45 @@ fn test_inner_contract_deploy_succeeds_if_address_is_allowed() {
 	});
 }
 
+#[test]
+fn test_inner_contract_deploy_succeeds_if_only_inner_create_is_allowed() {
+	new_test_ext().execute_with(|| {
+		let gas_limit: u64 = 1_000_000;
+		let weight_limit = FixedGasWeightMapping::<Test>::gas_to_weight(gas_limit, true);
+
+		let result1 = create_foo_bar_contract_creator(gas_limit, Some(weight_limit))
+			.expect("create succeeds");
+
+		let call_data: String = "2fc11060".to_owned();
+		let call_contract_address = result1.value;
+
+		let result = <Test as Config>::Runner::call(
+			// Bob is not allowed to deploy contracts, but is allowed to deploy inner contracts
+			H160::from([5u8; 20]),
+			call_contract_address,
+			hex::decode(&call_data).unwrap(),
+			U256::zero(),
+			gas_limit,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			Some(weight_limit),
+			Some(0),
+			&<Test as Config>::config().clone(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+	});
+}
+
 #[test]
 fn test_inner_contract_deploy_reverts_if_address_not_allowed() {
 	new_test_ext().execute_with(|| {
@@ -1110,6 +1149,60 @@ fn test_inner_contract_deploy_reverts_if_address_not_allowed() {
 	});
 }
 
+/// Address of a contract created with CREATE by `creator` at the given (single byte) nonce.
+fn legacy_create_address(creator: H160, nonce: u8) -> H160 {
+	// rlp([creator, nonce]) for nonces in 1..=0x7f.
+	let mut stream = vec![0xd6, 0x94];
+	stream.extend_from_slice(creator.as_bytes());
+	stream.push(nonce);
+	H160::from_slice(&sp_io::hashing::keccak_256(&stream)[12..])
+}
+
+#[test]
+fn eip161_created_contracts_start_with_nonce_one() {
+	new_test_ext().execute_with(|| {
+		let gas_limit: u64 = 1_000_000;
+		let weight_limit = FixedGasWeightMapping::<Test>::gas_to_weight(gas_limit, true);
+
+		let factory = create_foo_bar_contract_creator(gas_limit, Some(weight_limit))
+			.expect("create succeeds")
+			.value;
+		// EIP-161: the nonce of a newly created contract starts at 1.
+		assert_eq!(EVM::account_basic(&factory).0.nonce, U256::one());
+
+		let result = <Test as Config>::Runner::call(
+			// Alith is allowed to deploy inner contracts
+			H160::from([4u8; 20]),
+			factory,
+			hex::decode("2fc11060").unwrap(),
+			U256::zero(),
+			gas_limit,
+			Some(FixedGasPrice::min_gas_price().0),
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			true, // transactional
+			true, // must be validated
+			Some(weight_limit),
+			Some(0),
+			&<Test as Config>::config().clone(),
+		)
+		.expect("call succeeds");
+		assert_eq!(
+			result.exit_reason,
+			ExitReason::Succeed(ExitSucceed::Returned)
+		);
+
+		// The child address is derived from the factory nonce at the time of the CREATE,
+		// which is 1 for a freshly deployed factory.
+		let child = H160::from_slice(&result.value[12..32]);
+		assert_eq!(child, legacy_create_address(factory, 1));
+		assert_eq!(EVM::account_basic(&factory).0.nonce, U256::from(2));
+		assert_eq!(EVM::account_basic(&child).0.nonce, U256::one());
+	});
+}
+
 #[test]
 fn fail_call_return_ok() {
 	new_test_ext().execute_with(|| {
@@ -1701,3 +1794,667 @@ fn metadata_empty_dont_code_gets_cached() {
 		assert!(<AccountCodesMetadata<Test>>::get(address).is_none());
 	});
 }
+
+#[test]
+fn access_list_prewarms_storage_and_charges_intrinsic_gas() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::repeat_byte(0xaa);
+		// PUSH1 0x00 SLOAD POP STOP
+		assert!(crate::Pallet::<Test>::create_account(
+			contract,
+			hex::decode("6000545000").unwrap(),
+			None
+		)
+		.is_ok());
+
+		let used_gas = |access_list: Vec<(H160, Vec<H256>)>| {
+			<Test as Config>::Runner::call(
+				H160::default(),
+				contract,
+				Vec::new(),
+				U256::zero(),
+				1_000_000,
+				None,
+				None,
+				None,
+				access_list,
+				Vec::new(),
+				false, // non-transactional
+				false, // must not be validated
+				None,
+				None,
+				&<Test as Config>::config().clone(),
+			)
+			.expect("call succeeds")
+			.used_gas
+			.standard
+		};
+
+		let without_access_list = used_gas(Vec::new());
+		let with_access_list = used_gas(vec![(contract, vec![H256::zero()])]);
+
+		// Intrinsic cost of the access list: one address and one storage key.
+		let access_list_cost = U256::from(2_400 + 1_900);
+		// The listed slot is warm, SLOAD costs 100 instead of 2_100.
+		let warm_sload_savings = U256::from(2_100 - 100);
+		assert_eq!(
+			with_access_list,
+			without_access_list - warm_sload_savings + access_list_cost
+		);
+	});
+}
+
+fn new_test_ext_with_genesis_accounts(
+	accounts: BTreeMap<H160, GenesisAccount>,
+) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default()
+		.build_storage()
+		.unwrap();
+
+	crate::GenesisConfig::<Test> {
+		accounts,
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	t.into()
+}
+
+#[test]
+fn genesis_predeployed_contract_is_callable() {
+	let contract = H160::repeat_byte(0xcc);
+	let slot = H256::from_low_u64_be(1);
+	let mut accounts = BTreeMap::new();
+	accounts.insert(
+		contract,
+		GenesisAccount {
+			nonce: U256::from(1),
+			balance: U256::from(1_000),
+			storage: BTreeMap::from([(slot, H256::from_low_u64_be(7))]),
+			// PUSH1 0x2a PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+			code: hex::decode("602a60005260206000f3").unwrap(),
+		},
+	);
+
+	new_test_ext_with_genesis_accounts(accounts).execute_with(|| {
+		System::set_block_number(1);
+
+		let (account, _) = EVM::account_basic(&contract);
+		assert_eq!(account.nonce, U256::from(1));
+		assert_eq!(account.balance, U256::from(1_000));
+		assert_eq!(
+			<AccountStorages<Test>>::get(contract, slot),
+			H256::from_low_u64_be(7)
+		);
+
+		let info = <Test as Config>::Runner::call(
+			H160::default(),
+			contract,
+			Vec::new(),
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			false, // non-transactional
+			false, // must not be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("call succeeds");
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.value, H256::from_low_u64_be(0x2a).as_bytes().to_vec());
+	});
+}
+
+#[test]
+#[should_panic(expected = "exceeds the contract size limit")]
+fn genesis_code_over_size_limit_is_rejected() {
+	let limit = <Test as Config>::config()
+		.create_contract_limit
+		.expect("a value");
+	let mut accounts = BTreeMap::new();
+	accounts.insert(
+		H160::repeat_byte(0xcc),
+		GenesisAccount {
+			nonce: U256::zero(),
+			balance: U256::zero(),
+			storage: Default::default(),
+			code: vec![0; limit + 1],
+		},
+	);
+
+	new_test_ext_with_genesis_accounts(accounts);
+}
+
+fn genesis_account_with_balance(balance: U256) -> GenesisAccount {
+	GenesisAccount {
+		nonce: U256::zero(),
+		balance,
+		storage: Default::default(),
+		code: vec![],
+	}
+}
+
+#[test]
+fn genesis_balances_up_to_the_balance_type_are_minted() {
+	let account = H160::repeat_byte(0xcc);
+	let accounts = BTreeMap::from([(account, genesis_account_with_balance(u64::MAX.into()))]);
+
+	new_test_ext_with_genesis_accounts(accounts).execute_with(|| {
+		assert_eq!(EVM::account_basic(&account).0.balance, U256::from(u64::MAX));
+	});
+}
+
+#[test]
+#[should_panic(expected = "overflows the balance type")]
+fn genesis_balance_over_the_balance_type_is_rejected() {
+	let accounts = BTreeMap::from([(
+		H160::repeat_byte(0xcc),
+		genesis_account_with_balance(U256::from(u64::MAX) + 1),
+	)]);
+
+	new_test_ext_with_genesis_accounts(accounts);
+}
+
+#[test]
+#[should_panic(expected = "overflows the total issuance of the currency")]
+fn genesis_balances_over_the_total_issuance_are_rejected() {
+	let balance = U256::from(u64::MAX / 2 + 1);
+	let accounts = BTreeMap::from([
+		(
+			H160::repeat_byte(0xcc),
+			genesis_account_with_balance(balance),
+		),
+		(
+			H160::repeat_byte(0xdd),
+			genesis_account_with_balance(balance),
+		),
+	]);
+
+	new_test_ext_with_genesis_accounts(accounts);
+}
+
+#[test]
+fn unfunded_accounts_have_no_balance() {
+	new_test_ext_with_genesis_accounts(BTreeMap::new()).execute_with(|| {
+		let zero = H160::zero();
+		let precompile = H160::from_low_u64_be(1);
+		assert!(MockPrecompiles::get().precompiles().contains(&precompile));
+		let never_funded = H160::repeat_byte(0xee);
+
+		for address in [zero, precompile, never_funded] {
+			let (account, _) = EVM::account_basic(&address);
+			assert_eq!(account.balance, U256::zero());
+			assert_eq!(account.nonce, U256::zero());
+		}
+	});
+}
+
+#[test]
+fn runner_deploys_at_predicted_contract_addresses() {
+	new_test_ext().execute_with(|| {
+		let source = H160::default();
+		// PUSH1 0x00 PUSH1 0x00 RETURN: deploys an empty contract.
+		let init = hex::decode("60006000f3").unwrap();
+
+		let nonce = EVM::account_basic(&source).0.nonce;
+		let info = <Test as Config>::Runner::create(
+			source,
+			init.clone(),
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			false, // non-transactional
+			false, // must not be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("create succeeds");
+		assert_eq!(info.value, fp_evm::create_address(source, nonce));
+
+		let salt = H256::repeat_byte(0x42);
+		let info = <Test as Config>::Runner::create2(
+			source,
+			init.clone(),
+			salt,
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			false, // non-transactional
+			false, // must not be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("create2 succeeds");
+		assert_eq!(
+			info.value,
+			fp_evm::create2_address(source, salt, H256(sp_io::hashing::keccak_256(&init)))
+		);
+	});
+}
+
+fn create2(source: H160, init: Vec<u8>, salt: H256) -> CreateInfo {
+	<Test as Config>::Runner::create2(
+		source,
+		init,
+		salt,
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		Vec::new(),
+		false, // non-transactional
+		false, // must not be validated
+		None,
+		None,
+		&<Test as Config>::config().clone(),
+	)
+	.expect("create2 runs")
+}
+
+#[test]
+fn runner_refuses_deploying_to_reserved_addresses() {
+	new_test_ext().execute_with(|| {
+		let source = H160::default();
+		// Deploys a contract whose code is 0x01.
+		let init = hex::decode("600160005360016000f3").unwrap();
+		let salt = H256::repeat_byte(0x42);
+		let reserved =
+			fp_evm::create2_address(source, salt, H256(sp_io::hashing::keccak_256(&init)));
+		ReservedAddress::set(Some(reserved));
+
+		let info = create2(source, init.clone(), salt);
+		assert!(matches!(
+			info.exit_reason,
+			ExitReason::Error(ExitError::Other(_))
+		));
+		assert!(<AccountCodes<Test>>::get(reserved).is_empty());
+
+		// Other addresses are unaffected.
+		let info = create2(source, init, H256::repeat_byte(0x43));
+		assert!(info.exit_reason.is_succeed());
+		assert_eq!(<AccountCodes<Test>>::get(info.value), vec![0x01]);
+
+		// The precompiles themselves still run.
+		let identity = H160::from_low_u64_be(1);
+		let info = <Test as Config>::Runner::call(
+			source,
+			identity,
+			vec![0xde, 0xad],
+			U256::zero(),
+			1_000_000,
+			None,
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+			false, // non-transactional
+			false, // must not be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		)
+		.expect("call succeeds");
+		assert_eq!(info.value, vec![0xde, 0xad]);
+
+		ReservedAddress::set(None);
+	});
+}
+
+/// Runs a non-transactional call of `target` from the zero address.
+fn runner_call(target: H160) -> CallInfo {
+	<Test as Config>::Runner::call(
+		H160::default(),
+		target,
+		Vec::new(),
+		U256::zero(),
+		1_000_000,
+		None,
+		None,
+		None,
+		Vec::new(),
+		Vec::new(),
+		false, // non-transactional
+		false, // must not be validated
+		None,
+		None,
+		&<Test as Config>::config().clone(),
+	)
+	.expect("call runs")
+}
+
+/// The execution events deposited by the runner, in order.
+fn execution_events() -> Vec<crate::Event<Test>> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::EVM(
+				event @ (crate::Event::ExecutionSucceeded { .. }
+				| crate::Event::ExecutionReverted { .. }
+				| crate::Event::ExecutionFailed { .. }),
+			) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn runner_deposits_execution_outcome_events() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ExecutionEvents::set(true);
+		let source = H160::default();
+
+		let target = H160::repeat_byte(0xaa);
+		let info = runner_call(target);
+		System::assert_has_event(RuntimeEvent::EVM(crate::Event::ExecutionSucceeded {
+			from: source,
+			to: target,
+			gas_used: info.used_gas.standard,
+		}));
+
+		// PUSH1 0x2a PUSH1 0x00 MSTORE8 PUSH1 0x20 PUSH1 0x00 REVERT
+		let reverter = H160::repeat_byte(0xbb);
+		EVM::create_account(reverter, hex::decode("602a60005360206000fd").unwrap(), None)
+			.expect("account is created");
+		let info = runner_call(reverter);
+		assert_eq!(info.value.len(), 32);
+		// The revert data is truncated to `ExecutionEventReasonLength` bytes.
+		System::assert_has_event(RuntimeEvent::EVM(crate::Event::ExecutionReverted {
+			from: source,
+			to: reverter,
+			gas_used: info.used_gas.standard,
+			reason: vec![0x2a, 0, 0, 0],
+		}));
+
+		// INVALID
+		let invalid = H160::repeat_byte(0xcc);
+		EVM::create_account(invalid, vec![0xfe], None).expect("account is created");
+		runner_call(invalid);
+		System::assert_has_event(RuntimeEvent::EVM(crate::Event::ExecutionFailed {
+			from: source,
+			to: invalid,
+			error: ExitReason::Error(ExitError::DesignatedInvalid),
+		}));
+	});
+}
+
+#[test]
+fn execution_events_are_disabled_by_default() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		runner_call(H160::repeat_byte(0xaa));
+		assert!(execution_events().is_empty());
+	});
+}
+
+#[test]
+fn execution_events_come_with_the_events_of_the_calls() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ExecutionEvents::set(true);
+		let target = H160::repeat_byte(0xaa);
+
+		assert_ok!(EVM::call(
+			RuntimeOrigin::root(),
+			H160::default(),
+			target,
+			Vec::new(),
+			U256::default(),
+			1000000,
+			U256::from(1_000_000_000),
+			None,
+			None,
+			Vec::new(),
+			Vec::new(),
+		));
+
+		let events: Vec<_> = System::events()
+			.into_iter()
+			.filter_map(|record| match record.event {
+				RuntimeEvent::EVM(event) => Some(event),
+				_ => None,
+			})
+			.collect();
+		assert!(matches!(
+			events.as_slice(),
+			[
+				crate::Event::ExecutionSucceeded { from, to, .. },
+				crate::Event::Executed { address },
+			] if *from == H160::default() && *to == target && *address == target
+		));
+	});
+}
+
+#[test]
+fn execution_event_weight_covers_both_events() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EVM::execution_event_weight(), Weight::zero());
+
+		ExecutionEvents::set(true);
+		let reason_length = u64::from(<Test as Config>::ExecutionEventReasonLength::get());
+		assert_eq!(
+			EVM::execution_event_weight(),
+			<Test as frame_system::Config>::DbWeight::get()
+				.writes(2)
+				.saturating_add(Weight::from_parts(0, 20 + 20 + 20 + 32 + 5 + reason_length))
+		);
+	});
+}
+
+#[test]
+fn runner_rejects_overflowing_fee() {
+	new_test_ext().execute_with(|| {
+		let result = <Test as Config>::Runner::call(
+			H160::default(),
+			H160::from_str("1000000000000000000000000000000000000001").unwrap(),
+			Vec::new(),
+			U256::zero(),
+			u64::MAX,
+			Some(U256::MAX),
+			Some(U256::MAX),
+			None,
+			Vec::new(),
+			Vec::new(),
+			true,  // transactional
+			false, // must not be validated
+			None,
+			None,
+			&<Test as Config>::config().clone(),
+		);
+
+		assert!(matches!(
+			result,
+			Err(RunnerError {
+				error: Error::<Test>::FeeOverflow,
+				..
+			})
+		));
+	});
+}
+
+// Runtime code: with empty input, `SELFDESTRUCT` to the caller, otherwise return the word 1.
+//   CALLDATASIZE PUSH1 0x06 JUMPI CALLER SELFDESTRUCT
+//   JUMPDEST PUSH1 0x01 PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+const SELF_DESTRUCT_CONTRACT_BYTECODE: &str =
+	"6011600c60003960116000f33660065733ff5b600160005260206000f3";
+
+// Runtime code: call the contract at the address given as input with empty input, which
+// self-destructs it, then call it again with input 0x01 and return what the second call returned.
+const SELF_DESTRUCT_THEN_CALL_CONTRACT_BYTECODE: &str = concat!(
+	"602f600c600039602f6000f3",
+	"600060006000600060006000355af150",
+	"6001600052602060006001601f60006000355af150",
+	"3d600060003e3d6000f3",
+);
+
+fn create_with_config(init: &str, config: &EvmConfig) -> H160 {
+	let info = <Test as Config>::Runner::create(
+		H160::default(),
+		hex::decode(init).unwrap(),
+		U256::zero(),
+		1_000_000,
+		Some(FixedGasPrice::min_gas_price().0),
+		None,
+		None,
+		Vec::new(),
+		Vec::new(),
+		true, // transactional
+		true, // must be validated
+		None,
+		None,
+		config,
+	)
+	.expect("create succeeds");
+	assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+	info.value
+}
+
+fn call_with_config(target: H160, input: Vec<u8>, config: &EvmConfig) -> CallInfo {
+	<Test as Config>::Runner::call(
+		H160::default(),
+		target,
+		input,
+		U256::zero(),
+		1_000_000,
+		Some(FixedGasPrice::min_gas_price().0),
+		None,
+		None,
+		Vec::new(),
+		Vec::new(),
+		true, // transactional
+		true, // must be validated
+		None,
+		None,
+		config,
+	)
+	.expect("call succeeds")
+}
+
+fn word(value: u64) -> Vec<u8> {
+	H256::from_low_u64_be(value).as_bytes().to_vec()
+}
+
+#[test]
+fn self_destructed_contract_runs_until_the_end_of_the_transaction() {
+	new_test_ext().execute_with(|| {
+		let config = EvmConfig::shanghai();
+		let target = create_with_config(SELF_DESTRUCT_CONTRACT_BYTECODE, &config);
+		let caller = create_with_config(SELF_DESTRUCT_THEN_CALL_CONTRACT_BYTECODE, &config);
+
+		// Within the transaction, the self-destructed contract still has its code.
+		let info = call_with_config(caller, H256::from(target).as_bytes().to_vec(), &config);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.value, word(1));
+
+		// It is removed once the transaction ends.
+		assert!(AccountCodes::<Test>::get(target).is_empty());
+		assert!(AccountCodesMetadata::<Test>::get(target).is_none());
+	});
+}
+
+#[test]
+fn call_to_contract_self_destructed_in_a_previous_transaction_returns_no_output() {
+	new_test_ext().execute_with(|| {
+		let config = EvmConfig::shanghai();
+		let target = create_with_config(SELF_DESTRUCT_CONTRACT_BYTECODE, &config);
+
+		let info = call_with_config(target, Vec::new(), &config);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+
+		// In the same block, the contract has no code anymore.
+		let info = call_with_config(target, vec![1], &config);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert!(info.value.is_empty());
+	});
+}
+
+#[test]
+fn eip6780_self_destruct_keeps_contract_created_in_a_previous_transaction() {
+	new_test_ext().execute_with(|| {
+		let config = <Test as Config>::config().clone();
+		let target = create_with_config(SELF_DESTRUCT_CONTRACT_BYTECODE, &config);
+		let caller = create_with_config(SELF_DESTRUCT_THEN_CALL_CONTRACT_BYTECODE, &config);
+
+		let info = call_with_config(caller, H256::from(target).as_bytes().to_vec(), &config);
+		assert_eq!(info.value, word(1));
+
+		// `SELFDESTRUCT` only transferred the balance, the contract is kept.
+		let info = call_with_config(target, Vec::new(), &config);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Suicided));
+		assert!(!AccountCodes::<Test>::get(target).is_empty());
+		let info = call_with_config(target, vec![1], &config);
+		assert_eq!(info.value, word(1));
+	});
+}
+
+// Runtime code: calls the identity precompile with as many bytes of memory as the word of input.
+//   PUSH1 0x00 PUSH1 0x00 PUSH1 0x00 CALLDATALOAD PUSH1 0x00 PUSH1 0x01 GAS STATICCALL STOP
+const IDENTITY_CALLER_BYTECODE: &str = "60006000600035600060015afa00";
+
+#[test]
+fn estimation_charges_the_input_dependent_cost_of_precompiles() {
+	new_test_ext().execute_with(|| {
+		let caller = H160::repeat_byte(0xca);
+		assert!(
+			EVM::create_account(caller, hex::decode(IDENTITY_CALLER_BYTECODE).unwrap(), None)
+				.is_ok()
+		);
+
+		let used_gas = |size: u64, estimate: bool| {
+			let mut config = <Test as Config>::config().clone();
+			config.estimate = estimate;
+			<Test as Config>::Runner::call(
+				H160::default(),
+				caller,
+				H256::from_low_u64_be(size).as_bytes().to_vec(),
+				U256::zero(),
+				1_000_000,
+				None,
+				None,
+				None,
+				Vec::new(),
+				Vec::new(),
+				false, // non-transactional
+				false, // must not be validated
+				None,
+				None,
+				&config,
+			)
+			.expect("call succeeds")
+			.used_gas
+			.standard
+		};
+
+		let small = used_gas(1024, true);
+		let large = used_gas(4096, true);
+		assert_eq!(small, used_gas(1024, false));
+		assert_eq!(large, used_gas(4096, false));
+
+		// The memory expansion of the caller, then the linear cost of the precompile.
+		let memory_cost = |words: u64| 3 * words + words * words / 512;
+		let identity_cost = |words: u64| 15 + 3 * words;
+		assert_eq!(
+			large - small,
+			U256::from(memory_cost(128) - memory_cost(32) + identity_cost(128) - identity_cost(32))
+		);
+	});
+}
@@ -46,6 +46,8 @@ pub struct MockHandle {
 	pub context: Context,
 	pub is_static: bool,
 	pub gas_used: u64,
+	/// The `(ref_time, proof_size, storage_growth)` of every `record_external_cost` call.
+	pub external_costs: Vec<(Option<u64>, Option<u64>, Option<u64>)>,
 }
 
 impl MockHandle {
@@ -56,6 +58,7 @@ impl MockHandle {
 			context,
 			is_static: false,
 			gas_used: 0,
+			external_costs: Vec::new(),
 		}
 	}
 }
@@ -82,10 +85,12 @@ impl PrecompileHandle for MockHandle {
 
 	fn record_external_cost(
 		&mut self,
-		_: Option<u64>,
-		_: Option<u64>,
-		_: Option<u64>,
+		ref_time: Option<u64>,
+		proof_size: Option<u64>,
+		storage_growth: Option<u64>,
 	) -> Result<(), ExitError> {
+		self.external_costs
+			.push((ref_time, proof_size, storage_growth));
 		Ok(())
 	}
 
@@ -0,0 +1,131 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, parse_quote, DeriveInput, LitStr};
+
+pub fn main(input: TokenStream) -> TokenStream {
+	let DeriveInput {
+		ident,
+		mut generics,
+		data,
+		..
+	} = parse_macro_input!(input as DeriveInput);
+
+	let syn::Data::Struct(syn::DataStruct {
+		fields: syn::Fields::Named(fields),
+		..
+	}) = data
+	else {
+		return quote_spanned! { ident.span() =>
+			compile_error!("Eip712StructHash can only be derived for structs with named fields");
+		}
+		.into();
+	};
+	let fields = fields.named;
+
+	if fields.is_empty() {
+		return quote_spanned! { ident.span() =>
+			compile_error!(
+				"Eip712StructHash can only be derived for structs with at least one field"
+			);
+		}
+		.into();
+	}
+
+	let fields_ty: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+	let fields_ident: Vec<_> = fields
+		.iter()
+		.map(|f| f.ident.as_ref().expect("fields are named"))
+		.collect();
+	// Member declarations are prefixed with a space, e.g. `address wallet` is built from the
+	// type name `address` and the literal ` wallet`.
+	let fields_name_lit: Vec<_> = fields_ident
+		.iter()
+		.map(|i| LitStr::new(&format!(" {}", i), i.span()))
+		.collect();
+	let type_name_lit = LitStr::new(&ident.to_string(), ident.span());
+
+	let where_clause = generics.make_where_clause();
+	for ty in &fields_ty {
+		where_clause
+			.predicates
+			.push(parse_quote!(#ty: ::precompile_utils::eip712::Eip712Type));
+	}
+
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+	quote! {
+		impl #impl_generics ::precompile_utils::eip712::Eip712Type for #ident #ty_generics
+		#where_clause {
+			fn type_name() -> ::precompile_utils::__alloc::string::String {
+				::precompile_utils::__alloc::string::String::from(#type_name_lit)
+			}
+
+			fn referenced_struct_types(
+				types: &mut ::precompile_utils::__alloc::collections::BTreeMap<
+					::precompile_utils::__alloc::string::String,
+					::precompile_utils::__alloc::string::String,
+				>,
+			) {
+				if types.contains_key(#type_name_lit) {
+					return;
+				}
+				types.insert(
+					::precompile_utils::__alloc::string::String::from(#type_name_lit),
+					<Self as ::precompile_utils::eip712::Eip712StructHash>::encode_struct_type(),
+				);
+				#(
+					<#fields_ty as ::precompile_utils::eip712::Eip712Type>::referenced_struct_types(
+						types,
+					);
+				)*
+			}
+
+			fn encode_data(&self) -> ::precompile_utils::eip712::H256 {
+				<Self as ::precompile_utils::eip712::Eip712StructHash>::struct_hash(self)
+			}
+		}
+
+		impl #impl_generics ::precompile_utils::eip712::Eip712StructHash for #ident #ty_generics
+		#where_clause {
+			fn encode_struct_type() -> ::precompile_utils::__alloc::string::String {
+				let members: ::precompile_utils::__alloc::vec::Vec<
+					::precompile_utils::__alloc::string::String,
+				> = ::precompile_utils::__alloc::vec![
+					#(
+						<#fields_ty as ::precompile_utils::eip712::Eip712Type>::type_name()
+							+ #fields_name_lit,
+					)*
+				];
+				::precompile_utils::__alloc::format!("{}({})", #type_name_lit, members.join(","))
+			}
+
+			fn encode_members(
+				&self,
+			) -> ::precompile_utils::__alloc::vec::Vec<::precompile_utils::eip712::H256> {
+				::precompile_utils::__alloc::vec![
+					#(
+						::precompile_utils::eip712::Eip712Type::encode_data(&self.#fields_ident),
+					)*
+				]
+			}
+		}
+	}
+	.into()
+}
@@ -25,6 +25,7 @@ use sp_crypto_hashing::keccak_256;
 use syn::{parse_macro_input, spanned::Spanned, Expr, Ident, ItemType, Lit, LitStr};
 
 mod derive_codec;
+mod derive_eip712;
 mod precompile;
 mod precompile_name_from_address;
 
@@ -76,3 +77,8 @@ pub fn precompile_name_from_address(attr: TokenStream, input: TokenStream) -> To
 pub fn derive_codec(input: TokenStream) -> TokenStream {
 	derive_codec::main(input)
 }
+
+#[proc_macro_derive(Eip712StructHash)]
+pub fn derive_eip712_struct_hash(input: TokenStream) -> TokenStream {
+	derive_eip712::main(input)
+}
@@ -0,0 +1,436 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hashing and signature verification of
+//! [EIP-712](https://eips.ethereum.org/EIPS/eip-712) typed structured data.
+//!
+//! Structs implement [`Eip712StructHash`] with `#[derive(Eip712StructHash)]`, the members being
+//! any type implementing [`Eip712Type`]: the atomic types of the Solidity codec, `bytes`,
+//! `string`, dynamic arrays and other derived structs.
+
+use crate::solidity::{
+	codec::{
+		bytes::{BoundedBytesString, BytesKind, StringKind},
+		Address, BoundedVec, Codec,
+	},
+	revert::{Revert, RevertReason},
+};
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
+use frame_support::traits::Get;
+use pallet_evm::PrecompileHandle;
+use sp_core::{H160, U256};
+use sp_io::hashing::keccak_256;
+
+// Used by the derive macro.
+pub use sp_core::H256;
+
+// derive macro
+pub use precompile_utils_macro::Eip712StructHash;
+
+/// Half of the order of the secp256k1 curve. Signatures with a larger `s` are rejected to
+/// prevent malleability, like OpenZeppelin's `ECDSA.recover`.
+const SECP256K1N_HALF: [u8; 32] = [
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// A type that can be a member of an EIP-712 struct.
+pub trait Eip712Type {
+	/// Name of the type in the EIP-712 type encoding, e.g. `address` or `Mail`.
+	fn type_name() -> String;
+
+	/// Add the struct types referenced by this type, including itself, to `types`. Entries are
+	/// keyed by struct name and hold the encoding of the struct alone.
+	fn referenced_struct_types(_types: &mut BTreeMap<String, String>) {}
+
+	/// The `encodeData` word of the value.
+	fn encode_data(&self) -> H256;
+}
+
+/// A struct of EIP-712 typed data. Should be implemented with `#[derive(Eip712StructHash)]`.
+pub trait Eip712StructHash: Eip712Type {
+	/// The encoding of the struct alone, e.g. `Mail(Person from,Person to,string contents)`.
+	fn encode_struct_type() -> String;
+
+	/// The `encodeData` words of the struct members, in declaration order.
+	fn encode_members(&self) -> Vec<H256>;
+
+	/// The `encodeType` of the struct: its own encoding followed by the encodings of the
+	/// referenced struct types, sorted by name.
+	fn encode_type() -> String {
+		let mut types = BTreeMap::new();
+		Self::referenced_struct_types(&mut types);
+		types.remove(&Self::type_name());
+
+		let mut encoded = Self::encode_struct_type();
+		for referenced in types.into_values() {
+			encoded.push_str(&referenced);
+		}
+		encoded
+	}
+
+	/// The `typeHash` of the struct.
+	fn type_hash() -> H256 {
+		keccak_256(Self::encode_type().as_bytes()).into()
+	}
+
+	/// The `hashStruct` of the value.
+	fn struct_hash(&self) -> H256 {
+		let mut data = Self::type_hash().as_bytes().to_vec();
+		for member in self.encode_members() {
+			data.extend_from_slice(member.as_bytes());
+		}
+		keccak_256(&data).into()
+	}
+}
+
+macro_rules! impl_eip712_for_atomic_types {
+	($($ty:ty),* $(,)?) => {
+		$(
+			impl Eip712Type for $ty {
+				fn type_name() -> String {
+					<$ty as Codec>::signature()
+				}
+
+				fn encode_data(&self) -> H256 {
+					H256::from_slice(&crate::solidity::encode_arguments(*self))
+				}
+			}
+		)*
+	};
+}
+
+impl_eip712_for_atomic_types!(Address, H256, U256, bool, u8, u16, u32, u64, u128);
+
+impl<S: Get<u32>> Eip712Type for BoundedBytesString<BytesKind, S> {
+	fn type_name() -> String {
+		<Self as Codec>::signature()
+	}
+
+	fn encode_data(&self) -> H256 {
+		keccak_256(self.as_bytes()).into()
+	}
+}
+
+impl<S: Get<u32>> Eip712Type for BoundedBytesString<StringKind, S> {
+	fn type_name() -> String {
+		<Self as Codec>::signature()
+	}
+
+	fn encode_data(&self) -> H256 {
+		keccak_256(self.as_bytes()).into()
+	}
+}
+
+impl<T: Eip712Type> Eip712Type for Vec<T> {
+	fn type_name() -> String {
+		format!("{}[]", T::type_name())
+	}
+
+	fn referenced_struct_types(types: &mut BTreeMap<String, String>) {
+		T::referenced_struct_types(types)
+	}
+
+	fn encode_data(&self) -> H256 {
+		encode_array(self)
+	}
+}
+
+impl<T: Eip712Type, S> Eip712Type for BoundedVec<T, S> {
+	fn type_name() -> String {
+		format!("{}[]", T::type_name())
+	}
+
+	fn referenced_struct_types(types: &mut BTreeMap<String, String>) {
+		T::referenced_struct_types(types)
+	}
+
+	fn encode_data(&self) -> H256 {
+		encode_array(self.as_slice())
+	}
+}
+
+/// Arrays are encoded as the hash of the concatenated `encodeData` of their items.
+fn encode_array<T: Eip712Type>(items: &[T]) -> H256 {
+	let mut data = Vec::with_capacity(items.len() * 32);
+	for item in items {
+		data.extend_from_slice(item.encode_data().as_bytes());
+	}
+	keccak_256(&data).into()
+}
+
+/// The domain of EIP-712 typed data, hashed into the domain separator. Only the fields that are
+/// set are part of the domain type.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Domain {
+	pub name: Option<String>,
+	pub version: Option<String>,
+	pub chain_id: Option<U256>,
+	pub verifying_contract: Option<H160>,
+}
+
+impl Domain {
+	/// Create an empty domain.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The domain of a precompile: the chain id of the runtime and the address of the precompile
+	/// as verifying contract.
+	pub fn for_precompile<Runtime: pallet_evm::Config>(
+		handle: &impl PrecompileHandle,
+		name: impl Into<String>,
+		version: impl Into<String>,
+	) -> Self {
+		Self::new()
+			.with_name(name)
+			.with_version(version)
+			.with_chain_id(Runtime::ChainId::get())
+			.with_verifying_contract(handle.code_address())
+	}
+
+	pub fn with_name(mut self, name: impl Into<String>) -> Self {
+		self.name = Some(name.into());
+		self
+	}
+
+	pub fn with_version(mut self, version: impl Into<String>) -> Self {
+		self.version = Some(version.into());
+		self
+	}
+
+	pub fn with_chain_id(mut self, chain_id: impl Into<U256>) -> Self {
+		self.chain_id = Some(chain_id.into());
+		self
+	}
+
+	pub fn with_verifying_contract(mut self, verifying_contract: H160) -> Self {
+		self.verifying_contract = Some(verifying_contract);
+		self
+	}
+
+	/// The `encodeType` of the domain, only containing the fields that are set.
+	pub fn encode_type(&self) -> String {
+		let mut members = Vec::new();
+		if self.name.is_some() {
+			members.push("string name");
+		}
+		if self.version.is_some() {
+			members.push("string version");
+		}
+		if self.chain_id.is_some() {
+			members.push("uint256 chainId");
+		}
+		if self.verifying_contract.is_some() {
+			members.push("address verifyingContract");
+		}
+		format!("EIP712Domain({})", members.join(","))
+	}
+
+	/// The domain separator, i.e. the `hashStruct` of the domain.
+	pub fn separator(&self) -> H256 {
+		let mut data = keccak_256(self.encode_type().as_bytes()).to_vec();
+		if let Some(name) = &self.name {
+			data.extend_from_slice(&keccak_256(name.as_bytes()));
+		}
+		if let Some(version) = &self.version {
+			data.extend_from_slice(&keccak_256(version.as_bytes()));
+		}
+		if let Some(chain_id) = self.chain_id {
+			data.extend_from_slice(&chain_id.to_big_endian());
+		}
+		if let Some(verifying_contract) = self.verifying_contract {
+			data.extend_from_slice(H256::from(verifying_contract).as_bytes());
+		}
+		keccak_256(&data).into()
+	}
+
+	/// The digest to sign for the typed data with the given `hashStruct`:
+	/// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`.
+	pub fn hash_typed_data(&self, struct_hash: H256) -> H256 {
+		let mut data = Vec::with_capacity(66);
+		data.extend_from_slice(b"\x19\x01");
+		data.extend_from_slice(self.separator().as_bytes());
+		data.extend_from_slice(struct_hash.as_bytes());
+		keccak_256(&data).into()
+	}
+}
+
+/// Recover the signer of typed data from its `hashStruct` and a 65 bytes `r ‖ s ‖ v` signature.
+/// Both the `{27, 28}` and `{0, 1}` conventions are accepted for `v`.
+pub fn recover_signer(
+	domain: &Domain,
+	struct_hash: H256,
+	signature: &[u8],
+) -> Result<H160, Revert> {
+	let mut signature: [u8; 65] = signature
+		.try_into()
+		.map_err(|_| RevertReason::custom("Invalid signature length"))?;
+
+	signature[64] = match signature[64] {
+		v @ (27 | 28) => v - 27,
+		v @ (0 | 1) => v,
+		_ => return Err(RevertReason::custom("Invalid signature v").into()),
+	};
+
+	if signature[32..64] > SECP256K1N_HALF[..] {
+		return Err(RevertReason::custom("Invalid signature s").into());
+	}
+
+	let digest = domain.hash_typed_data(struct_hash);
+	let public_key = sp_io::crypto::secp256k1_ecdsa_recover(&signature, digest.as_fixed_bytes())
+		.map_err(|_| RevertReason::custom("Invalid signature"))?;
+
+	Ok(H160::from(H256::from(keccak_256(&public_key))))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::solidity::codec::UnboundedString;
+	use hex_literal::hex;
+
+	// The example of the EIP, also used by the test suites of ethers-js.
+	#[derive(Eip712StructHash)]
+	struct Person {
+		name: UnboundedString,
+		wallet: Address,
+	}
+
+	#[derive(Eip712StructHash)]
+	struct Mail {
+		from: Person,
+		to: Person,
+		contents: UnboundedString,
+	}
+
+	fn mail() -> Mail {
+		Mail {
+			from: Person {
+				name: "Cow".into(),
+				wallet: H160(hex!("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826")).into(),
+			},
+			to: Person {
+				name: "Bob".into(),
+				wallet: H160(hex!("bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB")).into(),
+			},
+			contents: "Hello, Bob!".into(),
+		}
+	}
+
+	fn domain(chain_id: u64) -> Domain {
+		Domain::new()
+			.with_name("Ether Mail")
+			.with_version("1")
+			.with_chain_id(chain_id)
+			.with_verifying_contract(H160(hex!("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC")))
+	}
+
+	// Signature of the mail with the private key `keccak256("cow")`.
+	const SIGNATURE: [u8; 65] = hex!(
+		"4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"
+		"07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"
+		"1c"
+	);
+
+	#[test]
+	fn nested_struct_matches_reference_vectors() {
+		assert_eq!(
+			Mail::encode_type(),
+			"Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+		);
+		assert_eq!(
+			Mail::type_hash(),
+			H256(hex!(
+				"a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+			))
+		);
+		assert_eq!(
+			Person::type_hash(),
+			H256(hex!(
+				"b9d8c78acf9b987311de6c7b45bb6a9c8e1bf361fa7fd3467a2163f994c79500"
+			))
+		);
+		assert_eq!(
+			mail().struct_hash(),
+			H256(hex!(
+				"c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+			))
+		);
+		assert_eq!(
+			domain(1).separator(),
+			H256(hex!(
+				"f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+			))
+		);
+		assert_eq!(
+			domain(1).hash_typed_data(mail().struct_hash()),
+			H256(hex!(
+				"be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+			))
+		);
+	}
+
+	#[test]
+	fn domain_separator_depends_on_chain_id() {
+		assert_eq!(
+			domain(42).separator(),
+			H256(hex!(
+				"72a92e66c6b45dace9a24fe78285e46dea44840fd8d5e95ee4fb3928b85508f4"
+			))
+		);
+	}
+
+	#[test]
+	fn recover_signer_works() {
+		let cow = H160(hex!("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"));
+		assert_eq!(
+			recover_signer(&domain(1), mail().struct_hash(), &SIGNATURE),
+			Ok(cow)
+		);
+
+		// `v` in the `{0, 1}` convention.
+		let mut signature = SIGNATURE;
+		signature[64] = 1;
+		assert_eq!(
+			recover_signer(&domain(1), mail().struct_hash(), &signature),
+			Ok(cow)
+		);
+
+		// Signed for another chain.
+		assert_ne!(
+			recover_signer(&domain(42), mail().struct_hash(), &SIGNATURE),
+			Ok(cow)
+		);
+	}
+
+	#[test]
+	fn recover_signer_rejects_malformed_signatures() {
+		let struct_hash = mail().struct_hash();
+
+		assert!(recover_signer(&domain(1), struct_hash, &SIGNATURE[..64]).is_err());
+
+		let mut signature = SIGNATURE;
+		signature[64] = 29;
+		assert!(recover_signer(&domain(1), struct_hash, &signature).is_err());
+
+		// `s` in the upper half of the curve order.
+		let mut signature = SIGNATURE;
+		signature[32..64].copy_from_slice(&[0xff; 32]);
+		assert!(recover_signer(&domain(1), struct_hash, &signature).is_err());
+	}
+}
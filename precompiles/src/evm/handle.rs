@@ -45,6 +45,15 @@ pub trait PrecompileHandleExt: PrecompileHandle {
 	/// called into.
 	fn check_function_modifier(&self, modifier: FunctionModifier) -> MayRevert;
 
+	/// Revert if value is sent with the call.
+	///
+	/// The `#[precompile::payable]` modifier applies to all the selectors of a method, so a
+	/// payable method that must refuse value for some of its selectors can use this guard.
+	fn enforce_not_payable(&self) -> MayRevert;
+
+	/// Revert if no value is sent with the call.
+	fn enforce_payable(&self) -> MayRevert;
+
 	/// Read the selector from the input data.
 	fn read_u32_selector(&self) -> MayRevert<u32>;
 
@@ -89,6 +98,22 @@ impl<T: PrecompileHandle> PrecompileHandleExt for T {
 		)
 	}
 
+	/// Revert if value is sent with the call.
+	fn enforce_not_payable(&self) -> MayRevert {
+		if !self.context().apparent_value.is_zero() {
+			return Err(RevertReason::custom("function not payable").into());
+		}
+		Ok(())
+	}
+
+	/// Revert if no value is sent with the call.
+	fn enforce_payable(&self) -> MayRevert {
+		if self.context().apparent_value.is_zero() {
+			return Err(RevertReason::custom("function requires value").into());
+		}
+		Ok(())
+	}
+
 	/// Read the selector from the input data as u32.
 	fn read_u32_selector(&self) -> MayRevert<u32> {
 		crate::solidity::codec::selector(self.input())
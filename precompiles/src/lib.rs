@@ -29,6 +29,7 @@ pub mod __alloc {
 	pub use ::alloc::*;
 }
 
+pub mod eip712;
 pub mod evm;
 pub mod precompile_set;
 pub mod substrate;
@@ -73,7 +74,10 @@ pub mod prelude {
 					RevertReason,
 				},
 			},
-			substrate::{RuntimeHelper, TryDispatchError},
+			substrate::{
+				u256_to_balance, BalanceOverflow, RuntimeHelper, StorageReader, SubstrateStorage,
+				TryDispatchError,
+			},
 			EvmResult,
 		},
 		alloc::string::String,
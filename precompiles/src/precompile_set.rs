@@ -29,7 +29,7 @@ use alloc::{collections::btree_map::BTreeMap, vec, vec::Vec};
 use core::{cell::RefCell, marker::PhantomData, ops::RangeInclusive};
 use fp_evm::{
 	ExitError, IsPrecompileResult, Precompile, PrecompileFailure, PrecompileHandle,
-	PrecompileResult, PrecompileSet, ACCOUNT_CODES_METADATA_PROOF_SIZE,
+	PrecompileResult, PrecompileSet, PrecompileSetList, ACCOUNT_CODES_METADATA_PROOF_SIZE,
 };
 use frame_support::pallet_prelude::Get;
 use impl_trait_for_tuples::impl_for_tuples;
@@ -90,6 +90,15 @@ pub trait PrecompileChecks {
 		None
 	}
 
+	#[inline(always)]
+	/// How many subcalls can a single call of this precompile make? `None` means there is no
+	/// limit.
+	///
+	/// If all checks return None, defaults to `None` (no limit).
+	fn subcall_limit() -> Option<Option<u32>> {
+		None
+	}
+
 	/// Summarize the checks when being called by a smart contract.
 	fn callable_by_smart_contract_summary() -> Option<String> {
 		None
@@ -138,6 +147,8 @@ pub struct PrecompileCheckSummary {
 	pub name: Option<String>,
 	pub precompile_kind: PrecompileKind,
 	pub recursion_limit: Option<u16>,
+	/// How many subcalls a single call can make, `None` if there is no limit.
+	pub subcall_limit: Option<u32>,
 	pub accept_delegate_call: bool,
 	pub callable_by_smart_contract: String,
 	pub callable_by_precompile: String,
@@ -200,6 +211,17 @@ impl PrecompileChecks for Tuple {
 		None
 	}
 
+	#[inline(always)]
+	fn subcall_limit() -> Option<Option<u32>> {
+		for_tuples!(#(
+			if let Some(check) = Tuple::subcall_limit() {
+				return Some(check);
+			}
+		)*);
+
+		None
+	}
+
 	fn callable_by_smart_contract_summary() -> Option<String> {
 		for_tuples!(#(
 			if let Some(check) = Tuple::callable_by_smart_contract_summary() {
@@ -221,6 +243,16 @@ impl PrecompileChecks for Tuple {
 	}
 }
 
+/// Subcall limit of a precompile with the checks `C`, which can't make any when subcalls are not
+/// allowed.
+fn summarize_subcall_limit<C: PrecompileChecks>() -> Option<u32> {
+	if C::allow_subcalls().unwrap_or(false) {
+		C::subcall_limit().unwrap_or(None)
+	} else {
+		Some(0)
+	}
+}
+
 /// Precompile can be called using DELEGATECALL/CALLCODE.
 pub struct AcceptDelegateCall;
 
@@ -246,6 +278,18 @@ impl<const R: u16> PrecompileChecks for SubcallWithMaxNesting<R> {
 	}
 }
 
+/// Precompile can make at most `N` subcalls each time it is called, so a batching precompile
+/// can't fan out without bounds. Subcalls must still be allowed, e.g. with
+/// `SubcallWithMaxNesting`.
+pub struct SubcallWithMaxCount<const N: u32>;
+
+impl<const N: u32> PrecompileChecks for SubcallWithMaxCount<N> {
+	#[inline(always)]
+	fn subcall_limit() -> Option<Option<u32>> {
+		Some(Some(N))
+	}
+}
+
 pub trait SelectorFilter {
 	fn is_allowed(_caller: H160, _selector: Option<u32>) -> bool;
 
@@ -407,6 +451,8 @@ impl<const N: u64> Get<H160> for AddressU64<N> {
 pub struct RestrictiveHandle<'a, H> {
 	handle: &'a mut H,
 	allow_subcalls: bool,
+	subcall_limit: Option<u32>,
+	subcall_count: u32,
 }
 
 impl<'a, H: PrecompileHandle> PrecompileHandle for RestrictiveHandle<'a, H> {
@@ -426,6 +472,17 @@ impl<'a, H: PrecompileHandle> PrecompileHandle for RestrictiveHandle<'a, H> {
 			);
 		}
 
+		if self
+			.subcall_limit
+			.is_some_and(|limit| self.subcall_count >= limit)
+		{
+			return (
+				evm::ExitReason::Revert(evm::ExitRevert::Reverted),
+				crate::solidity::revert::revert_as_bytes("too many subcalls for this precompile"),
+			);
+		}
+		self.subcall_count += 1;
+
 		self.handle
 			.call(address, transfer, input, target_gas, is_static, context)
 	}
@@ -521,6 +578,15 @@ pub trait PrecompileSetFragment {
 	/// Return the list of addresses covered by this fragment.
 	fn used_addresses(&self) -> Vec<H160>;
 
+	/// Return the address ranges reserved by this fragment, where contracts can't be deployed.
+	/// Defaults to the addresses covered by this fragment.
+	fn reserved_address_ranges(&self) -> Vec<RangeInclusive<H160>> {
+		self.used_addresses()
+			.into_iter()
+			.map(|address| address..=address)
+			.collect()
+	}
+
 	/// Summarize
 	fn summarize_checks(&self) -> Vec<PrecompileCheckSummary>;
 }
@@ -588,6 +654,8 @@ where
 		let mut handle = RestrictiveHandle {
 			handle,
 			allow_subcalls,
+			subcall_limit: C::subcall_limit().unwrap_or(None),
+			subcall_count: 0,
 		};
 
 		let res = P::execute(&mut handle);
@@ -625,6 +693,7 @@ where
 			name: None,
 			precompile_kind: PrecompileKind::Single(A::get()),
 			recursion_limit: C::recursion_limit().unwrap_or(Some(0)),
+			subcall_limit: summarize_subcall_limit::<C>(),
 			accept_delegate_call: C::accept_delegate_call().unwrap_or(false),
 			callable_by_smart_contract: C::callable_by_smart_contract_summary()
 				.unwrap_or_else(|| "Not callable".into()),
@@ -711,6 +780,8 @@ where
 		let mut handle = RestrictiveHandle {
 			handle,
 			allow_subcalls,
+			subcall_limit: C::subcall_limit().unwrap_or(None),
+			subcall_count: 0,
 		};
 
 		let res = self.precompile_set.execute(&mut handle);
@@ -752,6 +823,16 @@ where
 		vec![]
 	}
 
+	fn reserved_address_ranges(&self) -> Vec<RangeInclusive<H160>> {
+		// The whole prefix is reserved.
+		let prefix = A::get();
+		let mut start = H160::zero();
+		let mut end = H160::repeat_byte(0xff);
+		start.as_bytes_mut()[..prefix.len()].copy_from_slice(prefix);
+		end.as_bytes_mut()[..prefix.len()].copy_from_slice(prefix);
+		vec![start..=end]
+	}
+
 	fn summarize_checks(&self) -> Vec<PrecompileCheckSummary> {
 		let prefix = A::get();
 
@@ -759,6 +840,7 @@ where
 			name: None,
 			precompile_kind: PrecompileKind::Prefixed(prefix.to_vec()),
 			recursion_limit: C::recursion_limit().unwrap_or(Some(0)),
+			subcall_limit: summarize_subcall_limit::<C>(),
 			accept_delegate_call: C::accept_delegate_call().unwrap_or(false),
 			callable_by_smart_contract: C::callable_by_smart_contract_summary()
 				.unwrap_or_else(|| "Not callable".into()),
@@ -821,6 +903,7 @@ where
 			name: None,
 			precompile_kind: PrecompileKind::Single(A::get()),
 			recursion_limit: Some(0),
+			subcall_limit: Some(0),
 			accept_delegate_call: true,
 			callable_by_smart_contract: "Reverts in all cases".into(),
 			callable_by_precompile: "Reverts in all cases".into(),
@@ -880,6 +963,7 @@ where
 			name: None,
 			precompile_kind: PrecompileKind::Multiple(A::get()),
 			recursion_limit: Some(0),
+			subcall_limit: Some(0),
 			accept_delegate_call: true,
 			callable_by_smart_contract: "Reverts in all cases".into(),
 			callable_by_precompile: "Reverts in all cases".into(),
@@ -942,6 +1026,7 @@ where
 			name: None,
 			precompile_kind: PrecompileKind::Single(A::get()),
 			recursion_limit: Some(0),
+			subcall_limit: Some(0),
 			accept_delegate_call: true,
 			callable_by_smart_contract: "Reverts in all cases".into(),
 			callable_by_precompile: "Reverts in all cases".into(),
@@ -1013,6 +1098,17 @@ impl PrecompileSetFragment for Tuple {
 		used_addresses
 	}
 
+	fn reserved_address_ranges(&self) -> Vec<RangeInclusive<H160>> {
+		let mut ranges = vec![];
+
+		for_tuples!(#(
+			let mut inner = self.Tuple.reserved_address_ranges();
+			ranges.append(&mut inner);
+		)*);
+
+		ranges
+	}
+
 	fn summarize_checks(&self) -> Vec<PrecompileCheckSummary> {
 		let mut checks = Vec::new();
 
@@ -1093,6 +1189,10 @@ where
 		self.inner.used_addresses()
 	}
 
+	fn reserved_address_ranges(&self) -> Vec<RangeInclusive<H160>> {
+		vec![self.range.clone()]
+	}
+
 	fn summarize_checks(&self) -> Vec<PrecompileCheckSummary> {
 		self.inner.summarize_checks()
 	}
@@ -1130,6 +1230,18 @@ impl<R: pallet_evm::Config, P: PrecompileSetFragment> PrecompileSet for Precompi
 	}
 }
 
+impl<R: pallet_evm::Config, P: PrecompileSetFragment> PrecompileSetList
+	for PrecompileSetBuilder<R, P>
+{
+	fn precompiles(&self) -> Vec<H160> {
+		self.inner.used_addresses()
+	}
+
+	fn reserved_address_ranges(&self) -> Vec<RangeInclusive<H160>> {
+		self.inner.reserved_address_ranges()
+	}
+}
+
 impl<R, P: IsActivePrecompile> IsActivePrecompile for PrecompileSetBuilder<R, P> {
 	fn is_active_precompile(&self, address: H160, gas: u64) -> IsPrecompileResult {
 		self.inner.is_active_precompile(address, gas)
@@ -1158,10 +1270,98 @@ impl<R: pallet_evm::Config, P: PrecompileSetFragment> PrecompileSetBuilder<R, P>
 
 	/// Return the list of H160 addresses contained in this PrecompileSet.
 	pub fn used_addresses_h160() -> impl Iterator<Item = H160> {
-		Self::new().inner.used_addresses().into_iter()
+		Self::new().precompiles().into_iter()
 	}
 
 	pub fn summarize_checks(&self) -> Vec<PrecompileCheckSummary> {
 		self.inner.summarize_checks()
 	}
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+	use super::*;
+	use crate::testing::{MockHandle, Subcall, SubcallOutput};
+
+	/// A batching precompile making `count` subcalls, which fails on the first one which doesn't
+	/// succeed.
+	fn batch(handle: &mut impl PrecompileHandle, count: u32) -> EvmResult<u32> {
+		let context = handle.context().clone();
+		for done in 0..count {
+			let (reason, output) =
+				handle.call(H160::repeat_byte(0x10), None, vec![], None, false, &context);
+			if !matches!(reason, evm::ExitReason::Succeed(_)) {
+				return Err(PrecompileFailure::Revert {
+					exit_status: evm::ExitRevert::Reverted,
+					output: [done.to_be_bytes().to_vec(), output].concat(),
+				});
+			}
+		}
+		Ok(count)
+	}
+
+	fn mock_handle() -> MockHandle {
+		let mut handle = MockHandle::new(
+			H160::repeat_byte(1),
+			evm::Context {
+				address: H160::repeat_byte(1),
+				caller: H160::repeat_byte(2),
+				apparent_value: 0.into(),
+			},
+		);
+		handle.gas_limit = u64::MAX;
+		handle.subcall_handle = Some(Box::new(|_: Subcall| SubcallOutput::succeed()));
+		handle
+	}
+
+	#[test]
+	fn subcalls_beyond_the_limit_revert() {
+		let mut mock = mock_handle();
+		let mut handle = RestrictiveHandle {
+			handle: &mut mock,
+			allow_subcalls: true,
+			subcall_limit: <SubcallWithMaxCount<3>>::subcall_limit().unwrap(),
+			subcall_count: 0,
+		};
+		assert_eq!(batch(&mut handle, 3), Ok(3));
+
+		let mut handle = RestrictiveHandle {
+			handle: &mut mock,
+			allow_subcalls: true,
+			subcall_limit: Some(3),
+			subcall_count: 0,
+		};
+		// The 4th subcall fails, and the 3 first ones succeeded.
+		assert_eq!(
+			batch(&mut handle, 4),
+			Err(PrecompileFailure::Revert {
+				exit_status: evm::ExitRevert::Reverted,
+				output: [
+					3u32.to_be_bytes().to_vec(),
+					crate::solidity::revert::revert_as_bytes(
+						"too many subcalls for this precompile"
+					)
+				]
+				.concat(),
+			})
+		);
+	}
+
+	#[test]
+	fn subcalls_are_unlimited_by_default() {
+		assert_eq!(<SubcallWithMaxNesting<1>>::subcall_limit(), None);
+		assert_eq!(
+			<(SubcallWithMaxNesting<1>, SubcallWithMaxCount<3>)>::subcall_limit(),
+			Some(Some(3))
+		);
+
+		let mut mock = mock_handle();
+		let mut handle = RestrictiveHandle {
+			handle: &mut mock,
+			allow_subcalls: true,
+			subcall_limit: <SubcallWithMaxNesting<1>>::subcall_limit().unwrap_or(None),
+			subcall_count: 0,
+		};
+		assert_eq!(batch(&mut handle, 100), Ok(100));
+	}
+}
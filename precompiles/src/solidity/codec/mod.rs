@@ -25,10 +25,10 @@ pub mod native;
 #[cfg(any(feature = "codec-xcm", test))]
 pub mod xcm;
 
-use crate::solidity::revert::{MayRevert, RevertReason};
+use crate::solidity::revert::{InjectBacktrace, MayRevert, RevertReason};
 use alloc::{vec, vec::Vec};
 use core::{convert::TryInto, marker::PhantomData, ops::Range};
-use sp_core::{H256, U256};
+use sp_core::{H160, H256, U256};
 
 pub use alloc::string::String;
 pub use bytes::{BoundedBytes, BoundedString, UnboundedBytes, UnboundedString};
@@ -176,6 +176,36 @@ impl<'inner> Reader<'inner> {
 		})
 	}
 
+	/// Read an `address[]` of at most `max_len` items, such as the recipients of a batch, reverting
+	/// before reading any item if the array is longer.
+	///
+	/// Addresses are read from the last 20 bytes of their word. With `strict`, addresses whose
+	/// first 12 bytes aren't zero are rejected instead of being truncated.
+	pub fn read_address_array(&mut self, max_len: usize, strict: bool) -> MayRevert<Vec<Address>> {
+		let mut inner_reader = self.read_pointer()?;
+
+		let array_size: usize = inner_reader
+			.read::<U256>()
+			.map_err(|_| RevertReason::read_out_of_bounds("length"))?
+			.try_into()
+			.map_err(|_| RevertReason::value_is_too_large("length"))?;
+
+		if array_size > max_len {
+			return Err(RevertReason::custom("array too large").into());
+		}
+
+		let mut addresses = Vec::with_capacity(array_size);
+		for i in 0..array_size {
+			let word = inner_reader.read::<H256>().in_array(i)?;
+			if strict && word[..12].iter().any(|byte| *byte != 0) {
+				return Err(RevertReason::custom("address has dirty high bytes").in_array(i));
+			}
+			addresses.push(Address(H160::from_slice(&word[12..])));
+		}
+
+		Ok(addresses)
+	}
+
 	/// Read remaining bytes
 	pub fn read_till_end(&mut self) -> MayRevert<&[u8]> {
 		let range = self.move_cursor(self.input.len() - self.cursor)?;
@@ -363,3 +393,105 @@ where
 		P::signature()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn static_tuple_return_is_encoded_inline() {
+		let value = (U256::from(1), Address(H160::repeat_byte(0x11)), true);
+		let encoded = encode_return_value(value);
+
+		assert_eq!(
+			encoded,
+			hex_literal::hex!(
+				"0000000000000000000000000000000000000000000000000000000000000001"
+				"0000000000000000000000001111111111111111111111111111111111111111"
+				"0000000000000000000000000000000000000000000000000000000000000001"
+			)
+		);
+		assert_eq!(decode_return_value(&encoded), Ok(value));
+	}
+
+	#[test]
+	fn dynamic_tuple_return_uses_head_and_tail() {
+		let value = (
+			U256::from(1),
+			UnboundedBytes::from(b"abc"),
+			Address(H160::repeat_byte(0x11)),
+			false,
+		);
+		let encoded = encode_return_value(value.clone());
+
+		assert_eq!(
+			encoded,
+			hex_literal::hex!(
+				// Head: the bytes are replaced by the offset of their tail.
+				"0000000000000000000000000000000000000000000000000000000000000001"
+				"0000000000000000000000000000000000000000000000000000000000000080"
+				"0000000000000000000000001111111111111111111111111111111111111111"
+				"0000000000000000000000000000000000000000000000000000000000000000"
+				// Tail: the length of the bytes, then the right-padded bytes.
+				"0000000000000000000000000000000000000000000000000000000000000003"
+				"6162630000000000000000000000000000000000000000000000000000000000"
+			)
+		);
+		assert_eq!(decode_return_value(&encoded), Ok(value));
+	}
+
+	fn address_array(words: &[H256]) -> Vec<u8> {
+		let mut input = H256::from_low_u64_be(32).as_bytes().to_vec();
+		input.extend_from_slice(H256::from_low_u64_be(words.len() as u64).as_bytes());
+		for word in words {
+			input.extend_from_slice(word.as_bytes());
+		}
+		input
+	}
+
+	#[test]
+	fn address_array_is_read() {
+		let addresses = vec![
+			Address(H160::repeat_byte(0x11)),
+			Address(H160::repeat_byte(0x22)),
+		];
+		let input = encode_arguments(addresses.clone());
+
+		assert_eq!(
+			Reader::new(&input).read_address_array(2, true),
+			Ok(addresses)
+		);
+		assert_eq!(
+			Reader::new(&address_array(&[])).read_address_array(0, true),
+			Ok(vec![])
+		);
+	}
+
+	#[test]
+	fn address_array_over_the_max_length_is_rejected() {
+		let input = encode_arguments(vec![Address(H160::repeat_byte(0x11)); 3]);
+
+		assert_eq!(
+			Reader::new(&input).read_address_array(2, false),
+			Err(RevertReason::custom("array too large").into())
+		);
+	}
+
+	#[test]
+	fn dirty_addresses_are_only_rejected_in_strict_mode() {
+		let dirty = H256::repeat_byte(0x11);
+		let input = address_array(&[H160::repeat_byte(0x22).into(), dirty]);
+
+		assert_eq!(
+			Reader::new(&input).read_address_array(2, true),
+			Err(RevertReason::custom("address has dirty high bytes").in_array(1))
+		);
+		assert_eq!(
+			Reader::new(&input).read_address_array(2, false),
+			Ok(vec![
+				Address(H160::repeat_byte(0x22)),
+				Address(H160::repeat_byte(0x11)),
+			])
+		);
+	}
+}
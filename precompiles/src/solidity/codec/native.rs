@@ -389,6 +389,10 @@ impl<T, S> Default for BoundedVec<T, S> {
 }
 
 impl<T, S> BoundedVec<T, S> {
+	pub fn as_slice(&self) -> &[T] {
+		&self.inner
+	}
+
 	pub fn len(&self) -> usize {
 		self.inner.len()
 	}
@@ -19,21 +19,33 @@
 //! Utils related to Substrate features:
 //! - Substrate call dispatch.
 //! - Substrate DB read and write costs
+//! - Substrate storage reads
+//! - Conversion of EVM amounts to balances
 
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 
+use scale_codec::Decode;
+
 // Substrate
 use frame_support::{
 	dispatch::{GetDispatchInfo, PostDispatchInfo},
 	traits::Get,
 	weights::Weight,
 };
-use sp_runtime::{traits::Dispatchable, DispatchError};
+use sp_core::U256;
+use sp_runtime::{
+	traits::{Bounded, Dispatchable},
+	DispatchError,
+};
 // Frontier
 use fp_evm::{ExitError, PrecompileFailure, PrecompileHandle};
 use pallet_evm::GasWeightMapping;
 
-use crate::{evm::handle::using_precompile_handle, solidity::revert::revert};
+use crate::{
+	evm::handle::using_precompile_handle,
+	solidity::revert::{revert, MayRevert, RevertReason},
+};
 
 #[derive(Debug)]
 pub enum TryDispatchError {
@@ -155,3 +167,89 @@ where
 		)
 	}
 }
+
+/// Read access to the Substrate storage, so precompiles reading pallet storage can be tested
+/// against a mocked storage (see `testing::MockSubstrateStorage`).
+pub trait StorageReader {
+	/// Raw value stored under `key`.
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+
+	/// SCALE decoded value stored under `key`, `None` if it is missing or cannot be decoded.
+	fn read<V: Decode>(&self, key: &[u8]) -> Option<V> {
+		self.get(key)
+			.and_then(|value| V::decode(&mut value.as_slice()).ok())
+	}
+}
+
+/// `StorageReader` reading the storage of the runtime being executed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SubstrateStorage;
+
+impl StorageReader for SubstrateStorage {
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+		sp_io::storage::get(key).map(|value| value.to_vec())
+	}
+}
+
+/// What to do with an EVM amount which doesn't fit in the balance type of the runtime.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BalanceOverflow {
+	/// Revert with `RevertReason::ValueIsTooLarge`.
+	#[default]
+	Revert,
+	/// Use the largest balance instead.
+	Saturate,
+}
+
+/// Convert an EVM `uint256` amount to a balance of the runtime, applying `policy` when the
+/// amount is too large, so it is never silently truncated.
+pub fn u256_to_balance<Balance>(amount: U256, policy: BalanceOverflow) -> MayRevert<Balance>
+where
+	Balance: TryFrom<U256> + Bounded,
+{
+	Balance::try_from(amount).or_else(|_| match policy {
+		BalanceOverflow::Revert => Err(RevertReason::value_is_too_large("balance type").into()),
+		BalanceOverflow::Saturate => Ok(Balance::max_value()),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn amounts_in_range_are_converted() {
+		for policy in [BalanceOverflow::Revert, BalanceOverflow::Saturate] {
+			assert_eq!(
+				u256_to_balance::<u128>(U256::from(1_000), policy),
+				Ok(1_000)
+			);
+			assert_eq!(
+				u256_to_balance::<u128>(U256::from(u128::MAX), policy),
+				Ok(u128::MAX)
+			);
+		}
+	}
+
+	#[test]
+	fn too_large_amounts_follow_the_policy() {
+		let amount = U256::from(u128::MAX) + 1;
+		assert_eq!(
+			u256_to_balance::<u128>(amount, BalanceOverflow::Revert),
+			Err(RevertReason::value_is_too_large("balance type").into())
+		);
+		assert_eq!(
+			u256_to_balance::<u128>(amount, BalanceOverflow::Saturate),
+			Ok(u128::MAX)
+		);
+		assert_eq!(
+			u256_to_balance::<u64>(U256::from(u64::MAX) + 1, BalanceOverflow::Saturate),
+			Ok(u64::MAX)
+		);
+	}
+
+	#[test]
+	fn reverts_by_default() {
+		assert_eq!(BalanceOverflow::default(), BalanceOverflow::Revert);
+	}
+}
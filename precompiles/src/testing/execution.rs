@@ -113,7 +113,7 @@ impl<'p, P: PrecompileSet> PrecompilesTester<'p, P> {
 
 	fn assert_optionals(&self) {
 		if let Some(cost) = &self.expected_cost {
-			assert_eq!(&self.handle.gas_used, cost);
+			self.handle.assert_gas_used(*cost);
 		}
 
 		if let Some(logs) = &self.expected_logs {
@@ -0,0 +1,162 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test externalities builder to write integration tests of precompiles executed through the
+//! real EVM stack runner, against any mock runtime implementing `pallet_evm::Config`.
+
+use core::marker::PhantomData;
+use std::collections::{BTreeMap, BTreeSet};
+
+use fp_evm::{CallInfo, GenesisAccount};
+use pallet_evm::{BalanceOf, Runner, RunnerError};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{traits::UniqueSaturatedInto, BuildStorage};
+
+/// Builds the test externalities of `Runtime` with the given EVM accounts.
+///
+/// ```ignore
+/// ExtBuilder::<Runtime>::default()
+/// 	.with_balances(vec![(Alice.into(), 1_000.into())])
+/// 	.with_evm_account(Bob, code, storage)
+/// 	.with_suicided(Charlie)
+/// 	.build()
+/// 	.execute_with(|| {
+/// 		let info = call_precompile::<Runtime>(Alice, precompile, input, 1_000_000).unwrap();
+/// 	});
+/// ```
+pub struct ExtBuilder<Runtime> {
+	accounts: BTreeMap<H160, GenesisAccount>,
+	suicided: BTreeSet<H160>,
+	_marker: PhantomData<Runtime>,
+}
+
+impl<Runtime> Default for ExtBuilder<Runtime> {
+	fn default() -> Self {
+		Self {
+			accounts: BTreeMap::new(),
+			suicided: BTreeSet::new(),
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<Runtime> ExtBuilder<Runtime>
+where
+	Runtime: pallet_evm::Config,
+	U256: UniqueSaturatedInto<BalanceOf<Runtime>>,
+{
+	/// Fund the given accounts.
+	pub fn with_balances(mut self, balances: Vec<(H160, U256)>) -> Self {
+		for (address, balance) in balances {
+			self.account(address).balance = balance;
+		}
+		self
+	}
+
+	/// Deploy the given code and storage at the given address.
+	pub fn with_evm_account(
+		mut self,
+		address: impl Into<H160>,
+		code: Vec<u8>,
+		storage: BTreeMap<H256, H256>,
+	) -> Self {
+		let account = self.account(address.into());
+		account.code = code;
+		account.storage = storage;
+		self
+	}
+
+	/// Leave the given account as a contract which self-destructed: its code and storage are
+	/// removed, as the runner does at the end of the transaction, while its balance is kept.
+	pub fn with_suicided(mut self, address: impl Into<H160>) -> Self {
+		self.suicided.insert(address.into());
+		self
+	}
+
+	/// Build the externalities, starting at block 1.
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.expect("Frame system builds valid default genesis config");
+
+		pallet_evm::GenesisConfig::<Runtime> {
+			accounts: self.accounts,
+			_marker: PhantomData,
+		}
+		.assimilate_storage(&mut t)
+		.expect("Pallet evm storage can be assimilated");
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			frame_system::Pallet::<Runtime>::set_block_number(1u32.into());
+			for address in &self.suicided {
+				pallet_evm::Pallet::<Runtime>::remove_account(address);
+			}
+		});
+		ext
+	}
+
+	fn account(&mut self, address: H160) -> &mut GenesisAccount {
+		self.accounts
+			.entry(address)
+			.or_insert_with(|| GenesisAccount {
+				nonce: U256::zero(),
+				balance: U256::zero(),
+				storage: BTreeMap::new(),
+				code: Vec::new(),
+			})
+	}
+}
+
+/// Move the test runtime to the given block, for tests of time-gated precompiles. Precompile
+/// handles carry no block, precompiles read it from the runtime. Must be called within test
+/// externalities.
+pub fn warp_to_block<Runtime>(block_number: u64, timestamp_ms: u64)
+where
+	Runtime: frame_system::Config + pallet_timestamp::Config,
+{
+	frame_system::Pallet::<Runtime>::set_block_number(block_number.unique_saturated_into());
+	pallet_timestamp::Pallet::<Runtime>::set_timestamp(timestamp_ms.unique_saturated_into());
+}
+
+/// Call `to` through the EVM stack runner of `Runtime`, as a non-transactional call so no fee is
+/// charged. Must be called within the externalities built by `ExtBuilder`.
+pub fn call_precompile<Runtime: pallet_evm::Config>(
+	from: impl Into<H160>,
+	to: impl Into<H160>,
+	input: Vec<u8>,
+	gas_limit: u64,
+) -> Result<CallInfo, RunnerError<<Runtime::Runner as Runner<Runtime>>::Error>> {
+	Runtime::Runner::call(
+		from.into(),
+		to.into(),
+		input,
+		U256::zero(),
+		gas_limit,
+		None,
+		None,
+		None,
+		Vec::new(),
+		Vec::new(),
+		false, // non-transactional
+		false, // must not be validated
+		None,
+		None,
+		Runtime::config(),
+	)
+}
@@ -18,7 +18,7 @@
 
 use crate::testing::PrettyLog;
 use alloc::boxed::Box;
-use evm::{ExitRevert, ExitSucceed};
+use evm::{ExitFatal, ExitRevert, ExitSucceed};
 use fp_evm::{Context, ExitError, ExitReason, Log, PrecompileHandle, Transfer};
 use sp_core::{H160, H256};
 
@@ -69,6 +69,20 @@ impl SubcallOutput {
 			logs: Vec::new(),
 		}
 	}
+
+	pub fn fatal(reason: ExitFatal) -> Self {
+		Self {
+			reason: ExitReason::Fatal(reason),
+			output: Vec::new(),
+			cost: 0,
+			logs: Vec::new(),
+		}
+	}
+
+	pub fn with_output(mut self, output: Vec<u8>) -> Self {
+		self.output = output;
+		self
+	}
 }
 
 pub trait SubcallTrait: FnMut(Subcall) -> SubcallOutput + 'static {}
@@ -77,10 +91,50 @@ impl<T: FnMut(Subcall) -> SubcallOutput + 'static> SubcallTrait for T {}
 
 pub type SubcallHandle = Box<dyn SubcallTrait>;
 
+/// Dispatches subcalls to the handler registered for their address, and reverts the subcalls to
+/// other addresses.
+///
+/// `SubcallTrait` can only be implemented by closures, so the router is turned into one with
+/// `into_handle`.
+#[derive(Default)]
+pub struct SubcallRouter {
+	routes: Vec<(H160, SubcallHandle)>,
+}
+
+impl SubcallRouter {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Handle the subcalls to `address` with `handler`. The first handler registered for an
+	/// address is used.
+	pub fn route(mut self, address: H160, handler: impl SubcallTrait) -> Self {
+		self.routes.push((address, Box::new(handler)));
+		self
+	}
+
+	pub fn dispatch(&mut self, subcall: Subcall) -> SubcallOutput {
+		match self
+			.routes
+			.iter_mut()
+			.find(|(address, _)| *address == subcall.address)
+		{
+			Some((_, handler)) => handler(subcall),
+			None => SubcallOutput::revert(),
+		}
+	}
+
+	pub fn into_handle(mut self) -> impl SubcallTrait {
+		move |subcall: Subcall| self.dispatch(subcall)
+	}
+}
+
 /// Mock handle to write tests for precompiles.
 pub struct MockHandle {
 	pub gas_limit: u64,
 	pub gas_used: u64,
+	/// Every cost recorded through `record_cost`, in order.
+	pub recorded_costs: Vec<u64>,
 	pub logs: Vec<PrettyLog>,
 	pub subcall_handle: Option<SubcallHandle>,
 	pub code_address: H160,
@@ -94,6 +148,7 @@ impl MockHandle {
 		Self {
 			gas_limit: u64::MAX,
 			gas_used: 0,
+			recorded_costs: Vec::new(),
 			logs: vec![],
 			subcall_handle: None,
 			code_address,
@@ -102,6 +157,70 @@ impl MockHandle {
 			is_static: false,
 		}
 	}
+
+	/// Panics with the breakdown of the recorded costs if the used gas isn't `expected`.
+	#[track_caller]
+	pub fn assert_gas_used(&self, expected: u64) {
+		if self.gas_used != expected {
+			panic!(
+				"expected gas_used={expected}, got {}\n{}",
+				self.gas_used,
+				self.format_gas_breakdown()
+			);
+		}
+	}
+
+	/// Panics with the breakdown of the recorded costs if the remaining gas isn't `expected`.
+	#[track_caller]
+	pub fn assert_remaining_gas(&self, expected: u64) {
+		let remaining = self.gas_limit.saturating_sub(self.gas_used);
+		if remaining != expected {
+			panic!(
+				"expected remaining_gas={expected}, got {remaining} (gas_limit={})\n{}",
+				self.gas_limit,
+				self.format_gas_breakdown()
+			);
+		}
+	}
+
+	/// The logs emitted on behalf of `address`, in order.
+	pub fn logs_for_address(&self, address: H160) -> Vec<&PrettyLog> {
+		self.logs
+			.iter()
+			.filter(|log| log.0.address == address)
+			.collect()
+	}
+
+	/// Panics if the log at `index` wasn't emitted on behalf of `expected_address`.
+	#[track_caller]
+	pub fn assert_log_address(&self, index: usize, expected_address: H160) {
+		let Some(log) = self.logs.get(index) else {
+			panic!(
+				"expected a log at index {index}, only {} were emitted",
+				self.logs.len()
+			);
+		};
+		if log.0.address != expected_address {
+			panic!(
+				"expected log {index} to be emitted by {expected_address:?}, got {:?}",
+				log
+			);
+		}
+	}
+
+	fn format_gas_breakdown(&self) -> String {
+		if self.recorded_costs.is_empty() {
+			return "no cost was recorded".into();
+		}
+
+		let mut total = 0u64;
+		let mut breakdown = String::from("recorded costs:");
+		for (index, cost) in self.recorded_costs.iter().enumerate() {
+			total = total.saturating_add(*cost);
+			breakdown.push_str(&format!("\n  #{index}: {cost} (total {total})"));
+		}
+		breakdown
+	}
 }
 
 impl PrecompileHandle for MockHandle {
@@ -142,6 +261,11 @@ impl PrecompileHandle for MockHandle {
 					context: context.clone(),
 				});
 
+				// Fatal exits abort the whole execution, nothing of the subcall is kept.
+				if let ExitReason::Fatal(fatal) = reason {
+					return (ExitReason::Fatal(fatal), vec![]);
+				}
+
 				if self.record_cost(cost).is_err() {
 					return (ExitReason::Error(ExitError::OutOfGas), vec![]);
 				}
@@ -158,6 +282,7 @@ impl PrecompileHandle for MockHandle {
 	}
 
 	fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+		self.recorded_costs.push(cost);
 		self.gas_used += cost;
 
 		if self.gas_used > self.gas_limit {
@@ -224,3 +349,143 @@ impl PrecompileHandle for MockHandle {
 		false
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn handle() -> MockHandle {
+		let mut handle = MockHandle::new(
+			H160::repeat_byte(1),
+			Context {
+				address: H160::repeat_byte(1),
+				caller: H160::repeat_byte(2),
+				apparent_value: 0.into(),
+			},
+		);
+		handle.gas_limit = 10_000;
+		handle.record_cost(1_000).unwrap();
+		handle.record_cost(200).unwrap();
+		handle
+	}
+
+	#[test]
+	fn gas_assertions_pass_on_the_expected_values() {
+		let handle = handle();
+		assert_eq!(handle.recorded_costs, vec![1_000, 200]);
+		handle.assert_gas_used(1_200);
+		handle.assert_remaining_gas(8_800);
+	}
+
+	#[test]
+	#[should_panic(
+		expected = "expected gas_used=1234, got 1200\nrecorded costs:\n  #0: 1000 (total 1000)\n  #1: 200 (total 1200)"
+	)]
+	fn gas_used_assertion_shows_the_recorded_costs() {
+		handle().assert_gas_used(1_234);
+	}
+
+	#[test]
+	fn logs_are_filtered_by_address() {
+		let token = H160::repeat_byte(0x10);
+		let mut handle = handle();
+		handle.log(handle.code_address, vec![], vec![1]).unwrap();
+		handle
+			.log(token, vec![H256::repeat_byte(1)], vec![2])
+			.unwrap();
+
+		handle.assert_log_address(0, handle.code_address);
+		handle.assert_log_address(1, token);
+		assert_eq!(
+			handle.logs_for_address(token),
+			vec![&PrettyLog(Log {
+				address: token,
+				topics: vec![H256::repeat_byte(1)],
+				data: vec![2],
+			})]
+		);
+	}
+
+	#[test]
+	#[should_panic(expected = "expected log 0 to be emitted by")]
+	fn log_address_assertion_fails_on_other_address() {
+		let mut handle = handle();
+		handle.log(handle.code_address, vec![], vec![]).unwrap();
+		handle.assert_log_address(0, H160::repeat_byte(0x10));
+	}
+
+	#[test]
+	fn subcall_router_dispatches_by_address() {
+		let routed = H160::repeat_byte(0x10);
+		let mut handle = handle();
+		handle.gas_limit = u64::MAX;
+		handle.subcall_handle = Some(Box::new(
+			SubcallRouter::new()
+				.route(routed, |subcall: Subcall| {
+					SubcallOutput::succeed().with_output(subcall.input)
+				})
+				.into_handle(),
+		));
+		let context = handle.context.clone();
+
+		assert_eq!(
+			handle.call(routed, None, vec![1, 2, 3], None, false, &context),
+			(ExitReason::Succeed(ExitSucceed::Returned), vec![1, 2, 3])
+		);
+		assert_eq!(
+			handle.call(
+				H160::repeat_byte(0x20),
+				None,
+				vec![1],
+				None,
+				false,
+				&context
+			),
+			(ExitReason::Revert(ExitRevert::Reverted), vec![])
+		);
+	}
+
+	#[test]
+	fn fatal_subcalls_terminate_the_parent() {
+		// Emits a log once its subcall returns, unless the subcall failed fatally.
+		fn parent(handle: &mut impl PrecompileHandle) -> Result<(), fp_evm::PrecompileFailure> {
+			let context = handle.context().clone();
+			let (reason, _) =
+				handle.call(H160::repeat_byte(0x10), None, vec![], None, false, &context);
+			if let ExitReason::Fatal(exit_status) = reason {
+				return Err(fp_evm::PrecompileFailure::Fatal { exit_status });
+			}
+			handle.log(handle.code_address(), vec![], vec![])?;
+			Ok(())
+		}
+
+		let mut handle = handle();
+		handle.gas_limit = u64::MAX;
+		handle.subcall_handle = Some(Box::new(|_: Subcall| SubcallOutput {
+			cost: 5_000,
+			logs: vec![Log {
+				address: H160::repeat_byte(0x10),
+				topics: vec![],
+				data: vec![],
+			}],
+			..SubcallOutput::fatal(ExitFatal::NotSupported)
+		}));
+
+		assert_eq!(
+			parent(&mut handle),
+			Err(fp_evm::PrecompileFailure::Fatal {
+				exit_status: ExitFatal::NotSupported
+			})
+		);
+		// Only the cost of the call itself is recorded, and neither the subcall nor the parent
+		// emitted a log.
+		assert_eq!(handle.recorded_costs.len(), 3);
+		assert!(handle.logs.is_empty());
+	}
+
+	#[test]
+	#[should_panic(expected = "expected remaining_gas=9000, got 8800 (gas_limit=10000)")]
+	fn remaining_gas_assertion_fails_on_mismatch() {
+		handle().assert_remaining_gas(9_000);
+	}
+}
@@ -18,15 +18,19 @@
 
 pub mod account;
 pub mod execution;
+pub mod externalities;
 pub mod handle;
 pub mod modifier;
 mod solidity;
+pub mod storage;
 
 pub use account::*;
 pub use execution::*;
+pub use externalities::*;
 pub use handle::*;
 pub use modifier::*;
 pub use solidity::{check_precompile_implements_solidity_interfaces, compute_selector};
+pub use storage::*;
 
 use fp_evm::Log;
 
@@ -0,0 +1,112 @@
+// This file is part of Frontier.
+
+// Copyright (c) Moonsong Labs.
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use frame_support::storage::{StorageMap, StorageValue};
+use scale_codec::{Encode, EncodeLike, FullCodec, FullEncode};
+
+use crate::substrate::StorageReader;
+
+/// In-memory `StorageReader`, to test precompiles reading pallet storage without a runtime.
+#[derive(Clone, Debug, Default)]
+pub struct MockSubstrateStorage {
+	items: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl MockSubstrateStorage {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Store a raw value under the given key.
+	pub fn insert_raw(&mut self, key: Vec<u8>, value: Vec<u8>) {
+		self.items.insert(key, value);
+	}
+
+	/// Store the SCALE encoded value of the storage value `T`.
+	pub fn insert_storage_item<T, V>(&mut self, value: V)
+	where
+		T: StorageValue<V>,
+		V: FullCodec,
+	{
+		self.insert_raw(T::hashed_key().to_vec(), value.encode());
+	}
+
+	/// Store the SCALE encoded value under the given key of the storage map `T`.
+	pub fn insert_map_item<T, K, V>(&mut self, key: impl EncodeLike<K>, value: V)
+	where
+		T: StorageMap<K, V>,
+		K: FullEncode,
+		V: FullCodec,
+	{
+		self.insert_raw(T::hashed_key_for(key), value.encode());
+	}
+}
+
+impl StorageReader for MockSubstrateStorage {
+	fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+		self.items.get(key).cloned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::substrate::SubstrateStorage;
+	use frame_support::{storage_alias, Blake2_128Concat};
+
+	#[storage_alias]
+	type Counter = StorageValue<Mock, u32>;
+
+	#[storage_alias]
+	type Balances = StorageMap<Mock, Blake2_128Concat, u64, u128>;
+
+	#[test]
+	fn mock_storage_reads_scale_encoded_items() {
+		let mut storage = MockSubstrateStorage::new();
+		storage.insert_storage_item::<Counter, _>(42u32);
+		storage.insert_map_item::<Balances, _, _>(7u64, 1_000u128);
+
+		assert_eq!(storage.read::<u32>(&Counter::hashed_key()), Some(42));
+		assert_eq!(
+			storage.read::<u128>(&Balances::hashed_key_for(7u64)),
+			Some(1_000)
+		);
+		assert_eq!(storage.read::<u128>(&Balances::hashed_key_for(8u64)), None);
+	}
+
+	#[test]
+	fn mock_storage_matches_runtime_storage_layout() {
+		let mut storage = MockSubstrateStorage::new();
+		storage.insert_storage_item::<Counter, _>(42u32);
+		storage.insert_map_item::<Balances, _, _>(7u64, 1_000u128);
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			Counter::put(42u32);
+			Balances::insert(7u64, 1_000u128);
+
+			for key in [
+				Counter::hashed_key().to_vec(),
+				Balances::hashed_key_for(7u64),
+			] {
+				assert_eq!(SubstrateStorage.get(&key), storage.get(&key));
+			}
+		});
+	}
+}
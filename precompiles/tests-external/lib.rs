@@ -30,12 +30,16 @@ use frame_support::{
 use sp_core::{H160, H256, U256};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage, Perbill,
+	Perbill,
 };
 // Frontier
-use fp_evm::{ExitReason, ExitRevert, PrecompileFailure, PrecompileHandle};
+use fp_evm::{
+	ExitReason, ExitRevert, ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileSetList,
+};
 use pallet_evm::{CodeMetadata, EnsureAddressNever, EnsureAddressRoot};
 use precompile_utils::{
+	eip712::Domain,
+	evm::handle::PrecompileHandleExt,
 	precompile_set::*,
 	solidity::{codec::Writer, revert::revert},
 	testing::*,
@@ -140,11 +144,54 @@ impl MockPrecompile {
 		}
 	}
 
+	// b53d2468
+	#[precompile::public("batch(uint32)")]
+	fn batch(handle: &mut impl PrecompileHandle, count: u32) -> EvmResult {
+		for _ in 0..count {
+			match handle.call(
+				handle.code_address(),
+				None,
+				// calls success()
+				Writer::new_with_selector(0x0b93381bu32).build(),
+				None,
+				false,
+				&evm::Context {
+					caller: handle.code_address(),
+					address: handle.code_address(),
+					apparent_value: 0.into(),
+				},
+			) {
+				(ExitReason::Succeed(_), _) => (),
+				(ExitReason::Revert(_), v) => {
+					return Err(PrecompileFailure::Revert {
+						exit_status: ExitRevert::Reverted,
+						output: v,
+					})
+				}
+				_ => return Err(revert("unexpected error")),
+			}
+		}
+		Ok(())
+	}
+
 	// 0b93381b
 	#[precompile::public("success()")]
 	fn success(_: &mut impl PrecompileHandle) -> EvmResult {
 		Ok(())
 	}
+
+	// d0e30db0, 3ccfd60b
+	#[precompile::public("deposit()")]
+	#[precompile::public("withdraw()")]
+	#[precompile::payable]
+	fn deposit_or_withdraw(handle: &mut impl PrecompileHandle) -> EvmResult {
+		// Only `deposit()` is payable, and requires value.
+		match handle.read_u32_selector()? {
+			0xd0e30db0 => handle.enforce_payable()?,
+			_ => handle.enforce_not_payable()?,
+		}
+		Ok(())
+	}
 }
 
 #[derive(Default)]
@@ -229,6 +276,11 @@ pub type Precompiles<R> = PrecompileSetBuilder<
 		PrecompileAt<AddressU64<2>, MockPrecompile, CallableByContract>,
 		PrecompileAt<AddressU64<3>, MockPrecompile, CallableByPrecompile>,
 		PrecompileAt<AddressU64<4>, MockPrecompile, SubcallWithMaxNesting<1>>,
+		PrecompileAt<
+			AddressU64<5>,
+			MockPrecompile,
+			(SubcallWithMaxNesting<1>, SubcallWithMaxCount<2>),
+		>,
 	),
 >;
 
@@ -240,6 +292,7 @@ parameter_types! {
 	pub BlockGasLimit: U256 = U256::from(u64::MAX);
 	pub PrecompilesValue: Precompiles<Runtime> = Precompiles::new();
 	pub const WeightPerGas: Weight = Weight::from_parts(1, 0);
+	pub storage ChainId: u64 = 1;
 	pub GasLimitPovSizeRatio: u64 = {
 		let block_gas_limit = BlockGasLimit::get().min(u64::MAX.into()).low_u64();
 		block_gas_limit.saturating_div(MAX_POV_SIZE)
@@ -259,7 +312,8 @@ impl pallet_evm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PrecompilesType = Precompiles<Runtime>;
 	type PrecompilesValue = PrecompilesValue;
-	type ChainId = ();
+	type ReservedAddresses = pallet_evm::PrecompileReservedAddresses<Self>;
+	type ChainId = ChainId;
 	type BlockGasLimit = BlockGasLimit;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
 	type OnChargeTransaction = ();
@@ -269,6 +323,8 @@ impl pallet_evm::Config for Runtime {
 	type GasLimitStorageGrowthRatio = ();
 	type Timestamp = Timestamp;
 	type CreateInnerOriginFilter = ();
+	type ExecutionEvents = frame_support::traits::ConstBool<false>;
+	type ExecutionEventReasonLength = frame_support::traits::ConstU32<256>;
 	type CreateOriginFilter = ();
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Runtime>;
 }
@@ -283,22 +339,7 @@ impl pallet_timestamp::Config for Runtime {
 	type WeightInfo = ();
 }
 
-#[derive(Default)]
-struct ExtBuilder {}
-
-impl ExtBuilder {
-	fn build(self) -> sp_io::TestExternalities {
-		let t = frame_system::GenesisConfig::<Runtime>::default()
-			.build_storage()
-			.expect("Frame system builds valid default genesis config");
-
-		let mut ext = sp_io::TestExternalities::new(t);
-		ext.execute_with(|| {
-			System::set_block_number(1);
-		});
-		ext
-	}
-}
+type ExtBuilder = precompile_utils::testing::ExtBuilder<Runtime>;
 
 fn precompiles() -> Precompiles<Runtime> {
 	PrecompilesValue::get()
@@ -402,6 +443,87 @@ fn subcalls_works_when_allowed() {
 	})
 }
 
+#[test]
+fn subcalls_stop_at_the_limit_of_the_precompile() {
+	ExtBuilder::default().build().execute_with(|| {
+		let subcalls = Rc::new(RefCell::new(0u32));
+		{
+			let subcalls = Rc::clone(&subcalls);
+			precompiles()
+				.prepare_test(Alice, H160::from_low_u64_be(5), PCall::batch { count: 2 })
+				.with_subcall_handle(move |Subcall { .. }| {
+					*subcalls.borrow_mut() += 1;
+					SubcallOutput::succeed()
+				})
+				.execute_returns(());
+		}
+		assert_eq!(*subcalls.borrow(), 2);
+
+		// The limit applies to each call of the precompile: the 3rd subcall fails without
+		// reaching the handle.
+		{
+			let subcalls = Rc::clone(&subcalls);
+			precompiles()
+				.prepare_test(Alice, H160::from_low_u64_be(5), PCall::batch { count: 3 })
+				.with_subcall_handle(move |Subcall { .. }| {
+					*subcalls.borrow_mut() += 1;
+					SubcallOutput::succeed()
+				})
+				.execute_reverts(|output| output == b"too many subcalls for this precompile");
+		}
+		assert_eq!(*subcalls.borrow(), 4);
+	})
+}
+
+#[test]
+fn subcall_limit_is_summarized() {
+	let limits: Vec<_> = precompiles()
+		.summarize_checks()
+		.into_iter()
+		.map(|summary| summary.subcall_limit)
+		.collect();
+	// Only the 4th and 5th precompiles can do subcalls.
+	assert_eq!(limits, vec![Some(0), Some(0), Some(0), None, Some(2)]);
+}
+
+#[test]
+fn enforce_not_payable_reverts_with_value() {
+	ExtBuilder::default().build().execute_with(|| {
+		let withdraw = Writer::new_with_selector(0x3ccfd60bu32).build();
+
+		precompiles()
+			.prepare_test(Alice, H160::from_low_u64_be(1), withdraw.clone())
+			.with_value(1)
+			.execute_reverts(|r| r == b"function not payable");
+
+		precompiles()
+			.prepare_test(Alice, H160::from_low_u64_be(1), withdraw)
+			.execute_returns(());
+	})
+}
+
+#[test]
+fn enforce_payable_requires_value() {
+	ExtBuilder::default().build().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				Alice,
+				H160::from_low_u64_be(1),
+				PCall::deposit_or_withdraw {},
+			)
+			.with_value(1)
+			.execute_returns(());
+
+		precompiles()
+			.prepare_test(
+				Alice,
+				H160::from_low_u64_be(1),
+				PCall::deposit_or_withdraw {},
+			)
+			.execute_reverts(|r| r == b"function requires value");
+	})
+}
+
 #[test]
 fn get_address_type_works_for_eoa() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -419,8 +541,8 @@ fn get_address_type_works_for_eoa() {
 fn get_address_type_works_for_precompile() {
 	ExtBuilder::default().build().execute_with(|| {
 		let precompiles: Vec<H160> = Precompiles::<Runtime>::used_addresses_h160().collect();
-		// We expect 4 precompiles
-		assert_eq!(precompiles.len(), 4);
+		// We expect 5 precompiles
+		assert_eq!(precompiles.len(), 5);
 
 		let mut handle = MockPrecompileHandle::default();
 		precompiles.iter().cloned().for_each(|precompile| {
@@ -432,6 +554,22 @@ fn get_address_type_works_for_precompile() {
 	})
 }
 
+#[test]
+fn precompile_set_lists_its_precompiles() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			precompiles().precompiles(),
+			vec![
+				H160::from_low_u64_be(1),
+				H160::from_low_u64_be(2),
+				H160::from_low_u64_be(3),
+				H160::from_low_u64_be(4),
+				H160::from_low_u64_be(5),
+			]
+		);
+	})
+}
+
 #[test]
 fn get_address_type_works_for_smart_contract() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -465,3 +603,88 @@ fn get_address_type_works_for_smart_contract_being_constructed() {
 		);
 	})
 }
+
+#[test]
+fn warp_to_block_moves_the_runtime() {
+	ExtBuilder::default().build().execute_with(|| {
+		warp_to_block::<Runtime>(1_000, 6_000_500);
+
+		assert_eq!(System::block_number(), 1_000);
+		assert_eq!(pallet_timestamp::Pallet::<Runtime>::get(), 6_000_500);
+	})
+}
+
+#[test]
+fn precompile_executes_through_the_stack_runner() {
+	ExtBuilder::default()
+		.with_balances(vec![(Alice.into(), U256::from(1_000_000))])
+		.build()
+		.execute_with(|| {
+			let info = call_precompile::<Runtime>(
+				Alice,
+				H160::from_low_u64_be(1),
+				PCall::success {}.into(),
+				1_000_000,
+			)
+			.expect("call succeeds");
+
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+			assert_eq!(
+				pallet_evm::Pallet::<Runtime>::account_basic(&Alice.into())
+					.0
+					.balance,
+				U256::from(1_000_000)
+			);
+		})
+}
+
+#[test]
+fn eip712_domain_of_precompile_uses_runtime_chain_id() {
+	ExtBuilder::default().build().execute_with(|| {
+		let handle = MockHandle::new(
+			H160::from_low_u64_be(1),
+			fp_evm::Context {
+				address: H160::from_low_u64_be(1),
+				caller: Alice.into(),
+				apparent_value: U256::zero(),
+			},
+		);
+
+		let domain = Domain::for_precompile::<Runtime>(&handle, "Mock", "1");
+		assert_eq!(domain.chain_id, Some(U256::from(1)));
+		assert_eq!(domain.verifying_contract, Some(H160::from_low_u64_be(1)));
+
+		ChainId::set(&42);
+		let other_chain = Domain::for_precompile::<Runtime>(&handle, "Mock", "1");
+		assert_eq!(other_chain.chain_id, Some(U256::from(42)));
+		assert_ne!(domain.separator(), other_chain.separator());
+	})
+}
+
+#[test]
+fn suicided_accounts_lose_their_code_and_storage() {
+	let storage = [(H256::repeat_byte(1), H256::repeat_byte(2))].into();
+	ExtBuilder::default()
+		.with_balances(vec![(Bob.into(), U256::from(1_000))])
+		.with_evm_account(Bob, vec![0x60, 0x00, 0x60, 0x00, 0xfd], storage)
+		.with_suicided(Bob)
+		.build()
+		.execute_with(|| {
+			let bob: H160 = Bob.into();
+			assert!(pallet_evm::AccountCodes::<Runtime>::get(bob).is_empty());
+			assert_eq!(
+				pallet_evm::AccountStorages::<Runtime>::iter_prefix(bob).count(),
+				0
+			);
+			assert_eq!(
+				pallet_evm::Pallet::<Runtime>::account_basic(&bob).0.balance,
+				U256::from(1_000)
+			);
+
+			// Its reverting code is gone, calls succeed with no output.
+			let info =
+				call_precompile::<Runtime>(Alice, Bob, Vec::new(), 100_000).expect("call succeeds");
+			assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+			assert!(info.value.is_empty());
+		})
+}
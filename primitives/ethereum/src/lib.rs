@@ -26,7 +26,7 @@ pub use ethereum::{
 	LegacyTransactionMessage, Log, ReceiptV4 as Receipt, TransactionAction,
 	TransactionV3 as Transaction,
 };
-use ethereum_types::{H160, H256, U256};
+use ethereum_types::{Bloom, BloomInput, H160, H256, U256};
 use fp_evm::{CallOrCreateInfo, CheckEvmTransactionInput};
 use frame_support::dispatch::{DispatchErrorWithPostInfo, PostDispatchInfo};
 use scale_codec::{Decode, Encode};
@@ -199,3 +199,91 @@ impl From<&Transaction> for TransactionData {
 		}
 	}
 }
+
+/// Compute the bloom filter of a list of logs: each log accrues its address and its topics.
+pub fn logs_bloom(logs: &[Log]) -> Bloom {
+	let mut bloom = Bloom::default();
+	for log in logs {
+		bloom.accrue(BloomInput::Raw(&log.address[..]));
+		for topic in &log.topics {
+			bloom.accrue(BloomInput::Raw(&topic[..]));
+		}
+	}
+	bloom
+}
+
+/// Compute the `logsBloom` of a block header from the logs of all its receipts.
+pub fn compute_logs_bloom(receipts: &[Receipt]) -> Bloom {
+	let mut bloom = Bloom::default();
+	for receipt in receipts {
+		let logs = match receipt {
+			Receipt::Legacy(d) | Receipt::EIP2930(d) | Receipt::EIP1559(d) => &d.logs,
+			Receipt::EIP7702(d) => &d.logs,
+		};
+		bloom.accrue_bloom(&logs_bloom(logs));
+	}
+	bloom
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use ethereum::EIP658ReceiptData;
+
+	fn receipt(logs: Vec<Log>) -> Receipt {
+		let logs_bloom = logs_bloom(&logs);
+		Receipt::EIP1559(EIP658ReceiptData {
+			status_code: 1,
+			used_gas: U256::zero(),
+			logs_bloom,
+			logs,
+		})
+	}
+
+	#[test]
+	fn logs_bloom_sets_three_bits_per_input() {
+		let log = Log {
+			address: H160::repeat_byte(0x11),
+			topics: vec![H256::repeat_byte(0x22)],
+			data: Vec::new(),
+		};
+		let bloom = logs_bloom(&[log]);
+
+		// The low 11 bits of the first three byte pairs of the keccak256 of each input select
+		// bits 704, 1028 and 193 for the address, and 1213, 481 and 327 for the topic.
+		let mut expected = [0u8; 256];
+		for (index, byte) in [
+			(104, 0x20),
+			(127, 0x10),
+			(167, 0x01),
+			(195, 0x02),
+			(215, 0x80),
+			(231, 0x02),
+		] {
+			expected[index] = byte;
+		}
+		assert_eq!(bloom, Bloom::from(expected));
+	}
+
+	#[test]
+	fn compute_logs_bloom_is_union_of_receipt_logs() {
+		let first = Log {
+			address: H160::repeat_byte(0x11),
+			topics: vec![H256::repeat_byte(0x22)],
+			data: Vec::new(),
+		};
+		let second = Log {
+			address: H160::repeat_byte(0x33),
+			topics: vec![H256::repeat_byte(0x44), H256::repeat_byte(0x55)],
+			data: vec![1, 2, 3],
+		};
+		let receipts = vec![
+			receipt(vec![first.clone()]),
+			receipt(Vec::new()),
+			receipt(vec![second.clone()]),
+		];
+
+		assert_eq!(compute_logs_bloom(&receipts), logs_bloom(&[first, second]));
+		assert_eq!(compute_logs_bloom(&[]), Bloom::default());
+	}
+}
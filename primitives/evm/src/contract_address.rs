@@ -0,0 +1,115 @@
+// This file is part of Frontier.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Derivation of the address of contracts deployed with `CREATE` and `CREATE2`, matching the
+//! addresses computed by the EVM executor.
+
+use alloc::vec::Vec;
+use sp_core::{hashing::keccak_256, H160, H256, U256};
+
+/// Address of a contract deployed with `CREATE` by `sender` at `nonce`:
+/// `keccak256(rlp([sender, nonce]))[12..]`.
+pub fn create_address(sender: H160, nonce: U256) -> H160 {
+	let nonce = nonce.to_big_endian();
+	let nonce = &nonce[nonce.iter().take_while(|byte| **byte == 0).count()..];
+
+	let mut payload = Vec::with_capacity(30);
+	payload.push(0x80 + 20);
+	payload.extend_from_slice(sender.as_bytes());
+	// A single byte below 0x80 is its own encoding, anything else (including zero, encoded as
+	// the empty string) is prefixed with its length.
+	match nonce {
+		[byte] if *byte < 0x80 => payload.push(*byte),
+		_ => {
+			payload.push(0x80 + nonce.len() as u8);
+			payload.extend_from_slice(nonce);
+		}
+	}
+
+	let mut rlp = Vec::with_capacity(payload.len() + 1);
+	rlp.push(0xc0 + payload.len() as u8);
+	rlp.extend_from_slice(&payload);
+
+	H160::from_slice(&keccak_256(&rlp)[12..])
+}
+
+/// Address of a contract deployed with `CREATE2` by `sender`:
+/// `keccak256(0xff ++ sender ++ salt ++ init_code_hash)[12..]`.
+pub fn create2_address(sender: H160, salt: H256, init_code_hash: H256) -> H160 {
+	let mut data = [0u8; 85];
+	data[0] = 0xff;
+	data[1..21].copy_from_slice(sender.as_bytes());
+	data[21..53].copy_from_slice(salt.as_bytes());
+	data[53..85].copy_from_slice(init_code_hash.as_bytes());
+
+	H160::from_slice(&keccak_256(&data)[12..])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use hex_literal::hex;
+
+	#[test]
+	fn create_address_matches_reference_vectors() {
+		let sender = H160(hex!("6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"));
+		let vectors = [
+			(0u64, hex!("cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d")),
+			(1, hex!("343c43a37d37dff08ae8c4a11544c718abb4fcf8")),
+			(2, hex!("f778b86fa74e846c4f0a1fbd1335fe81c00a0c91")),
+			(3, hex!("fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c")),
+			(0x80, hex!("08e190dcb7b73f5fcdabb43e102215c83659a76d")),
+			(0x10000, hex!("f666a819b370d38f44f2573464da3fba8479b917")),
+		];
+
+		for (nonce, expected) in vectors {
+			assert_eq!(create_address(sender, nonce.into()), H160(expected));
+		}
+	}
+
+	#[test]
+	fn create2_address_matches_eip1014_vectors() {
+		let vectors = [
+			(
+				H160::zero(),
+				H256::zero(),
+				&hex!("00")[..],
+				hex!("4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38"),
+			),
+			(
+				H160(hex!("deadbeef00000000000000000000000000000000")),
+				H256::zero(),
+				&hex!("00")[..],
+				hex!("b928f69bb1d91cd65274e3c79d8986362984fda3"),
+			),
+			(
+				H160(hex!("00000000000000000000000000000000deadbeef")),
+				H256::from_low_u64_be(0xcafebabe),
+				&hex!("deadbeef")[..],
+				hex!("60f3f640a8508fc6a86d45df051962668e1e8ac7"),
+			),
+		];
+
+		for (sender, salt, init_code, expected) in vectors {
+			let init_code_hash = H256(keccak_256(init_code));
+			assert_eq!(
+				create2_address(sender, salt, init_code_hash),
+				H160(expected)
+			);
+		}
+	}
+}
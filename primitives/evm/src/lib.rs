@@ -21,6 +21,7 @@
 extern crate alloc;
 
 mod account_provider;
+mod contract_address;
 mod precompile;
 mod storage_oog;
 mod validation;
@@ -41,10 +42,11 @@ pub use evm::{
 
 pub use self::{
 	account_provider::AccountProvider,
+	contract_address::{create2_address, create_address},
 	precompile::{
-		Context, ExitError, ExitRevert, ExitSucceed, IsPrecompileResult, LinearCostPrecompile,
-		Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
-		PrecompileSet, Transfer,
+		BoundedInputPrecompile, Context, ExitError, ExitRevert, ExitSucceed, IsPrecompileResult,
+		LinearCostPrecompile, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+		PrecompileResult, PrecompileSet, PrecompileSetList, RefTimePerInputWord, Transfer,
 	},
 	storage_oog::{handle_storage_oog, set_storage_oog},
 	validation::{
@@ -16,12 +16,14 @@
 // limitations under the License.
 
 use alloc::vec::Vec;
+use core::{marker::PhantomData, ops::RangeInclusive};
 pub use evm::{
 	executor::stack::{
 		IsPrecompileResult, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileSet,
 	},
 	Context, ExitError, ExitRevert, ExitSucceed, Transfer,
 };
+use sp_core::{Get, H160};
 
 pub type PrecompileResult = Result<PrecompileOutput, PrecompileFailure>;
 
@@ -32,6 +34,41 @@ pub trait Precompile {
 	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult;
 }
 
+/// Enumeration of the precompiles of a `PrecompileSet`.
+///
+/// `PrecompileSet::is_precompile` only answers for a given address, this allows listing all the
+/// currently active precompile addresses, e.g. for the genesis of the precompile accounts. The
+/// ranges it reserves back `pallet_evm::PrecompileReservedAddresses`.
+///
+/// `PrecompileSet` is defined by the `evm` crate and cannot be extended here, hence the separate
+/// trait. Sets generated by `#[precompile::precompile_set]` resolve their precompiles with a
+/// discriminant function rather than a fixed address list, so they keep the empty default.
+pub trait PrecompileSetList {
+	/// Return the addresses of all the active precompiles. Defaults to an empty list for sets
+	/// that cannot enumerate their precompiles.
+	fn precompiles(&self) -> Vec<H160> {
+		Vec::new()
+	}
+
+	/// Return the address ranges reserved by the set. No contract can be deployed in them, so
+	/// that none shadows a current or future precompile. Defaults to the active precompiles.
+	fn reserved_address_ranges(&self) -> Vec<RangeInclusive<H160>> {
+		self.precompiles()
+			.into_iter()
+			.map(|address| address..=address)
+			.collect()
+	}
+
+	/// Whether the address is in one of the reserved ranges.
+	fn is_reserved_address(&self, address: H160) -> bool {
+		self.reserved_address_ranges()
+			.iter()
+			.any(|range| range.contains(&address))
+	}
+}
+
+impl PrecompileSetList for () {}
+
 pub trait LinearCostPrecompile {
 	const BASE: u64;
 	const WORD: u64;
@@ -56,6 +93,47 @@ impl<T: LinearCostPrecompile> Precompile for T {
 	}
 }
 
+/// Wraps a precompile to revert on inputs longer than `MaxLen` bytes.
+///
+/// The length is checked before the inner precompile charges or computes anything, so
+/// oversized inputs are rejected cheaply.
+pub struct BoundedInputPrecompile<P, MaxLen>(PhantomData<(P, MaxLen)>);
+
+impl<P: Precompile, MaxLen: Get<u32>> Precompile for BoundedInputPrecompile<P, MaxLen> {
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		if handle.input().len() > MaxLen::get() as usize {
+			return Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: b"input is too long".to_vec(),
+			});
+		}
+
+		P::execute(handle)
+	}
+}
+
+/// Wraps a precompile to record `RefTimePerWord` ref time as external cost for each 32 bytes
+/// word of input, before the inner precompile runs.
+///
+/// The value should come from the runtime's own benchmark of the inner precompile, see the
+/// `precompile_*` benchmarks of `pallet-evm`.
+pub struct RefTimePerInputWord<P, RefTimePerWord>(PhantomData<(P, RefTimePerWord)>);
+
+impl<P: Precompile, RefTimePerWord: Get<u64>> Precompile
+	for RefTimePerInputWord<P, RefTimePerWord>
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let words = (handle.input().len() as u64).saturating_add(31) / 32;
+		handle.record_external_cost(
+			Some(RefTimePerWord::get().saturating_mul(words)),
+			None,
+			None,
+		)?;
+
+		P::execute(handle)
+	}
+}
+
 /// Linear gas cost
 fn ensure_linear_cost(
 	target_gas: Option<u64>,
@@ -323,6 +323,8 @@ mod tests {
 	}
 
 	static PECTRA_CONFIG: evm::Config = evm::Config::pectra();
+	// Without the EIP-7623 calldata floor, so the intrinsic gas is the EIP-2028 data cost.
+	static SHANGHAI_CONFIG: evm::Config = evm::Config::shanghai();
 
 	impl From<TransactionValidationError> for TestError {
 		fn from(e: TransactionValidationError) -> Self {
@@ -1052,4 +1054,116 @@ mod tests {
 		let res = validator.with_eip7702_authorization_list(false); // Not EIP-7702
 		assert!(res.is_ok());
 	}
+
+	/// Whether a transaction with the given data can afford its intrinsic gas with `gas_limit`.
+	fn affords_intrinsic_gas(
+		to: Option<H160>,
+		input: Vec<u8>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		gas_limit: u64,
+	) -> bool {
+		CheckEvmTransaction::<TestError>::new(
+			CheckEvmTransactionConfig {
+				evm_config: &SHANGHAI_CONFIG,
+				block_gas_limit: U256::max_value(),
+				base_fee: U256::zero(),
+				chain_id: 42u64,
+				is_transactional: true,
+			},
+			CheckEvmTransactionInput {
+				chain_id: Some(42u64),
+				to,
+				input,
+				nonce: U256::zero(),
+				gas_limit: U256::from(gas_limit),
+				gas_price: None,
+				max_fee_per_gas: Some(U256::zero()),
+				max_priority_fee_per_gas: Some(U256::zero()),
+				value: U256::zero(),
+				access_list,
+				authorization_list: vec![],
+			},
+			None,
+			None,
+		)
+		.validate_common()
+		.is_ok()
+	}
+
+	/// Asserts the intrinsic gas of the transaction is exactly `expected`.
+	fn assert_intrinsic_gas(
+		to: Option<H160>,
+		input: Vec<u8>,
+		access_list: Vec<(H160, Vec<H256>)>,
+		expected: u64,
+	) {
+		assert!(affords_intrinsic_gas(
+			to,
+			input.clone(),
+			access_list.clone(),
+			expected
+		));
+		assert!(!affords_intrinsic_gas(to, input, access_list, expected - 1));
+	}
+
+	// EIP-2028: zero data bytes cost 4 gas, non-zero ones 16 gas.
+	#[test]
+	fn intrinsic_gas_of_zero_data_bytes() {
+		assert_intrinsic_gas(
+			Some(H160::default()),
+			vec![0; 100],
+			vec![],
+			21_000 + 4 * 100,
+		);
+	}
+
+	#[test]
+	fn intrinsic_gas_of_non_zero_data_bytes() {
+		assert_intrinsic_gas(
+			Some(H160::default()),
+			vec![0xff; 100],
+			vec![],
+			21_000 + 16 * 100,
+		);
+	}
+
+	#[test]
+	fn intrinsic_gas_of_mixed_data_bytes() {
+		let input = [0u8, 1, 0, 2, 3, 0, 0, 4].repeat(10);
+		assert_intrinsic_gas(
+			Some(H160::default()),
+			input,
+			vec![],
+			21_000 + 4 * 40 + 16 * 40,
+		);
+	}
+
+	// EIP-2930: 2400 gas per address and 1900 gas per storage key.
+	#[test]
+	fn intrinsic_gas_includes_access_list_cost() {
+		let access_list = vec![
+			(
+				H160::repeat_byte(1),
+				vec![H256::zero(), H256::repeat_byte(1)],
+			),
+			(H160::repeat_byte(2), vec![]),
+		];
+		assert_intrinsic_gas(
+			Some(H160::default()),
+			vec![0, 1],
+			access_list,
+			21_000 + 4 + 16 + 2 * 2400 + 2 * 1900,
+		);
+	}
+
+	// EIP-3860: contract creations cost 32000 more, plus 2 gas per 32-byte word of initcode.
+	#[test]
+	fn intrinsic_gas_includes_initcode_cost() {
+		assert_intrinsic_gas(
+			None,
+			vec![0xff; 33],
+			vec![],
+			21_000 + 32_000 + 16 * 33 + 2 * 2,
+		);
+	}
 }
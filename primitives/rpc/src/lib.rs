@@ -299,6 +299,17 @@ sp_api::decl_runtime_apis! {
 		#[changed_in(2)]
 		fn convert_transaction(transaction: ethereum::TransactionV0) -> <Block as BlockT>::Extrinsic;
 	}
+
+	/// API to predict the address of deployed contracts, using the same derivation as the
+	/// runtime EVM executor.
+	pub trait ContractAddressRuntimeApi {
+		/// Address of a contract deployed with CREATE by `sender` at `nonce`.
+		fn create_address(sender: Address, nonce: U256) -> Address;
+
+		/// Address of a contract deployed with CREATE2 by `sender`, with the given `salt` and
+		/// keccak256 hash of the init code.
+		fn create2_address(sender: Address, salt: H256, init_code_hash: H256) -> Address;
+	}
 }
 
 /// Fallback transaction converter when the `ConvertTransactionRuntimeApi` is not available. For almost all
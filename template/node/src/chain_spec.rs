@@ -126,7 +126,7 @@ fn testnet_genesis(
 			H160::from_str("d43593c715fdd31c61141abd04a99fd6822c8558")
 				.expect("internal H160 is valid; qed"),
 			fp_evm::GenesisAccount {
-				balance: U256::from_str("0xffffffffffffffffffffffffffffffff")
+				balance: U256::from_str("0x3fffffffffffffffffffffffffffffff")
 					.expect("internal U256 is valid; qed"),
 				code: Default::default(),
 				nonce: Default::default(),
@@ -138,7 +138,7 @@ fn testnet_genesis(
 			H160::from_str("6be02d1d3665660d22ff9624b7be0551ee1ac91b")
 				.expect("internal H160 is valid; qed"),
 			fp_evm::GenesisAccount {
-				balance: U256::from_str("0xffffffffffffffffffffffffffffffff")
+				balance: U256::from_str("0x3fffffffffffffffffffffffffffffff")
 					.expect("internal U256 is valid; qed"),
 				code: Default::default(),
 				nonce: Default::default(),
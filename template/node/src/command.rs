@@ -216,13 +216,16 @@ pub fn run() -> sc_cli::Result<()> {
 		Some(Subcommand::FrontierDb(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|mut config| {
+				// The SQL backend runs its queries on tokio.
+				let tokio_handle = config.tokio_handle.clone();
+				let _guard = tokio_handle.enter();
 				let (client, _, _, _, frontier_backend) =
 					service::new_chain_ops(&mut config, &cli.eth)?;
-				let frontier_backend = match frontier_backend {
-					fc_db::Backend::KeyValue(kv) => kv,
-					_ => panic!("Only fc_db::Backend::KeyValue supported"),
-				};
-				cmd.run(client, frontier_backend)
+				match frontier_backend {
+					fc_db::Backend::KeyValue(kv) => cmd.run(client, kv),
+					#[cfg(feature = "sql")]
+					fc_db::Backend::Sql(sql) => cmd.run_sql(sql),
+				}
 			})
 		}
 		None => {
@@ -7,17 +7,19 @@ use std::{
 
 use futures::{future, prelude::*};
 // Substrate
+use prometheus_endpoint::Registry;
 use sc_client_api::BlockchainEvents;
 use sc_executor::HostFunctions;
 use sc_network_sync::SyncingService;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
+use sc_transaction_pool_api::TransactionPool;
 use sp_api::ConstructRuntimeApi;
 use sp_core::H256;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
 pub use fc_consensus::FrontierBlockImport;
-use fc_rpc::EthTask;
-pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use fc_rpc::{EthTask, RpcMetrics};
+pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool, ReceiptFormat};
 pub use fc_storage::{StorageOverride, StorageOverrideHandler};
 
 use crate::client::{FullBackend, FullClient};
@@ -50,9 +52,26 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "2048")]
 	pub fee_history_limit: u64,
 
+	/// Sign transactions and messages with in-memory dev accounts (`eth_accounts`, `eth_sign`,
+	/// `eth_signTransaction` and `eth_sendTransaction`). Only allowed on development chains or
+	/// with unsafe RPC methods.
 	#[arg(long)]
 	pub enable_dev_signer: bool,
 
+	/// Secret key of a dev signer account, in hex. Can be repeated. Defaults to a test account.
+	#[arg(
+		long = "dev-signer-key",
+		value_name = "SECRET",
+		requires = "enable_dev_signer"
+	)]
+	pub dev_signer_keys: Vec<H256>,
+
+	/// Consider blocks this deep below the best block as final for `frontier_subscribe`
+	/// ("finalizedHeads") and `frontier_finalizedBlockNumber`. Only needed on chains without
+	/// a finality gadget.
+	#[arg(long)]
+	pub pseudo_finality_depth: Option<u32>,
+
 	/// The dynamic-fee pallet target gas price set by block author
 	#[arg(long, default_value = "1")]
 	pub target_gas_price: u64,
@@ -62,6 +81,21 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "10")]
 	pub execute_gas_limit_multiplier: u64,
 
+	/// Cap the gas of the eth_estimateGas requests with a non-zero fee by the balance of the
+	/// sender. By default the balance isn't checked, so users can estimate without funds.
+	#[arg(long)]
+	pub estimate_balance_check: bool,
+
+	/// Maximum number of eth_call/eth_estimateGas executions running at once. Defaults to the
+	/// number of CPUs.
+	#[arg(long)]
+	pub rpc_execution_pool_size: Option<usize>,
+
+	/// JSON format of the served receipts: `v1` for the format of geth v1.13, or `frontier` to
+	/// also serve the `transactionLogIndex` of the logs.
+	#[arg(long, default_value = "frontier")]
+	pub eth_receipt_format: ReceiptFormat,
+
 	/// Size in bytes of the LRU cache for block data.
 	#[arg(long, default_value = "50")]
 	pub eth_log_block_cache: usize,
@@ -70,6 +104,11 @@ pub struct EthConfiguration {
 	#[arg(long, default_value = "50")]
 	pub eth_statuses_cache: usize,
 
+	/// Maximum encoded size in bytes of the transactions in the pool. The lowest priority
+	/// transactions are evicted beyond it. Default value is 256MB.
+	#[arg(long, default_value = "268435456")]
+	pub eth_pool_max_memory: usize,
+
 	/// Sets the frontier backend type (KeyValue or Sql)
 	#[arg(long, value_enum, ignore_case = true, default_value_t = BackendType::default())]
 	pub frontier_backend_type: BackendType,
@@ -125,27 +164,32 @@ where
 {
 }
 
-pub async fn spawn_frontier_tasks<B, RA, HF>(
+pub async fn spawn_frontier_tasks<B, RA, HF, P>(
 	task_manager: &TaskManager,
 	client: Arc<FullClient<B, RA, HF>>,
+	transaction_pool: Arc<P>,
 	backend: Arc<FullBackend<B>>,
 	frontier_backend: Arc<FrontierBackend<B, FullClient<B, RA, HF>>>,
 	filter_pool: Option<FilterPool>,
 	storage_override: Arc<dyn StorageOverride<B>>,
 	fee_history_cache: FeeHistoryCache,
 	fee_history_cache_limit: FeeHistoryCacheLimit,
+	pool_max_memory: usize,
 	sync: Arc<SyncingService<B>>,
 	pubsub_notification_sinks: Arc<
 		fc_mapping_sync::EthereumBlockNotificationSinks<
 			fc_mapping_sync::EthereumBlockNotification<B>,
 		>,
 	>,
+	prometheus_registry: Option<Registry>,
+	rpc_metrics: RpcMetrics,
 ) where
 	B: BlockT<Hash = H256>,
 	RA: ConstructRuntimeApi<B, FullClient<B, RA, HF>>,
 	RA: Send + Sync + 'static,
 	RA::RuntimeApi: EthCompatRuntimeApiCollection<B>,
 	HF: HostFunctions + 'static,
+	P: TransactionPool<Block = B, Hash = B::Hash> + 'static,
 {
 	// Spawn main mapping sync worker background task.
 	match &*frontier_backend {
@@ -201,6 +245,25 @@ pub async fn spawn_frontier_tasks<B, RA, HF>(
 		);
 	}
 
+	// Spawn Frontier transaction pool memory limit task.
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-pool-memory-limit",
+		Some("frontier"),
+		EthTask::pool_memory_limit_task(
+			client.clone(),
+			transaction_pool.clone(),
+			pool_max_memory,
+			prometheus_registry,
+		),
+	);
+
+	// Spawn Frontier RPC metrics task. Not essential, it ends right away without a registry.
+	task_manager.spawn_handle().spawn(
+		"frontier-rpc-metrics",
+		Some("frontier"),
+		rpc_metrics.chain_metrics_task(client.clone(), transaction_pool),
+	);
+
 	// Spawn Frontier FeeHistory cache maintenance task.
 	task_manager.spawn_essential_handle().spawn(
 		"frontier-fee-history",
@@ -19,8 +19,8 @@ use sp_core::H256;
 use sp_inherents::CreateInherentDataProviders;
 use sp_runtime::traits::Block as BlockT;
 // Frontier
-pub use fc_rpc::{EthBlockDataCacheTask, EthConfig};
-pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+pub use fc_rpc::{EthBlockDataCacheTask, EthConfig, ExecutionPool, RpcMetrics};
+pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool, ReceiptFormat};
 use fc_storage::StorageOverride;
 use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
 
@@ -38,6 +38,10 @@ pub struct EthDeps<B: BlockT, C, P, CT, CIDP> {
 	pub is_authority: bool,
 	/// Whether to enable dev signer
 	pub enable_dev_signer: bool,
+	/// Secret keys of the dev signer accounts. The default test account is used if empty.
+	pub dev_signer_keys: Vec<H256>,
+	/// Depth below the best block considered final, for chains without a finality gadget.
+	pub pseudo_finality_depth: Option<u32>,
 	/// Network service
 	pub network: Arc<dyn NetworkService>,
 	/// Chain syncing service
@@ -52,6 +56,8 @@ pub struct EthDeps<B: BlockT, C, P, CT, CIDP> {
 	pub filter_pool: Option<FilterPool>,
 	/// Maximum number of logs in a query.
 	pub max_past_logs: u32,
+	/// Maximum size of an RPC response, in bytes.
+	pub max_response_size: usize,
 	/// Fee history cache.
 	pub fee_history_cache: FeeHistoryCache,
 	/// Maximum fee history cache size.
@@ -59,10 +65,18 @@ pub struct EthDeps<B: BlockT, C, P, CT, CIDP> {
 	/// Maximum allowed gas limit will be ` block.gas_limit * execute_gas_limit_multiplier` when
 	/// using eth_call/eth_estimateGas.
 	pub execute_gas_limit_multiplier: u64,
+	/// Whether eth_estimateGas caps the gas of a request with a fee by the sender's balance.
+	pub estimate_balance_check: bool,
+	/// Pool running the executions of eth_call/eth_estimateGas.
+	pub execution_pool: ExecutionPool,
+	/// JSON format of the served receipts.
+	pub receipt_format: ReceiptFormat,
 	/// Mandated parent hashes for a given block hash.
 	pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
 	/// Something that can create the inherent data providers for pending state
 	pub pending_create_inherent_data_providers: CIDP,
+	/// Prometheus metrics of the Ethereum RPC.
+	pub rpc_metrics: RpcMetrics,
 }
 
 /// Instantiate Ethereum-compatible RPC extensions.
@@ -93,8 +107,8 @@ where
 {
 	use fc_rpc::{
 		pending::AuraConsensusDataProvider, Debug, DebugApiServer, Eth, EthApiServer, EthDevSigner,
-		EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, EthSigner, Net, NetApiServer,
-		Web3, Web3ApiServer,
+		EthFilter, EthFilterApiServer, EthPubSub, EthPubSubApiServer, EthSigner, Frontier,
+		FrontierApiServer, Net, NetApiServer, Web3, Web3ApiServer,
 	};
 	#[cfg(feature = "txpool")]
 	use fc_rpc::{TxPool, TxPoolApiServer};
@@ -106,6 +120,8 @@ where
 		converter,
 		is_authority,
 		enable_dev_signer,
+		dev_signer_keys,
+		pseudo_finality_depth,
 		network,
 		sync,
 		frontier_backend,
@@ -113,16 +129,26 @@ where
 		block_data_cache,
 		filter_pool,
 		max_past_logs,
+		max_response_size,
 		fee_history_cache,
 		fee_history_cache_limit,
 		execute_gas_limit_multiplier,
+		estimate_balance_check,
+		execution_pool,
+		receipt_format,
 		forced_parent_hashes,
 		pending_create_inherent_data_providers,
+		rpc_metrics,
 	} = deps;
 
 	let mut signers = Vec::new();
 	if enable_dev_signer {
-		signers.push(Box::new(EthDevSigner::new()) as Box<dyn EthSigner>);
+		let signer = if dev_signer_keys.is_empty() {
+			EthDevSigner::new()
+		} else {
+			EthDevSigner::with_keys(&dev_signer_keys)?
+		};
+		signers.push(Box::new(signer) as Box<dyn EthSigner>);
 	}
 
 	io.merge(
@@ -145,6 +171,11 @@ where
 			Some(Box::new(AuraConsensusDataProvider::new(client.clone()))),
 		)
 		.replace_config::<EC>()
+		.with_max_response_size(max_response_size)
+		.with_execution_pool(execution_pool)
+		.with_estimate_balance_check(estimate_balance_check)
+		.with_receipt_format(receipt_format)
+		.with_dev_signing(enable_dev_signer)
 		.into_rpc(),
 	)?;
 
@@ -159,6 +190,7 @@ where
 				max_past_logs,
 				block_data_cache.clone(),
 			)
+			.with_max_response_size(max_response_size)
 			.into_rpc(),
 		)?;
 	}
@@ -168,9 +200,22 @@ where
 			pool,
 			client.clone(),
 			sync,
-			subscription_task_executor,
+			subscription_task_executor.clone(),
+			storage_override.clone(),
+			pubsub_notification_sinks.clone(),
+		)
+		.with_metrics(rpc_metrics)
+		.into_rpc(),
+	)?;
+
+	io.merge(
+		Frontier::new(
+			client.clone(),
+			frontier_backend.clone(),
 			storage_override.clone(),
+			subscription_task_executor,
 			pubsub_notification_sinks,
+			pseudo_finality_depth,
 		)
 		.into_rpc(),
 	)?;
@@ -10,7 +10,10 @@ use sc_consensus::{BasicQueue, BoxBlockImport};
 use sc_consensus_grandpa::BlockNumberOps;
 use sc_executor::HostFunctions as HostFunctionsT;
 use sc_network_sync::strategy::warp::{WarpSyncConfig, WarpSyncProvider};
-use sc_service::{error::Error as ServiceError, Configuration, PartialComponents, TaskManager};
+use sc_service::{
+	config::RpcMethods, error::Error as ServiceError, ChainType, Configuration, PartialComponents,
+	TaskManager,
+};
 use sc_telemetry::{Telemetry, TelemetryHandle, TelemetryWorker};
 use sc_transaction_pool::TransactionPoolHandle;
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
@@ -412,8 +415,24 @@ where
 	> = Default::default();
 	let pubsub_notification_sinks = Arc::new(pubsub_notification_sinks);
 
+	// The dev signer holds its keys in memory, so only allow it where that is harmless.
+	if eth_config.enable_dev_signer
+		&& !matches!(config.chain_spec.chain_type(), ChainType::Development)
+		&& !matches!(config.rpc.methods, RpcMethods::Unsafe)
+	{
+		return Err(ServiceError::Other(
+			"The dev signer is only allowed on development chains or with `--rpc-methods unsafe`"
+				.into(),
+		));
+	}
+
 	// for ethereum-compatibility rpc.
 	config.rpc.id_provider = Some(Box::new(fc_rpc::EthereumSubIdProvider));
+	let rpc_metrics = fc_rpc::RpcMetrics::new(prometheus_registry.clone());
+	let execution_pool = eth_config
+		.rpc_execution_pool_size
+		.map(fc_rpc::ExecutionPool::new)
+		.unwrap_or_default();
 
 	let rpc_builder = {
 		let client = client.clone();
@@ -423,13 +442,21 @@ where
 
 		let is_authority = role.is_authority();
 		let enable_dev_signer = eth_config.enable_dev_signer;
+		let dev_signer_keys = eth_config.dev_signer_keys.clone();
+		let pseudo_finality_depth = eth_config.pseudo_finality_depth;
 		let max_past_logs = eth_config.max_past_logs;
+		// The limit of the RPC server is given in MiB.
+		let max_response_size = config.rpc.max_response_size as usize * 1024 * 1024;
 		let execute_gas_limit_multiplier = eth_config.execute_gas_limit_multiplier;
+		let estimate_balance_check = eth_config.estimate_balance_check;
+		let receipt_format = eth_config.eth_receipt_format;
 		let filter_pool = filter_pool.clone();
 		let frontier_backend = frontier_backend.clone();
 		let pubsub_notification_sinks = pubsub_notification_sinks.clone();
 		let storage_override = storage_override.clone();
 		let fee_history_cache = fee_history_cache.clone();
+		let rpc_metrics = rpc_metrics.clone();
+		let execution_pool = execution_pool.clone();
 		let block_data_cache = Arc::new(fc_rpc::EthBlockDataCacheTask::new(
 			task_manager.spawn_handle(),
 			storage_override.clone(),
@@ -460,6 +487,8 @@ where
 				converter: Some(TransactionConverter::<B>::default()),
 				is_authority,
 				enable_dev_signer,
+				dev_signer_keys: dev_signer_keys.clone(),
+				pseudo_finality_depth,
 				network: network.clone(),
 				sync: sync_service.clone(),
 				frontier_backend: match &*frontier_backend {
@@ -470,11 +499,16 @@ where
 				block_data_cache: block_data_cache.clone(),
 				filter_pool: filter_pool.clone(),
 				max_past_logs,
+				max_response_size,
 				fee_history_cache: fee_history_cache.clone(),
 				fee_history_cache_limit,
 				execute_gas_limit_multiplier,
+				estimate_balance_check,
+				execution_pool: execution_pool.clone(),
+				receipt_format,
 				forced_parent_hashes: None,
 				pending_create_inherent_data_providers,
+				rpc_metrics: rpc_metrics.clone(),
 			};
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
@@ -513,14 +547,18 @@ where
 	spawn_frontier_tasks(
 		&task_manager,
 		client.clone(),
+		transaction_pool.clone(),
 		backend,
 		frontier_backend,
 		filter_pool,
 		storage_override,
 		fee_history_cache,
 		fee_history_cache_limit,
+		eth_config.eth_pool_max_memory,
 		sync_service.clone(),
 		pubsub_notification_sinks,
+		prometheus_registry.clone(),
+		rpc_metrics,
 	)
 	.await;
 
@@ -135,6 +135,9 @@ pub type CheckedExtrinsic =
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 
+/// Migrations to apply on runtime upgrade.
+pub type Migrations = (pallet_evm::migrations::MigrateV0ToV1<Runtime>,);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -142,6 +145,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 // Time is measured by number of blocks.
@@ -370,6 +374,7 @@ impl pallet_evm::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type PrecompilesType = FrontierPrecompiles<Self>;
 	type PrecompilesValue = PrecompilesValue;
+	type ReservedAddresses = pallet_evm::PrecompileReservedAddresses<Self>;
 	type ChainId = EVMChainId;
 	type BlockGasLimit = BlockGasLimit;
 	type Runner = pallet_evm::runner::stack::Runner<Self>;
@@ -381,6 +386,8 @@ impl pallet_evm::Config for Runtime {
 	type Timestamp = Timestamp;
 	type CreateOriginFilter = ();
 	type CreateInnerOriginFilter = ();
+	type ExecutionEvents = ConstBool<false>;
+	type ExecutionEventReasonLength = ConstU32<256>;
 	type WeightInfo = pallet_evm::weights::SubstrateWeight<Self>;
 }
 
@@ -393,6 +400,11 @@ impl pallet_ethereum::Config for Runtime {
 	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self::Version>;
 	type PostLogContent = PostBlockAndTxnHashes;
 	type ExtraDataLength = ConstU32<30>;
+	type MaxPoolTransactionSize = ConstU32<{ 128 * 1024 }>;
+	type MaxLogEvents = ConstU32<0>;
+	type MaxLogEventDataLength = ConstU32<256>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 parameter_types! {
@@ -599,6 +611,7 @@ mod benches {
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
 		[pallet_evm, EVM]
+		[pallet_ethereum, Ethereum]
 	);
 }
 
@@ -1029,6 +1042,16 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl fp_rpc::ContractAddressRuntimeApi<Block> for Runtime {
+		fn create_address(sender: H160, nonce: U256) -> H160 {
+			fp_evm::create_address(sender, nonce)
+		}
+
+		fn create2_address(sender: H160, salt: H256, init_code_hash: H256) -> H160 {
+			fp_evm::create2_address(sender, salt, init_code_hash)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
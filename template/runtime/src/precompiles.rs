@@ -1,12 +1,26 @@
+use alloc::vec::Vec;
 use core::marker::PhantomData;
 use pallet_evm::{
 	IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet,
+	PrecompileSetList,
 };
 use sp_core::H160;
 
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use pallet_evm_precompile_weth::{WrappedNative, WrappedNativeMetadata};
+
+pub struct WrappedUnit;
+impl WrappedNativeMetadata for WrappedUnit {
+	fn name() -> &'static str {
+		"Wrapped Unit"
+	}
+
+	fn symbol() -> &'static str {
+		"WUNIT"
+	}
+}
 
 pub struct FrontierPrecompiles<R>(PhantomData<R>);
 
@@ -17,7 +31,7 @@ where
 	pub fn new() -> Self {
 		Self(Default::default())
 	}
-	pub fn used_addresses() -> [H160; 7] {
+	pub fn used_addresses() -> [H160; 8] {
 		[
 			hash(1),
 			hash(2),
@@ -26,6 +40,7 @@ where
 			hash(5),
 			hash(1024),
 			hash(1025),
+			hash(2048),
 		]
 	}
 }
@@ -44,6 +59,8 @@ where
 			// Non-Frontier specific nor Ethereum precompiles :
 			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
 			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
+			// Wrapped native currency, see `WrappedNative.sol` :
+			a if a == hash(2048) => Some(WrappedNative::<R, WrappedUnit>::execute(handle)),
 			_ => None,
 		}
 	}
@@ -56,6 +73,15 @@ where
 	}
 }
 
+impl<R> PrecompileSetList for FrontierPrecompiles<R>
+where
+	R: pallet_evm::Config,
+{
+	fn precompiles(&self) -> Vec<H160> {
+		Self::used_addresses().to_vec()
+	}
+}
+
 fn hash(a: u64) -> H160 {
 	H160::from_low_u64_be(a)
 }
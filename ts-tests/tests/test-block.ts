@@ -263,3 +263,49 @@ describeWithFrontier("Frontier RPC (BlockReceipts)", (context) => {
 		expect((await customRequest(context.web3, "eth_getBlockReceipts", ["latest"])).result.length).to.be.eq(N);
 	});
 });
+
+describeWithFrontier("Frontier RPC (Block Transactions Pages)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	it("should page through the transactions of a block", async function () {
+		const expectedXtsNumber = 7;
+		for (let nonce = 0; nonce < expectedXtsNumber; nonce++) {
+			const tx = await context.web3.eth.accounts.signTransaction(
+				{
+					from: GENESIS_ACCOUNT,
+					to: TEST_ACCOUNT,
+					value: "0x200", // Must be higher than ExistentialDeposit
+					gasPrice: "0x3B9ACA00",
+					gas: "0x100000",
+					nonce: nonce,
+				},
+				GENESIS_ACCOUNT_PRIVATE_KEY
+			);
+			await customRequest(context.web3, "eth_sendRawTransaction", [tx.rawTransaction]);
+		}
+		await createAndFinalizeBlock(context.web3);
+
+		const full = (await customRequest(context.web3, "eth_getBlockByNumber", ["latest", true])).result;
+		expect(full.transactions.length).to.be.eq(expectedXtsNumber);
+
+		const pageSize = 3;
+		let paged = [];
+		for (let offset = 0; offset < expectedXtsNumber; offset += pageSize) {
+			const page = (
+				await customRequest(context.web3, "eth_getBlockTransactions", [full.number, offset, pageSize])
+			).result;
+			expect(page.length).to.be.lte(pageSize);
+			paged = paged.concat(page);
+		}
+		expect(paged).to.be.deep.eq(full.transactions);
+		expect(paged.map((tx) => Number(tx.transactionIndex))).to.be.deep.eq([...Array(expectedXtsNumber).keys()]);
+
+		const pastTheEnd = (
+			await customRequest(context.web3, "eth_getBlockTransactions", [full.number, expectedXtsNumber, pageSize])
+		).result;
+		expect(pastTheEnd).to.be.empty;
+
+		const tooLarge = await customRequest(context.web3, "eth_getBlockTransactions", [full.number, 0, 1001]);
+		expect(tooLarge.error.message).to.be.equal("count is too high (can be up to 1000 transactions per page)");
+	});
+});
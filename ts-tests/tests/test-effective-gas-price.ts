@@ -0,0 +1,102 @@
+import { ethers } from "ethers";
+import { expect } from "chai";
+import { step } from "mocha-steps";
+
+import { GENESIS_ACCOUNT, GENESIS_ACCOUNT_PRIVATE_KEY, CHAIN_ID } from "./config";
+import { createAndFinalizeBlock, customRequest, describeWithFrontier } from "./util";
+
+describeWithFrontier("Frontier RPC (Effective Gas Price)", (context) => {
+	const TEST_ACCOUNT = "0x1111111111111111111111111111111111111111";
+
+	async function sendTransaction(context, payload: any) {
+		let signer = new ethers.Wallet(GENESIS_ACCOUNT_PRIVATE_KEY, context.ethersjs);
+		const tx = await signer.sendTransaction(payload);
+		await createAndFinalizeBlock(context.web3);
+		return tx.hash;
+	}
+
+	// The base fee a transaction was charged with is the one of the parent of its block.
+	async function includedBaseFee(txHash: string) {
+		const tx = (await customRequest(context.web3, "eth_getTransactionByHash", [txHash])).result;
+		const parent = await context.web3.eth.getBlock(Number(tx.blockNumber) - 1);
+		return Number(parent.baseFeePerGas);
+	}
+
+	async function expectEffectiveGasPrice(txHash: string, expected: number) {
+		const tx = (await customRequest(context.web3, "eth_getTransactionByHash", [txHash])).result;
+		const receipt = (await customRequest(context.web3, "eth_getTransactionReceipt", [txHash])).result;
+		const effectiveGasPrice = (
+			await customRequest(context.web3, "eth_getTransactionEffectiveGasPrice", [txHash])
+		).result;
+
+		expect(Number(effectiveGasPrice)).to.be.eq(expected);
+		expect(Number(tx.effectiveGasPrice)).to.be.eq(expected);
+		expect(Number(tx.gasPrice)).to.be.eq(expected);
+		expect(Number(receipt.effectiveGasPrice)).to.be.eq(expected);
+	}
+
+	step("legacy transactions pay their gas price", async function () {
+		const txHash = await sendTransaction(context, {
+			from: GENESIS_ACCOUNT,
+			to: TEST_ACCOUNT,
+			value: "0x01",
+			gasPrice: "0x3B9ACA00",
+			type: 0,
+			gasLimit: "0x5208",
+			chainId: CHAIN_ID,
+		});
+		await expectEffectiveGasPrice(txHash, 1_000_000_000);
+	});
+
+	step("EIP-2930 transactions pay their gas price", async function () {
+		const txHash = await sendTransaction(context, {
+			from: GENESIS_ACCOUNT,
+			to: TEST_ACCOUNT,
+			value: "0x01",
+			gasPrice: "0x3B9ACA00",
+			type: 1,
+			accessList: [],
+			gasLimit: "0x5208",
+			chainId: CHAIN_ID,
+		});
+		await expectEffectiveGasPrice(txHash, 1_000_000_000);
+	});
+
+	step("EIP-1559 transactions pay the base fee and their priority fee", async function () {
+		const maxFeePerGas = 10_000_000_000;
+		const maxPriorityFeePerGas = 1_000;
+		const txHash = await sendTransaction(context, {
+			from: GENESIS_ACCOUNT,
+			to: TEST_ACCOUNT,
+			value: "0x01",
+			maxFeePerGas,
+			maxPriorityFeePerGas,
+			type: 2,
+			gasLimit: "0x5208",
+			chainId: CHAIN_ID,
+		});
+		const baseFee = await includedBaseFee(txHash);
+		const expected = Math.min(baseFee + maxPriorityFeePerGas, maxFeePerGas);
+		await expectEffectiveGasPrice(txHash, expected);
+	});
+
+	step("EIP-1559 transactions never pay more than their max fee", async function () {
+		const txHash = await sendTransaction(context, {
+			from: GENESIS_ACCOUNT,
+			to: TEST_ACCOUNT,
+			value: "0x01",
+			maxFeePerGas: "0x3B9ACA00",
+			maxPriorityFeePerGas: "0x3B9ACA00",
+			type: 2,
+			gasLimit: "0x5208",
+			chainId: CHAIN_ID,
+		});
+		await expectEffectiveGasPrice(txHash, 1_000_000_000);
+	});
+
+	step("unknown transactions have no effective gas price", async function () {
+		const unknown = "0x" + "00".repeat(32);
+		const result = await customRequest(context.web3, "eth_getTransactionEffectiveGasPrice", [unknown]);
+		expect(result.result).to.be.null;
+	});
+});
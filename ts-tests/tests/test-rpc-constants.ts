@@ -10,6 +10,11 @@ describeWithFrontier("Frontier RPC (Constant)", (context) => {
 		expect(await context.web3.eth.getHashrate()).to.equal(0);
 	});
 
+	it("should not be mining", async function () {
+		// Even though the dev node is an authority.
+		expect(await context.web3.eth.isMining()).to.equal(false);
+	});
+
 	it("should have chainId", async function () {
 		// The chainId is defined by the Substrate Chain Id, default to 42
 		expect(await context.web3.eth.getChainId()).to.equal(CHAIN_ID);
@@ -53,6 +53,28 @@ describeWithFrontierWs("Frontier RPC (Subscription)", (context) => {
 		expect(subscriptionId).not.empty;
 	}).timeout(20000);
 
+	step("should list the active subscriptions of the connection", async function () {
+		const activeSubscriptions = async () =>
+			(await customRequest(context.web3, "frontier_activeSubscriptions", [])).result;
+		// Subscriptions are listed and dropped by their task, right after the response to the call.
+		const waitListed = async (id: string, listed: boolean) => {
+			for (let attempt = 0; attempt < 10; attempt++) {
+				if ((await activeSubscriptions()).some((s) => s.id === id) === listed) {
+					return;
+				}
+				await new Promise((resolve) => setTimeout(resolve, 60));
+			}
+		};
+
+		const subscriptionId = (await customRequest(context.web3, "eth_subscribe", ["newHeads"])).result;
+		await waitListed(subscriptionId, true);
+		expect(await activeSubscriptions()).to.deep.include({ id: subscriptionId, kind: "newHeads" });
+
+		expect((await customRequest(context.web3, "eth_unsubscribe", [subscriptionId])).result).to.equal(true);
+		await waitListed(subscriptionId, false);
+		expect((await activeSubscriptions()).map((s) => s.id)).to.not.include(subscriptionId);
+	}).timeout(20000);
+
 	step("should get newHeads stream", async function (done) {
 		subscription = context.web3.eth.subscribe("newBlockHeaders", function (error, result) {});
 		let data = null;